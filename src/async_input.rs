@@ -0,0 +1,246 @@
+//! Reading input from async code, without blocking the executor.
+//!
+//! The blocking reads happen on a thread of their own, which hands each
+//! result over and wakes up whichever task is waiting on it. Nothing here
+//! depends on a particular async runtime.
+
+use std::future::Future;
+use std::io::{self, Read};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use reader::{ArgfReader, DEFAULT_CAPACITY};
+use Lines;
+
+/// Input which can be read from async code, from
+/// [`input_async()`](fn.input_async.html).
+///
+/// [`poll_read()`](#method.poll_read) has the same signature as
+/// `futures::io::AsyncRead::poll_read()`, so wrapping this up to use with a
+/// runtime's own traits only takes a few lines.
+pub struct AsyncInput {
+  chunks: Background<io::Result<Vec<u8>>>,
+  chunk: Vec<u8>,
+  pos: usize
+}
+
+impl AsyncInput {
+  pub(crate) fn new(mut reader: ArgfReader) -> Self {
+    let chunks = Background::spawn(move |send| {
+      let mut buf = vec![0; DEFAULT_CAPACITY];
+
+      loop {
+        let chunk = match reader.read(&mut buf) {
+          Ok(0) => return,
+          Ok(read) => Ok(buf[..read].to_vec()),
+          Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+          Err(err) => Err(err)
+        };
+
+        if !send(chunk) {
+          return;
+        }
+      }
+    });
+
+    AsyncInput { chunks, chunk: Vec::new(), pos: 0 }
+  }
+
+  /// Try to read some input into `buf`, giving back how much was read, or 0
+  /// at the end of all input. If nothing's ready yet, the task in `cx` gets
+  /// woken up once it is.
+  pub fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+    let this = self.get_mut();
+
+    if this.pos == this.chunk.len() {
+      match this.chunks.poll_next(cx) {
+        Poll::Pending => return Poll::Pending,
+        Poll::Ready(None) => return Poll::Ready(Ok(0)),
+        Poll::Ready(Some(Err(err))) => return Poll::Ready(Err(err)),
+        Poll::Ready(Some(Ok(chunk))) => {
+          this.chunk = chunk;
+          this.pos = 0;
+        }
+      }
+    }
+
+    let len = buf.len().min(this.chunk.len() - this.pos);
+    buf[..len].copy_from_slice(&this.chunk[this.pos..this.pos + len]);
+    this.pos += len;
+
+    Poll::Ready(Ok(len))
+  }
+
+  /// Read some input into `buf`, like `Read::read()`, but as a future.
+  pub fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> ReadFuture<'a> {
+    ReadFuture { input: self, buf }
+  }
+}
+
+/// The future from [`AsyncInput::read()`](struct.AsyncInput.html#method.read).
+pub struct ReadFuture<'a> {
+  input: &'a mut AsyncInput,
+  buf: &'a mut [u8]
+}
+
+impl<'a> Future for ReadFuture<'a> {
+  type Output = io::Result<usize>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let this = self.get_mut();
+
+    Pin::new(&mut *this.input).poll_read(cx, this.buf)
+  }
+}
+
+/// A stream of all lines of input, for async code, from
+/// [`input_lines_stream()`](fn.input_lines_stream.html).
+///
+/// [`poll_next()`](#method.poll_next) has the same signature as
+/// `futures::Stream::poll_next()`, so this can be wrapped up as a `Stream`
+/// in a few lines.
+pub struct AsyncLines {
+  lines: Background<io::Result<String>>
+}
+
+impl AsyncLines {
+  pub(crate) fn new(lines: Lines) -> Self {
+    let lines = Background::spawn(move |send| {
+      for line in lines {
+        if !send(line) {
+          return;
+        }
+      }
+    });
+
+    AsyncLines { lines }
+  }
+
+  /// Try to get the next line, or `None` at the end of all input. If it
+  /// isn't ready yet, the task in `cx` gets woken up once it is.
+  pub fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<io::Result<String>>> {
+    self.get_mut().lines.poll_next(cx)
+  }
+
+  /// Get the next line, like `Iterator::next()`, but as a future.
+  pub fn next_line(&mut self) -> NextLine<'_> {
+    NextLine { lines: self }
+  }
+}
+
+/// The future from [`AsyncLines::next_line()`](struct.AsyncLines.html#method.next_line).
+pub struct NextLine<'a> {
+  lines: &'a mut AsyncLines
+}
+
+impl<'a> Future for NextLine<'a> {
+  type Output = Option<io::Result<String>>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    Pin::new(&mut *self.get_mut().lines).poll_next(cx)
+  }
+}
+
+/// Values made on a thread of their own, one at a time, handed over as
+/// they're asked for.
+pub(crate) struct Background<T> {
+  shared: Arc<Shared<T>>,
+  finished: bool
+}
+
+struct Shared<T> {
+  slot: Mutex<Slot<T>>,
+  taken: Condvar
+}
+
+struct Slot<T> {
+  /// The next value, or `Some(None)` once there aren't any more.
+  next: Option<Option<T>>,
+  waker: Option<Waker>,
+  dropped: bool
+}
+
+impl<T: Send + 'static> Background<T> {
+  /// Run `produce` on a new thread. It gets a function to send each value
+  /// with, which waits for the last one to be taken first, and gives back
+  /// `false` once nobody's listening any more.
+  pub(crate) fn spawn<F>(produce: F) -> Self where
+    F: FnOnce(&mut dyn FnMut(T) -> bool) + Send + 'static
+  {
+    let shared = Arc::new(Shared {
+      slot: Mutex::new(Slot { next: None, waker: None, dropped: false }),
+      taken: Condvar::new()
+    });
+    let producing = Finish(Arc::clone(&shared));
+
+    thread::spawn(move || {
+      let mut send = |value| producing.0.put(Some(value));
+      produce(&mut send);
+    });
+
+    Background { shared, finished: false }
+  }
+}
+
+impl<T> Background<T> {
+  pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+    if self.finished {
+      return Poll::Ready(None);
+    }
+
+    let mut slot = self.shared.slot.lock().unwrap_or_else(PoisonError::into_inner);
+
+    match slot.next.take() {
+      Some(next) => {
+        self.finished = next.is_none();
+        self.shared.taken.notify_one();
+        Poll::Ready(next)
+      },
+      None => {
+        slot.waker = Some(cx.waker().clone());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+impl<T> Shared<T> {
+  fn put(&self, next: Option<T>) -> bool {
+    let mut slot = self.slot.lock().unwrap_or_else(PoisonError::into_inner);
+
+    while slot.next.is_some() && !slot.dropped {
+      slot = self.taken.wait(slot).unwrap_or_else(PoisonError::into_inner);
+    }
+
+    if slot.dropped {
+      return false;
+    }
+
+    slot.next = Some(next);
+
+    if let Some(waker) = slot.waker.take() {
+      waker.wake();
+    }
+
+    true
+  }
+}
+
+impl<T> Drop for Background<T> {
+  fn drop(&mut self) {
+    self.shared.slot.lock().unwrap_or_else(PoisonError::into_inner).dropped = true;
+    self.shared.taken.notify_one();
+  }
+}
+
+/// Says there aren't any more values once the producer's done with, even if
+/// it panicked, so that nothing waits on it forever.
+struct Finish<T>(Arc<Shared<T>>);
+
+impl<T> Drop for Finish<T> {
+  fn drop(&mut self) {
+    self.0.put(None);
+  }
+}
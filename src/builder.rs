@@ -0,0 +1,1451 @@
+use std::io::{self, Read, Write, IsTerminal, StdinLock};
+use std::cell::RefCell;
+use std::fs;
+use std::ffi::OsStr;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Formatter};
+use std::time::Duration;
+use std::iter::{self, Peekable};
+
+use reader::{ArgfReader, BannerCallback, FileOpenCallback, SharedStdin, Source, SourceReader, Tee, TeeWriter, DEFAULT_CAPACITY};
+use parallel::map_sources;
+use seekable::{SeekableArgf, SeekableFile};
+use {InputFile, InputReport, InputSource, ResolvedSource};
+use {FailReadFileError, InputError, InputErrorKind, InputReadError, TryIterator};
+
+/// The usual paths for `stdin` on Unix, which
+/// [`InputBuilder::dev_stdin()`](struct.InputBuilder.html#method.dev_stdin)
+/// adds as aliases.
+pub const DEV_STDIN_ALIASES: &[&str] = &["/dev/stdin", "/dev/fd/0"];
+
+/// The environment variable which
+/// [`InputBuilder::env_stdin_alias()`](struct.InputBuilder.html#method.env_stdin_alias)
+/// is usually given.
+pub const STDIN_ALIAS_VAR: &str = "ARG_INPUT_STDIN_ALIAS";
+
+#[cfg(feature = "glob")]
+use glob;
+#[cfg(all(feature = "glob", feature = "http"))]
+use http;
+#[cfg(feature = "gzip")]
+use gzip::GzDecoder;
+#[cfg(feature = "zstd")]
+use zstd::ZstdDecoder;
+#[cfg(feature = "bzip2")]
+use bzip2::Bzip2Decoder;
+#[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+use sniff::AutoDecoder;
+use walk::walk;
+#[cfg(feature = "mmap")]
+use mmap::{Chunk, MmapInput};
+use names::{split_name_lines, split_names};
+use open::{is_a_directory, Custom, Opener};
+
+/// What to sort input files by, for
+/// [`InputBuilder::sort()`](struct.InputBuilder.html#method.sort).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+  /// By name, comparing the names as given.
+  Name,
+  /// Oldest first.
+  ModifiedTime,
+  /// Smallest first.
+  Size
+}
+
+/// Configure how input gets read, for when the defaults used by
+/// [`input()`](fn.input.html) aren't what you want.
+///
+/// ```no_run
+/// use arg_input::InputBuilder;
+///
+/// let reader = InputBuilder::new()
+///   .skip_missing(true)
+///   .buffer_capacity(64 * 1024)
+///   .build(&["a.txt", "b.txt"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct InputBuilder {
+  stdin_aliases: Vec<String>,
+  stdin_alias_disabled: bool,
+  skip_missing: bool,
+  buffer_capacity: usize,
+  lazy: bool,
+  strip_bom: bool,
+  refuse_terminal_stdin: bool,
+  require_inputs: bool,
+  recursive: bool,
+  follow_symlinks: bool,
+  response_files: bool,
+  dedup: bool,
+  respect_double_dash: bool,
+  sort: Option<SortKey>,
+  head_bytes_per_file: Option<u64>,
+  on_file_open: Option<Callback>,
+  banner: Option<Banner>,
+  tee: Option<TeeTarget>,
+  ignore_tee_errors: bool,
+  opener: Opener,
+  #[cfg(feature = "gzip")]
+  gzip_stdin: bool,
+  #[cfg(feature = "zstd")]
+  zstd_stdin: bool,
+  #[cfg(feature = "bzip2")]
+  bzip2_stdin: bool,
+  #[cfg(feature = "glob")]
+  glob: bool
+}
+
+thread_local! {
+  /// The `stdin` lock that `build_locked()` is holding on this thread.
+  static STDIN_LOCK: RefCell<Option<StdinLock<'static>>> = const { RefCell::new(None) };
+}
+
+/// Reads `stdin` through the lock `build_locked()` is holding, if it's
+/// holding one on this thread, rather than trying to lock it again.
+/// Anywhere else, it waits for the lock like any other read from `stdin`.
+struct LockedStdin;
+
+impl Read for LockedStdin {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    STDIN_LOCK.with(|lock| match *lock.borrow_mut() {
+      Some(ref mut lock) => lock.read(buf),
+      None => io::stdin().read(buf)
+    })
+  }
+}
+
+/// Keeps `stdin` locked in `STDIN_LOCK` until it's dropped, even if the
+/// caller panics.
+struct HeldStdinLock {
+  /// Whether this took the lock, rather than finding a `build_locked()`
+  /// further up the stack already holding it.
+  took: bool
+}
+
+impl HeldStdinLock {
+  fn take() -> Self {
+    let took = STDIN_LOCK.with(|lock| {
+      let mut lock = lock.borrow_mut();
+      let took = lock.is_none();
+
+      if took {
+        *lock = Some(io::stdin().lock());
+      }
+      took
+    });
+
+    HeldStdinLock { took }
+  }
+}
+
+impl Drop for HeldStdinLock {
+  fn drop(&mut self) {
+    if self.took {
+      STDIN_LOCK.with(|lock| lock.borrow_mut().take());
+    }
+  }
+}
+
+/// Lets `InputBuilder` stay `Debug`, even though closures aren't.
+#[derive(Clone)]
+struct Callback(FileOpenCallback);
+
+impl Debug for Callback {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    write!(f, "Callback")
+  }
+}
+
+#[derive(Clone)]
+struct Banner(BannerCallback);
+
+impl Debug for Banner {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    write!(f, "Banner")
+  }
+}
+
+#[derive(Clone)]
+struct TeeTarget(TeeWriter);
+
+impl Debug for TeeTarget {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    write!(f, "TeeTarget")
+  }
+}
+
+impl Default for InputBuilder {
+  fn default() -> Self {
+    InputBuilder {
+      stdin_aliases: vec!["-".to_string()],
+      stdin_alias_disabled: false,
+      skip_missing: false,
+      buffer_capacity: DEFAULT_CAPACITY,
+      lazy: false,
+      strip_bom: false,
+      refuse_terminal_stdin: false,
+      require_inputs: false,
+      recursive: false,
+      follow_symlinks: false,
+      response_files: false,
+      dedup: false,
+      respect_double_dash: false,
+      sort: None,
+      head_bytes_per_file: None,
+      on_file_open: None,
+      banner: None,
+      tee: None,
+      ignore_tee_errors: false,
+      opener: Opener::default(),
+      #[cfg(feature = "gzip")]
+      gzip_stdin: false,
+      #[cfg(feature = "zstd")]
+      zstd_stdin: false,
+      #[cfg(feature = "bzip2")]
+      bzip2_stdin: false,
+      #[cfg(feature = "glob")]
+      glob: false
+    }
+  }
+}
+
+impl InputBuilder {
+  /// A builder with the same behavior as [`input()`](fn.input.html).
+  pub fn new() -> Self {
+    InputBuilder::default()
+  }
+
+  /// Use a different argument to stand in for `stdin`, instead of `-`.
+  /// This replaces any other aliases.
+  pub fn stdin_alias(mut self, alias: &str) -> Self {
+    self.stdin_aliases = vec![alias.to_string()];
+    self
+  }
+
+  /// Set every argument which stands in for `stdin`. Defaults to just `-`.
+  /// If this is empty, every argument gets treated as a file.
+  pub fn stdin_aliases<I, S>(mut self, aliases: I) -> Self where
+    I: IntoIterator<Item=S>,
+    S: AsRef<str>
+  {
+    self.stdin_aliases = aliases.into_iter().map(|alias| alias.as_ref().to_string()).collect();
+    self
+  }
+
+  /// Treat every argument as a file, even `-` and any other aliases for
+  /// `stdin`, so that a file called `-` can be read by that name. `stdin`
+  /// is still read when there aren't any arguments at all.
+  ///
+  /// Without this, a file called `-` can still be read as `./-`, since only
+  /// arguments which are exactly an alias stand in for `stdin`.
+  pub fn stdin_alias_disabled(mut self, disabled: bool) -> Self {
+    self.stdin_alias_disabled = disabled;
+    self
+  }
+
+  /// Also treat `/dev/stdin` and `/dev/fd/0` as `stdin`, instead of opening
+  /// them as files. They then share the same `stdin` as `-`, and work even
+  /// on platforms which don't have those paths.
+  pub fn dev_stdin(mut self, dev_stdin: bool) -> Self {
+    for alias in DEV_STDIN_ALIASES {
+      self.stdin_aliases.retain(|existing| existing != alias);
+
+      if dev_stdin {
+        self.stdin_aliases.push(alias.to_string());
+      }
+    }
+    self
+  }
+
+  /// Also treat whatever the environment variable `var` is set to as
+  /// `stdin`, on top of the existing aliases, so that scripts can pick their
+  /// own name for it without the program changing how it parses arguments.
+  /// Nothing changes if the variable isn't set, or is empty.
+  ///
+  /// ```no_run
+  /// use arg_input::{InputBuilder, STDIN_ALIAS_VAR};
+  ///
+  /// let reader = InputBuilder::new()
+  ///   .env_stdin_alias(STDIN_ALIAS_VAR)
+  ///   .build(&["a.txt", "STDIN"]);
+  /// ```
+  pub fn env_stdin_alias(mut self, var: &str) -> Self {
+    if let Some(alias) = env::var_os(var) {
+      let alias = alias.to_string_lossy().into_owned();
+
+      if !alias.is_empty() && !self.stdin_aliases.contains(&alias) {
+        self.stdin_aliases.push(alias);
+      }
+    }
+    self
+  }
+
+  /// Read arguments like `/dev/fd/3` or `fd:3` straight from that file
+  /// descriptor, say one handed over by a launcher, instead of opening them
+  /// by name.
+  ///
+  /// The descriptor is left open, and owned by whoever handed it over; only
+  /// a duplicate of it gets read from, and closed afterwards. Apart from
+  /// that, it's read like any other file, so reading it moves the original
+  /// along too. `/dev/fd/0` is still `stdin` if
+  /// [`dev_stdin()`](#method.dev_stdin) is on.
+  ///
+  /// Only available on Unix.
+  #[cfg(unix)]
+  pub fn fd_args(mut self, fd_args: bool) -> Self {
+    self.opener.fd_args = fd_args;
+    self
+  }
+
+  /// Leave out files which can't be opened, instead of failing. If none of
+  /// the files can be opened, the result is empty, and doesn't fall back to
+  /// reading `stdin`.
+  ///
+  /// Use [`build_skipping()`](#method.build_skipping) instead if you want to
+  /// know which files were left out.
+  pub fn skip_missing(mut self, skip: bool) -> Self {
+    self.skip_missing = skip;
+    self
+  }
+
+  /// How many bytes the reader should buffer at a time. Defaults to 8 KiB.
+  pub fn buffer_capacity(mut self, capacity: usize) -> Self {
+    self.buffer_capacity = capacity;
+    self
+  }
+
+  /// Only open each file once reading reaches it.
+  ///
+  /// See [`input_lazy()`](fn.input_lazy.html) for how this changes errors.
+  /// Since nothing gets opened up front, `skip_missing` has no effect on
+  /// lazy input.
+  pub fn lazy(mut self, lazy: bool) -> Self {
+    self.lazy = lazy;
+    self
+  }
+
+  /// Strip off a UTF-8 byte order mark at the very start of the input, if
+  /// there is one. Only the first source gets checked; byte order marks at
+  /// the start of any later files are left alone.
+  pub fn strip_bom(mut self, strip: bool) -> Self {
+    self.strip_bom = strip;
+    self
+  }
+
+  /// Decompress `stdin` as gzip data. Files ending in `.gz` always get
+  /// decompressed, but `stdin` is read as-is unless this is set.
+  ///
+  /// Only available with the `gzip` feature.
+  #[cfg(feature = "gzip")]
+  pub fn gzip_stdin(mut self, gzip: bool) -> Self {
+    self.gzip_stdin = gzip;
+    self
+  }
+
+  /// Decompress `stdin` as zstd data. Files ending in `.zst` always get
+  /// decompressed, but `stdin` is read as-is unless this is set.
+  ///
+  /// Only available with the `zstd` feature.
+  #[cfg(feature = "zstd")]
+  pub fn zstd_stdin(mut self, zstd: bool) -> Self {
+    self.zstd_stdin = zstd;
+    self
+  }
+
+  /// Decompress `stdin` as bzip2 data. Files ending in `.bz2` always get
+  /// decompressed, but `stdin` is read as-is unless this is set.
+  ///
+  /// Only available with the `bzip2` feature.
+  #[cfg(feature = "bzip2")]
+  pub fn bzip2_stdin(mut self, bzip2: bool) -> Self {
+    self.bzip2_stdin = bzip2;
+    self
+  }
+
+  /// Work out whether each input is compressed from its first few bytes,
+  /// rather than from its name, so that renamed files and compressed data
+  /// piped into `stdin` get decompressed too. Anything that isn't in a format
+  /// we can decompress is read as-is, whatever it's called.
+  ///
+  /// Only available with the `gzip`, `zstd` or `bzip2` features.
+  #[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+  pub fn auto_decompress(mut self, auto: bool) -> Self {
+    self.opener.auto_decompress = auto;
+    self
+  }
+
+  /// When there are no inputs, and `stdin` is an interactive terminal, fail
+  /// with `InputErrorKind::StdinIsTerminal` instead of reading from it. This
+  /// avoids seemingly hanging forever when someone forgets to give any files.
+  ///
+  /// Only the fallback to `stdin` is affected; an explicit `-` still reads
+  /// from the terminal.
+  pub fn refuse_terminal_stdin(mut self, refuse: bool) -> Self {
+    self.refuse_terminal_stdin = refuse;
+    self
+  }
+
+  /// When there are no inputs, fail with `InputErrorKind::NoInputs`, instead
+  /// of reading from `stdin`, for when files have to be given explicitly.
+  /// An explicit `-` still reads from `stdin`.
+  ///
+  /// [`build_partial()`](#method.build_partial) gives back no reader,
+  /// [`build_skipping()`](#method.build_skipping) an empty one, and
+  /// [`build_report()`](#method.build_report) an empty report, without any
+  /// errors. The same goes for
+  /// [`refuse_terminal_stdin()`](#method.refuse_terminal_stdin).
+  pub fn require_inputs(mut self, require: bool) -> Self {
+    self.require_inputs = require;
+    self
+  }
+
+  /// Expand wildcards like `*.txt` in each argument, reading every matching
+  /// file in sorted order. This is for platforms like Windows, where the
+  /// shell leaves wildcards for the program to deal with.
+  ///
+  /// An argument with wildcards which matches nothing fails with
+  /// `ErrorKind::NotFound`, rather than being opened as-is. Arguments
+  /// without wildcards, and `stdin` aliases, are used as they are. Every
+  /// file from one pattern has that pattern's `arg_index`.
+  ///
+  /// Only available with the `glob` feature.
+  #[cfg(feature = "glob")]
+  pub fn glob(mut self, glob: bool) -> Self {
+    self.glob = glob;
+    self
+  }
+
+  /// Read every file inside directory arguments, like `grep -r`, instead of
+  /// failing on them. Directories are walked depth-first, in sorted order,
+  /// and only regular files get read. Every file from one directory has that
+  /// directory's `arg_index`, and so does the error for anything inside it
+  /// which couldn't be listed, which names that entry instead.
+  pub fn recursive(mut self, recursive: bool) -> Self {
+    self.recursive = recursive;
+    self
+  }
+
+  /// When reading directories recursively, follow symbolic links found inside
+  /// them, instead of skipping them. Links which lead back into a directory
+  /// that's already being walked are still skipped.
+  ///
+  /// A directory argument which is itself a link always gets followed.
+  pub fn follow_symlinks(mut self, follow: bool) -> Self {
+    self.follow_symlinks = follow;
+    self
+  }
+
+  /// Treat any argument starting with `@` as a response file, like `gcc`
+  /// does: the file gets read as a list of more inputs, one per line, which
+  /// take its place. The response file itself isn't part of the input.
+  /// Lines can end in `\r\n` as well as `\n`, as they often do on Windows.
+  ///
+  /// Every input listed in a response file has that file's `arg_index`, and
+  /// a response file which can't be read fails like any other file.
+  pub fn response_files(mut self, response_files: bool) -> Self {
+    self.response_files = response_files;
+    self
+  }
+
+  /// Treat a bare `--` argument like `getopt` does: it gets left out, and
+  /// every argument after it is a filename, whatever it looks like. So a `-`
+  /// after it opens a file called `-` instead of reading `stdin`, and it
+  /// isn't expanded as a response file or a glob pattern either. Only the
+  /// first `--` is special; any after it are filenames too.
+  ///
+  /// If `--` is the only argument, it counts as no arguments at all: input
+  /// comes from `stdin`, or fails if
+  /// [`require_inputs()`](#method.require_inputs) or
+  /// [`refuse_terminal_stdin()`](#method.refuse_terminal_stdin) says so.
+  pub fn respect_double_dash(mut self, respect: bool) -> Self {
+    self.respect_double_dash = respect;
+    self
+  }
+
+  /// Only read each file once, even if it's given more than once, or under
+  /// different names, like `a.txt` and `./a.txt`, or a link to it. Later
+  /// copies get left out, as if they weren't there. Files are compared after
+  /// resolving their names with `fs::canonicalize()`; anything that can't be
+  /// resolved is left in as it is. `stdin` is never left out.
+  pub fn dedup(mut self, dedup: bool) -> Self {
+    self.dedup = dedup;
+    self
+  }
+
+  /// Sort the files before reading them, instead of reading them in the
+  /// order they were given, for when that order can't be relied on, like
+  /// with some shells' wildcards. Files which tie, or whose size or
+  /// modification time can't be found out, keep their original order, after
+  /// the ones which could be.
+  ///
+  /// `stdin` stays exactly where it was given; only the files around it get
+  /// moved. Files found by [`recursive()`](#method.recursive) and
+  /// [`glob()`](#method.glob) get sorted along with everything else.
+  pub fn sort(mut self, key: SortKey) -> Self {
+    self.sort = Some(key);
+    self
+  }
+
+  /// Read at most `max_bytes` bytes from each file, then move on to the
+  /// next one, like `head -c` on every file separately rather than on all
+  /// of them together. Files shorter than that get read in full.
+  ///
+  /// Banners from [`with_banners()`](#method.with_banners) don't count
+  /// towards the limit. This has no effect on seekable, memory-mapped, or
+  /// separately opened input.
+  pub fn head_bytes_per_file(mut self, max_bytes: u64) -> Self {
+    self.head_bytes_per_file = Some(max_bytes);
+    self
+  }
+
+  /// Call `callback` with the name of each file when reading first gets to
+  /// it, rather than when it's opened, say for printing progress. `stdin`
+  /// gets the name `-`.
+  pub fn on_file_open<F>(mut self, callback: F) -> Self where
+    F: Fn(&Path) + Send + Sync + 'static
+  {
+    self.on_file_open = Some(Callback(Arc::new(callback)));
+    self
+  }
+
+  /// Put a banner line before the contents of each source, like the
+  /// `==> file <==` lines from `head` and `tail`, with the text from calling
+  /// `banner` on the name of the source. `stdin` gets the name `-`. A newline
+  /// gets added after the banner.
+  ///
+  /// Banners show up in everything read from the input, lines or bytes, but
+  /// only when there's more than one source, like with `head`. Lines which
+  /// span files can run into the banner after them, if a file doesn't end
+  /// in a newline. Empty files still get a banner, with nothing after it.
+  ///
+  /// ```no_run
+  /// use arg_input::InputBuilder;
+  ///
+  /// let reader = InputBuilder::new()
+  ///   .with_banners(|path| format!("==> {} <==", path.display()))
+  ///   .build(&["a.txt", "b.txt"]);
+  /// ```
+  pub fn with_banners<F>(mut self, banner: F) -> Self where
+    F: Fn(&Path) -> String + Send + Sync + 'static
+  {
+    self.banner = Some(Banner(Arc::new(banner)));
+    self
+  }
+
+  /// Copy everything read from the input into `writer` too, like `tee`, say
+  /// for keeping a record of exactly what got consumed. Bytes are copied
+  /// once they're read, not when they're only peeked at, so anything read
+  /// twice after a [`rewind()`](struct.ArgfReader.html#method.rewind) gets
+  /// copied twice. Banners get copied, but a stripped byte order mark
+  /// doesn't.
+  ///
+  /// If writing to `writer` fails, the read which couldn't be copied still
+  /// gives back what it read, so nothing is lost, and the next read gives
+  /// back the error, unless
+  /// [`ignore_tee_errors()`](#method.ignore_tee_errors) is set. Through
+  /// `BufRead`, it's the next `fill_buf()`.
+  /// Every reader built from this builder writes to the same `writer`.
+  pub fn tee(mut self, writer: Box<dyn Write + Send>) -> Self {
+    self.tee = Some(TeeTarget(Arc::new(Mutex::new(writer))));
+    self
+  }
+
+  /// Carry on reading when writing to the [`tee()`](#method.tee) fails,
+  /// instead of giving back the error. Defaults to `false`.
+  pub fn ignore_tee_errors(mut self, ignore: bool) -> Self {
+    self.ignore_tee_errors = ignore;
+    self
+  }
+
+  /// Use `open` to turn each file argument into a reader, instead of opening
+  /// it as a file, say for reading from an archive, or from fixtures in
+  /// memory. Arguments standing in for `stdin` are still read from `stdin`.
+  ///
+  /// Anything read as a stream goes through `open`, including files opened
+  /// lazily or again by [`rewind()`](struct.ArgfReader.html#method.rewind),
+  /// as does [`build_mmap()`](#method.build_mmap), which reads what it gives
+  /// back into memory. Since they need real files,
+  /// [`build_seekable()`](#method.build_seekable) and
+  /// [`build_files()`](#method.build_files) still open files as usual, and so
+  /// do `recursive()` and `glob()` when looking for them. Nothing gets
+  /// decompressed, other than by `open` itself.
+  pub fn opener<F>(mut self, open: F) -> Self where
+    F: Fn(&Path) -> io::Result<Box<dyn Read + Send>> + Send + Sync + 'static
+  {
+    self.opener.custom = Some(Custom(Arc::new(open)));
+    self
+  }
+
+  /// If opening a file fails with one of the
+  /// [`TRANSIENT_ERRORS`](constant.TRANSIENT_ERRORS.html), try again up to
+  /// `attempts` more times, waiting `delay` before the first retry and twice
+  /// as long before each one after that. Any other error fails straight away.
+  /// Defaults to no retries.
+  pub fn retry(mut self, attempts: u32, delay: Duration) -> Self {
+    self.opener.retry.attempts = attempts;
+    self.opener.retry.delay = delay;
+    self
+  }
+
+  /// Change which kinds of error [`retry()`](#method.retry) tries again on.
+  pub fn retry_on<I>(mut self, kinds: I) -> Self where
+    I: IntoIterator<Item=io::ErrorKind>
+  {
+    self.opener.retry.kinds = kinds.into_iter().collect();
+    self
+  }
+
+  /// Open all the given inputs, and chain them together.
+  ///
+  /// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+  pub fn build<I, J, S>(&self, inputs: I) -> Result<ArgfReader, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+    self.check_stdin_fallback(iter.len())?;
+
+    self.build_with_stdin(iter, Box::new(io::stdin()))
+  }
+
+  /// Build a reader like [`build()`](#method.build) does, and hand it to
+  /// `f`, holding `stdin` locked for as long as `f` runs if any of the
+  /// sources is `stdin`.
+  ///
+  /// See [`input_locked()`](fn.input_locked.html).
+  pub fn build_locked<I, J, S, F, T>(&self, inputs: I, f: F) -> Result<T, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>,
+    F: FnOnce(ArgfReader) -> T
+  {
+    let iter = self.inputs(inputs);
+    self.check_stdin_fallback(iter.len())?;
+
+    let reader = self.build_with_stdin(iter, Box::new(LockedStdin))?;
+
+    // Taking the lock again on this thread would deadlock, so the reader
+    // reads through this one instead, for as long as it's held.
+    let _held = if reader.used_stdin() { Some(HeldStdinLock::take()) } else { None };
+
+    Ok(f(reader))
+  }
+
+  /// Like [`build()`](#method.build), but take any iterator of inputs, not
+  /// just ones which know their length up front.
+  ///
+  /// See [`input_any()`](fn.input_any.html).
+  pub fn build_any<I, S>(&self, inputs: I) -> Result<ArgfReader, InputError> where
+    I: IntoIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let mut inputs = inputs.into_iter();
+
+    let first = match inputs.next() {
+      Some(first) => first,
+      None => return self.build(iter::empty::<S>())
+    };
+
+    let mut rest = inputs.peekable();
+
+    // With only one input there's a length after all, and it might be a
+    // lone `--`.
+    if rest.peek().is_none() {
+      return self.build(iter::once(first));
+    }
+
+    self.build_from(iter::once(first).chain(rest), Box::new(io::stdin()))
+  }
+
+  /// Like [`build()`](#method.build), but use the given reader in place of
+  /// the real `stdin`.
+  pub fn build_with_stdin<I, J, S>(&self, inputs: I, stdin: Box<dyn Read + Send>) -> Result<ArgfReader, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+    self.check_inputs(iter.len())?;
+
+    if iter.len() == 0 {
+      return Ok(self.reader(vec![stdin_source(0)], stdin));
+    }
+
+    self.build_from(iter, stdin)
+  }
+
+  /// Open inputs which are known not to be empty, and chain them together.
+  fn build_from<I, S>(&self, inputs: I, stdin: Box<dyn Read + Send>) -> Result<ArgfReader, InputError> where
+    I: Iterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let mut len = 0;
+    let (args, errors) = self.args(inputs.inspect(|_| len += 1));
+
+    self.build_from_args(args, errors, len, stdin)
+  }
+
+  /// Open arguments which have already been expanded, out of `len` inputs,
+  /// and chain them together.
+  fn build_from_args(&self, args: Vec<Arg>, errors: Vec<FailReadFileError>, len: usize, stdin: Box<dyn Read + Send>) -> Result<ArgfReader, InputError> {
+    if self.lazy {
+      return Ok(self.lazy_reader_from(args, errors, stdin));
+    }
+
+    let (sources, errors) = self.open_expanded(args, errors);
+
+    if !errors.is_empty() && !self.skip_missing {
+      return Err(InputError::from(errors).of_inputs(len));
+    }
+
+    Ok(self.reader(sources, stdin))
+  }
+
+  /// Chain together a mix of files, readers, and `stdin`, in order.
+  ///
+  /// See [`input_mixed()`](fn.input_mixed.html).
+  pub fn build_mixed<I>(&self, sources: I) -> Result<ArgfReader, InputError> where
+    I: IntoIterator<Item=InputSource>
+  {
+    let sources: Vec<InputSource> = sources.into_iter().collect();
+    let len = sources.len();
+    self.check_stdin_fallback(len)?;
+
+    if len == 0 {
+      return Ok(self.reader(vec![stdin_source(0)], Box::new(io::stdin())));
+    }
+
+    let mut opened = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, source) in sources.into_iter().enumerate() {
+      match source {
+        InputSource::Path(path) => {
+          if self.lazy {
+            opened.push(Source::new(Some(path), index, SourceReader::Pending));
+            continue;
+          }
+
+          match self.opener.open(&path) {
+            Ok(file) => opened.push(Source::new(Some(path), index, SourceReader::Open(file))),
+            Err(err) => errors.push(FailReadFileError::new(index, &path, err))
+          }
+        },
+        InputSource::Reader(reader) => opened.push(Source::new(None, index, SourceReader::Open(reader))),
+        InputSource::Stdin => opened.push(stdin_source(index))
+      }
+    }
+
+    if !errors.is_empty() && !self.skip_missing {
+      return Err(InputError::from(errors).of_inputs(len));
+    }
+
+    Ok(self.reader(opened, Box::new(io::stdin())))
+  }
+
+  /// Like [`build()`](#method.build), but give back a reader over whichever
+  /// files could be opened, along with the errors for the ones that couldn't.
+  ///
+  /// See [`input_partial()`](fn.input_partial.html).
+  pub fn build_partial<I, J, S>(&self, inputs: I) -> (Option<ArgfReader>, Vec<FailReadFileError>) where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let stdin = Box::new(io::stdin());
+    let iter = self.inputs(inputs);
+
+    if self.check_stdin_fallback(iter.len()).is_err() {
+      return (None, Vec::new());
+    }
+
+    if self.lazy {
+      return (Some(self.build_lazy(iter, stdin)), Vec::new());
+    }
+
+    if iter.len() == 0 {
+      return (Some(self.reader(vec![stdin_source(0)], stdin)), Vec::new());
+    }
+
+    let (sources, errors) = self.open_args(iter);
+
+    if sources.is_empty() {
+      (None, errors)
+    } else {
+      (Some(self.reader(sources, stdin)), errors)
+    }
+  }
+
+  /// Like [`build_partial()`](#method.build_partial), but if none of the files
+  /// could be opened, give back an empty reader instead of nothing.
+  ///
+  /// See [`input_skipping()`](fn.input_skipping.html).
+  pub fn build_skipping<I, J, S>(&self, inputs: I) -> (ArgfReader, Vec<FailReadFileError>) where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    match self.build_partial(inputs) {
+      (Some(reader), errors) => (reader, errors),
+      (None, errors) => (self.reader(Vec::new(), Box::new(io::stdin())), errors)
+    }
+  }
+
+  /// Open all the given inputs so that they can be seeked through.
+  ///
+  /// See [`input_seekable()`](fn.input_seekable.html).
+  pub fn build_seekable<I, J, S>(&self, inputs: I) -> Result<SeekableArgf, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+    self.check_inputs(iter.len())?;
+
+    if iter.len() == 0 {
+      return Err(InputError::from(vec![stdin_not_seekable(0)]));
+    }
+
+    let len = iter.len();
+    let (args, errors) = self.args(iter);
+
+    if !errors.is_empty() {
+      return Err(InputError::from(errors).of_inputs(len));
+    }
+
+    let files = args.into_iter()
+      .attempt_map(|_, arg| self.open_seekable(&arg))
+      .map_err(|errors| InputError::from(errors).of_inputs(len))?;
+
+    Ok(SeekableArgf::new(files))
+  }
+
+  /// Build a reader for editing the given files in place. Each file has to
+  /// be read as it is on disk, since that's what gets replaced, so anything
+  /// which would be decompressed or fetched is an error instead.
+  ///
+  /// See [`input_inplace()`](fn.input_inplace.html).
+  pub(crate) fn build_inplace<I, J, S>(&self, inputs: I) -> Result<ArgfReader, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let inputs: Vec<PathBuf> = self.inputs(inputs).map(|arg| arg.as_ref().to_path_buf()).collect();
+
+    let errors: Vec<FailReadFileError> = inputs.iter()
+      .enumerate()
+      .filter(|&(_, path)| !self.is_stdin_alias(path) && !self.opener.opens_as_is(path))
+      .map(|(index, path)| {
+        let err = io::Error::new(io::ErrorKind::InvalidInput, "not read as-is, so can't be edited in place");
+        FailReadFileError::new(index, path, err)
+      })
+      .collect();
+
+    if !errors.is_empty() {
+      return Err(InputError::from(errors).of_inputs(inputs.len()));
+    }
+
+    self.build(inputs)
+  }
+
+  /// Map all the given inputs into memory.
+  ///
+  /// See [`input_mmap()`](fn.input_mmap.html).
+  ///
+  /// # Safety
+  ///
+  /// The same as for [`input_mmap()`](fn.input_mmap.html): nothing else may
+  /// truncate or write to any of the files while the `MmapInput` is alive.
+  #[cfg(feature = "mmap")]
+  pub unsafe fn build_mmap<I, J, S>(&self, inputs: I) -> Result<MmapInput, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+    self.check_stdin_fallback(iter.len())?;
+
+    let mut stdin = SharedStdin::new(self.stdin(Box::new(io::stdin())));
+    let mut chunks = Vec::new();
+
+    if iter.len() == 0 {
+      let mut contents = Vec::new();
+      stdin.read_to_end(&mut contents).map_err(|err| vec![FailReadFileError::new(0, Path::new("-"), err)])?;
+      chunks.push((None, Chunk::Read(contents)));
+
+      return Ok(MmapInput::new(chunks));
+    }
+
+    let len = iter.len();
+    let (args, mut errors) = self.args(iter);
+
+    for arg in args {
+      if arg.stdin {
+        let mut contents = Vec::new();
+
+        match stdin.read_to_end(&mut contents) {
+          Ok(_) => chunks.push((None, Chunk::Read(contents))),
+          Err(err) => errors.push(FailReadFileError::new(arg.index, &arg.path, err))
+        }
+      } else {
+        match Chunk::open(&arg.path, &self.opener) {
+          Ok(chunk) => chunks.push((Some(arg.path), chunk)),
+          Err(err) => errors.push(FailReadFileError::new(arg.index, &arg.path, err))
+        }
+      }
+    }
+
+    if !errors.is_empty() {
+      errors.sort_by_key(|err| err.arg_index);
+      return Err(InputError::from(errors).of_inputs(len));
+    }
+
+    Ok(MmapInput::new(chunks))
+  }
+
+  /// Work out which sources would be read, and in what order, without
+  /// opening or reading any of them.
+  ///
+  /// See [`resolve_sources()`](fn.resolve_sources.html).
+  pub fn resolve<I, J, S>(&self, inputs: I) -> Result<Vec<ResolvedSource>, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+    self.check_inputs(iter.len())?;
+
+    if iter.len() == 0 {
+      return Ok(vec![ResolvedSource::Stdin]);
+    }
+
+    let len = iter.len();
+    let (args, errors) = self.args(iter);
+
+    if !errors.is_empty() && !self.skip_missing {
+      return Err(InputError::from(errors).of_inputs(len));
+    }
+
+    Ok(args.into_iter().map(|arg| {
+      if arg.stdin { ResolvedSource::Stdin } else { ResolvedSource::File(arg.path) }
+    }).collect())
+  }
+
+  /// Open all the given inputs, but give back the opened files themselves,
+  /// instead of chaining them together.
+  ///
+  /// See [`input_files()`](fn.input_files.html).
+  pub fn build_files<I, J, S>(&self, inputs: I) -> Result<Vec<InputFile>, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+    self.check_stdin_fallback(iter.len())?;
+
+    if iter.len() == 0 {
+      return Ok(vec![InputFile::Stdin]);
+    }
+
+    let len = iter.len();
+    let (args, mut errors) = self.args(iter);
+    let mut files = Vec::new();
+
+    for arg in args {
+      if arg.stdin {
+        files.push(InputFile::Stdin);
+        continue;
+      }
+
+      match self.opener.open_file(&arg.path) {
+        Ok(file) => files.push(InputFile::File(arg.path, file)),
+        Err(err) => errors.push(FailReadFileError::new(arg.index, &arg.path, err))
+      }
+    }
+
+    if !errors.is_empty() && !self.skip_missing {
+      errors.sort_by_key(|err| err.arg_index);
+      return Err(InputError::from(errors).of_inputs(len));
+    }
+
+    Ok(files)
+  }
+
+  /// Open all the given inputs, giving back a separate reader for each one
+  /// that opened, along with the errors for the ones that didn't.
+  ///
+  /// See [`input_report()`](fn.input_report.html).
+  pub fn build_report<I, J, S>(&self, inputs: I) -> InputReport where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+
+    if self.check_stdin_fallback(iter.len()).is_err() {
+      return InputReport { readers: Vec::new(), errors: Vec::new() };
+    }
+
+    let (sources, errors) = if iter.len() == 0 {
+      (vec![stdin_source(0)], Vec::new())
+    } else {
+      self.open_args(iter)
+    };
+
+    let mut stdin = Some(self.stdin(Box::new(io::stdin())));
+    let readers = sources.into_iter().map(|source| {
+      let path = source.path.unwrap_or_else(|| PathBuf::from("-"));
+      let reader = match source.reader {
+        SourceReader::Open(reader) => reader,
+        SourceReader::Stdin => stdin.take().unwrap_or_else(|| Box::new(io::empty())),
+        SourceReader::Pending | SourceReader::Failed { .. } => Box::new(io::empty())
+      };
+
+      (path, reader)
+    }).collect();
+
+    InputReport { readers, errors }
+  }
+
+  /// Read a list of NUL-separated filenames from `names`, then open all of
+  /// those files, and chain them together.
+  ///
+  /// See [`input_from_name_list()`](fn.input_from_name_list.html).
+  pub fn build_from_name_list<R>(&self, mut names: R) -> Result<ArgfReader, InputReadError> where
+    R: Read
+  {
+    let mut list = Vec::new();
+    names.read_to_end(&mut list)?;
+
+    let names = split_names(&list, 0);
+
+    if names.is_empty() {
+      return Ok(self.reader(Vec::new(), Box::new(io::stdin())));
+    }
+
+    // These are names of files, not arguments, so none of them mean
+    // `stdin`, and none of them get expanded.
+    let len = names.len();
+    let args = names.into_iter()
+      .enumerate()
+      .map(|(index, path)| Arg { index, path, literal: true, stdin: false })
+      .collect();
+    let (args, errors) = self.tidy_args(args, Vec::new());
+
+    Ok(self.build_from_args(args, errors, len, Box::new(io::stdin()))?)
+  }
+
+  /// Open all the given inputs, then call `f` on each one separately, on
+  /// multiple threads at once.
+  ///
+  /// See [`input_par_map()`](fn.input_par_map.html).
+  pub fn par_map<I, J, S, F, T>(&self, inputs: I, f: F) -> Result<Vec<T>, InputError> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>,
+    F: Fn(&mut dyn Read) -> T + Sync,
+    T: Send
+  {
+    let iter = self.inputs(inputs);
+    self.check_stdin_fallback(iter.len())?;
+
+    let stdin = SharedStdin::new(self.stdin(Box::new(io::stdin())));
+
+    if iter.len() == 0 {
+      return Ok(map_sources(vec![stdin_source(0)], stdin, f));
+    }
+
+    let len = iter.len();
+    let (sources, errors) = self.open_args(iter);
+
+    if !errors.is_empty() && !self.skip_missing {
+      return Err(InputError::from(errors).of_inputs(len));
+    }
+
+    Ok(map_sources(sources, stdin, f))
+  }
+
+  /// Like `build()`, but never opens anything up front, so can't fail.
+  pub(crate) fn build_lazy<I, J, S>(&self, inputs: I, stdin: Box<dyn Read + Send>) -> ArgfReader where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let iter = self.inputs(inputs);
+
+    if iter.len() == 0 {
+      self.reader(vec![stdin_source(0)], stdin)
+    } else {
+      self.lazy_reader(iter, stdin)
+    }
+  }
+
+  /// Set up inputs which are known not to be empty to be opened once
+  /// reading gets to each of them.
+  fn lazy_reader<I, S>(&self, inputs: I, stdin: Box<dyn Read + Send>) -> ArgfReader where
+    I: Iterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let (args, errors) = self.args(inputs);
+
+    self.lazy_reader_from(args, errors, stdin)
+  }
+
+  fn lazy_reader_from(&self, args: Vec<Arg>, errors: Vec<FailReadFileError>, stdin: Box<dyn Read + Send>) -> ArgfReader {
+    let mut sources: Vec<Source> = args.into_iter().map(|arg| self.lazy_arg(arg)).collect();
+
+    // Arguments which couldn't be expanded, like patterns which match
+    // nothing, keep their error until reading gets to them.
+    sources.extend(errors.into_iter().map(|err| {
+      let reader = SourceReader::Failed { kind: err.inner.kind(), message: err.inner.to_string(), reported: false };
+      Source::new(Some(PathBuf::from(err.os_filename)), err.arg_index, reader)
+    }));
+    sources.sort_by_key(|source| source.arg_index);
+
+    self.reader(sources, stdin)
+  }
+
+  /// Start going through the inputs. A lone `--` leaves nothing after it,
+  /// so it gets dropped, and falls back to `stdin` just like no arguments at
+  /// all.
+  fn inputs<I, J, S>(&self, inputs: I) -> Peekable<J> where
+    I: IntoIterator<Item=S, IntoIter=J>,
+    J: ExactSizeIterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let mut inputs = inputs.into_iter().peekable();
+
+    if self.respect_double_dash && inputs.len() == 1 && inputs.peek().is_some_and(|arg| arg.as_ref().as_os_str() == "--") {
+      inputs.next();
+    }
+
+    inputs
+  }
+
+  /// Fail if there aren't any inputs, and falling back to `stdin` isn't
+  /// allowed.
+  fn check_inputs(&self, len: usize) -> Result<(), InputError> {
+    if self.require_inputs && len == 0 {
+      Err(InputError::new(InputErrorKind::NoInputs))
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Like `check_inputs()`, for when no inputs would mean reading the real
+  /// `stdin`: also fail if it's a terminal that shouldn't be read from.
+  fn check_stdin_fallback(&self, len: usize) -> Result<(), InputError> {
+    self.check_inputs(len)?;
+
+    if self.refuse_terminal_stdin && len == 0 && io::stdin().is_terminal() {
+      return Err(InputError::new(InputErrorKind::StdinIsTerminal));
+    }
+
+    Ok(())
+  }
+
+  fn reader(&self, sources: Vec<Source>, stdin: Box<dyn Read + Send>) -> ArgfReader {
+    let mut reader = ArgfReader::with_capacity(self.buffer_capacity, sources, self.stdin(stdin));
+    reader.strip_bom = self.strip_bom;
+    reader.on_file_open = self.on_file_open.as_ref().map(|callback| callback.0.clone());
+    reader.banner = self.banner.as_ref().map(|banner| banner.0.clone());
+    reader.tee = self.tee.as_ref().map(|tee| Tee::new(tee.0.clone(), self.ignore_tee_errors));
+    reader.opener = self.opener.clone();
+    reader.set_per_source_limit(self.head_bytes_per_file);
+    reader
+  }
+
+  /// Wrap up `stdin` however it needs to be read.
+  fn stdin(&self, stdin: Box<dyn Read + Send>) -> Box<dyn Read + Send> {
+    #[cfg(feature = "gzip")]
+    {
+      if self.gzip_stdin {
+        return Box::new(GzDecoder::new(stdin));
+      }
+    }
+
+    #[cfg(feature = "zstd")]
+    {
+      if self.zstd_stdin {
+        return Box::new(ZstdDecoder::new(stdin));
+      }
+    }
+
+    #[cfg(feature = "bzip2")]
+    {
+      if self.bzip2_stdin {
+        return Box::new(Bzip2Decoder::new(stdin));
+      }
+    }
+
+    #[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+    {
+      if self.opener.auto_decompress {
+        return Box::new(AutoDecoder::new(stdin));
+      }
+    }
+
+    stdin
+  }
+
+  /// Turn the arguments into the paths to actually read, along with where
+  /// each one came from in the arguments.
+  fn args<I, S>(&self, inputs: I) -> (Vec<Arg>, Vec<FailReadFileError>) where
+    I: Iterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let mut args = Vec::new();
+    let mut literal = false;
+
+    for (index, arg) in inputs.enumerate() {
+      let path = arg.as_ref().to_path_buf();
+
+      if self.respect_double_dash && !literal && path.as_os_str() == "--" {
+        literal = true;
+      } else {
+        let stdin = !literal && self.is_stdin_alias(&path);
+        args.push(Arg { index, path, literal, stdin });
+      }
+    }
+
+    let mut errors = Vec::new();
+
+    if self.response_files {
+      args = self.expand_args(args, &mut errors, |arg| {
+        if arg.literal {
+          return Ok(None);
+        }
+
+        match arg.path.to_str().and_then(|arg| arg.strip_prefix('@')) {
+          Some(list) => Ok(Some(split_name_lines(&fs::read(list)?))),
+          None => Ok(None)
+        }
+      });
+    }
+
+    #[cfg(feature = "glob")]
+    {
+      if self.glob {
+        args = self.expand_args(args, &mut errors, |arg| {
+          if arg.literal || !glob::is_pattern(&arg.path) {
+            return Ok(None);
+          }
+
+          // A `?` in a URL is part of the query.
+          #[cfg(feature = "http")]
+          {
+            if http::is_url(&arg.path) {
+              return Ok(None);
+            }
+          }
+
+          match glob::expand(&arg.path)? {
+            ref paths if paths.is_empty() => Err(io::Error::new(io::ErrorKind::NotFound, "no files match this pattern")),
+            paths => Ok(Some(paths))
+          }
+        });
+      }
+    }
+
+    if self.recursive {
+      let mut walked = Vec::new();
+
+      for arg in args {
+        if arg.stdin || !arg.path.is_dir() {
+          walked.push(arg);
+          continue;
+        }
+
+        // Anything inside which can't be looked at gets an error of its
+        // own, under the directory's index, and everything else still gets
+        // read.
+        let (files, failures) = walk(&arg.path, self.follow_symlinks);
+        let Arg { index, literal, .. } = arg;
+
+        errors.extend(failures.into_iter().map(|(path, err)| FailReadFileError::new(index, &path, err)));
+        walked.extend(files.into_iter().map(|path| Arg { index, path, literal, stdin: false }));
+      }
+
+      args = walked;
+    }
+
+    self.tidy_args(args, errors)
+  }
+
+  /// Drop duplicates among arguments which have already been expanded, and
+  /// sort them, if asked to.
+  fn tidy_args(&self, mut args: Vec<Arg>, mut errors: Vec<FailReadFileError>) -> (Vec<Arg>, Vec<FailReadFileError>) {
+    if self.dedup {
+      let mut seen = HashSet::new();
+
+      args.retain(|arg| {
+        if arg.stdin {
+          return true;
+        }
+
+        match fs::canonicalize(&arg.path) {
+          Ok(canonical) => seen.insert(canonical),
+          Err(_) => true
+        }
+      });
+    }
+
+    if let Some(key) = self.sort {
+      self.sort_args(&mut args, key);
+    }
+
+    errors.sort_by_key(|err| err.arg_index);
+
+    (args, errors)
+  }
+
+  /// Sort the files among the arguments, leaving `stdin` aliases in place.
+  fn sort_args(&self, args: &mut [Arg], key: SortKey) {
+    let slots: Vec<usize> = (0..args.len()).filter(|&slot| !args[slot].stdin).collect();
+    let mut files: Vec<Arg> = slots.iter().map(|&slot| args[slot].clone()).collect();
+
+    match key {
+      SortKey::Name => files.sort_by(|a, b| a.path.cmp(&b.path)),
+      SortKey::ModifiedTime => {
+        // `None` sorts first, so flip it around to put unknowns last.
+        files.sort_by_cached_key(|arg| {
+          let modified = fs::metadata(&arg.path).and_then(|metadata| metadata.modified()).ok();
+          (modified.is_none(), modified)
+        });
+      },
+      SortKey::Size => {
+        files.sort_by_cached_key(|arg| {
+          let len = fs::metadata(&arg.path).ok().map(|metadata| metadata.len());
+          (len.is_none(), len)
+        });
+      }
+    }
+
+    for (slot, file) in slots.into_iter().zip(files) {
+      args[slot] = file;
+    }
+  }
+
+  /// Replace each argument with whatever paths `expand` turns it into, if
+  /// any, keeping the argument's index. `stdin` aliases are left alone.
+  fn expand_args<F>(&self, args: Vec<Arg>, errors: &mut Vec<FailReadFileError>, expand: F) -> Vec<Arg> where
+    F: Fn(&Arg) -> io::Result<Option<Vec<PathBuf>>>
+  {
+    let mut expanded = Vec::new();
+
+    for arg in args {
+      if arg.stdin {
+        expanded.push(arg);
+        continue;
+      }
+
+      match expand(&arg) {
+        Ok(Some(paths)) => {
+          let Arg { index, literal, .. } = arg;
+          expanded.extend(paths.into_iter().map(|path| Arg { index, path, literal, stdin: false }));
+        },
+        Ok(None) => expanded.push(arg),
+        Err(err) => errors.push(FailReadFileError::new(arg.index, &arg.path, err))
+      }
+    }
+
+    expanded
+  }
+
+  /// Open every argument, giving back whichever ones could be opened, and
+  /// the errors for the rest, in the order of the arguments.
+  fn open_args<I, S>(&self, inputs: I) -> (Vec<Source>, Vec<FailReadFileError>) where
+    I: Iterator<Item=S>,
+    S: AsRef<Path>
+  {
+    let (args, errors) = self.args(inputs);
+
+    self.open_expanded(args, errors)
+  }
+
+  fn open_expanded(&self, args: Vec<Arg>, mut errors: Vec<FailReadFileError>) -> (Vec<Source>, Vec<FailReadFileError>) {
+    let (sources, open_errors) = args.into_iter().attempt_map_partial(|_, arg| self.open_arg(arg));
+
+    errors.extend(open_errors);
+    errors.sort_by_key(|err| err.arg_index);
+
+    (sources, errors)
+  }
+
+  fn is_stdin_alias(&self, arg: &Path) -> bool {
+    !self.stdin_alias_disabled && self.stdin_aliases.iter().any(|alias| arg.as_os_str() == OsStr::new(alias))
+  }
+
+  fn lazy_arg(&self, arg: Arg) -> Source {
+    if arg.stdin {
+      stdin_source(arg.index)
+    } else {
+      Source::new(Some(arg.path), arg.index, SourceReader::Pending)
+    }
+  }
+
+  fn open_arg(&self, arg: Arg) -> Result<Source, FailReadFileError> {
+    if arg.stdin {
+      Ok(stdin_source(arg.index))
+    } else {
+      let file = self.opener.open(&arg.path).map_err(|err| FailReadFileError::new(arg.index, &arg.path, err))?;
+      Ok(Source::new(Some(arg.path), arg.index, SourceReader::Open(file)))
+    }
+  }
+
+  fn open_seekable(&self, arg: &Arg) -> Result<SeekableFile, FailReadFileError> {
+    if arg.stdin {
+      return Err(stdin_not_seekable(arg.index));
+    }
+
+    let (arg_index, arg) = (arg.index, arg.path.as_path());
+
+    // Check first, since just opening a named pipe can wait for a writer.
+    match fs::metadata(arg) {
+      Ok(ref metadata) if metadata.is_dir() => {
+        return Err(FailReadFileError::new(arg_index, arg, is_a_directory()));
+      },
+      Ok(ref metadata) if !metadata.is_file() => {
+        let err = io::Error::new(io::ErrorKind::InvalidInput, "not a regular file, so can't be seeked");
+        return Err(FailReadFileError::new(arg_index, arg, err));
+      },
+      _ => ()
+    }
+
+    let opened = self.opener.open_file(arg).and_then(|file| {
+      let len = file.metadata()?.len();
+      Ok((file, len))
+    });
+
+    match opened {
+      Ok((file, len)) => Ok(SeekableFile { path: arg.to_path_buf(), file, len }),
+      Err(err) => Err(FailReadFileError::new(arg_index, arg, err))
+    }
+  }
+}
+
+/// One of the arguments, after it's been expanded.
+#[derive(Clone)]
+struct Arg {
+  /// Which of the inputs it came from.
+  index: usize,
+  path: PathBuf,
+  /// Whether it came after a `--`, and so always names a file.
+  literal: bool,
+  stdin: bool
+}
+
+fn stdin_not_seekable(arg_index: usize) -> FailReadFileError {
+  let err = io::Error::new(io::ErrorKind::InvalidInput, "stdin can't be seeked");
+
+  FailReadFileError::new(arg_index, Path::new("-"), err)
+}
+
+fn stdin_source(arg_index: usize) -> Source {
+  Source::new(None, arg_index, SourceReader::Stdin)
+}
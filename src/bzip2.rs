@@ -0,0 +1,451 @@
+//! A small, dependency-free bzip2 decoder, used to transparently decompress
+//! `.bz2` inputs. Only decompression is supported.
+//!
+//! There's no formal spec for bzip2; this follows the format as written by
+//! the reference `bzip2` program.
+
+use std::io::{self, Read, BufRead, BufReader};
+
+const BLOCK_MAGIC: u64 = 0x3141_5926_5359;
+const END_MAGIC: u64 = 0x1772_4538_5090;
+
+const MAX_BITS: usize = 20;
+
+/// Each group of this many symbols is coded with the same Huffman table.
+const GROUP_SIZE: usize = 50;
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+/// bzip2 uses the same polynomial as gzip, but with the bits the other way
+/// around.
+const fn crc_table() -> [u32; 256] {
+  let mut table = [0; 256];
+  let mut i = 0;
+
+  while i < 256 {
+    let mut crc = (i as u32) << 24;
+    let mut bit = 0;
+
+    while bit < 8 {
+      crc = if crc & 0x8000_0000 != 0 { (crc << 1) ^ 0x04C1_1DB7 } else { crc << 1 };
+      bit += 1;
+    }
+
+    table[i] = crc;
+    i += 1;
+  }
+
+  table
+}
+
+fn crc(bytes: &[u8]) -> u32 {
+  let mut crc = !0;
+
+  for &byte in bytes {
+    crc = (crc << 8) ^ CRC_TABLE[((crc >> 24) ^ u32::from(byte)) as usize];
+  }
+
+  !crc
+}
+
+fn invalid(msg: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, format!("invalid bzip2 data: {}", msg))
+}
+
+/// A canonical Huffman code, stored as the number of codes of each length
+/// plus the symbols in code order.
+struct Huffman {
+  counts: [u16; MAX_BITS + 1],
+  symbols: Vec<u16>
+}
+
+impl Huffman {
+  fn new(lengths: &[u8]) -> io::Result<Self> {
+    let mut counts = [0; MAX_BITS + 1];
+
+    for &len in lengths {
+      counts[len as usize] += 1;
+    }
+
+    // Make sure the code isn't over-subscribed.
+    let mut left: i32 = 1;
+    for &count in &counts[1..] {
+      left = (left << 1) - i32::from(count);
+      if left < 0 {
+        return Err(invalid("over-subscribed Huffman code"));
+      }
+    }
+
+    let mut offsets = [0; MAX_BITS + 1];
+    for len in 1..MAX_BITS {
+      offsets[len + 1] = offsets[len] + counts[len];
+    }
+
+    let mut symbols = vec![0; lengths.len()];
+    for (symbol, &len) in lengths.iter().enumerate() {
+      symbols[offsets[len as usize] as usize] = symbol as u16;
+      offsets[len as usize] += 1;
+    }
+
+    Ok(Huffman { counts, symbols })
+  }
+}
+
+enum State {
+  StreamHeader,
+  Block,
+  Done
+}
+
+/// Decompresses a bzip2 stream read from `R`. Multiple concatenated streams
+/// are decompressed one after another, like `bzcat` does.
+pub struct Bzip2Decoder<R> {
+  inner: BufReader<R>,
+  state: State,
+  bits: u64,
+  bit_count: u32,
+  /// The most a block can hold before it's run-length decoded.
+  block_size: usize,
+  /// Every block's CRC, folded together, to check against the stream's.
+  stream_crc: u32,
+  /// The last block decompressed; blocks don't refer back to each other, so
+  /// nothing older needs keeping.
+  output: Vec<u8>,
+  /// How much of `output` has been handed back to the caller already.
+  delivered: usize
+}
+
+impl<R: Read> Bzip2Decoder<R> {
+  pub fn new(inner: R) -> Self {
+    Bzip2Decoder {
+      inner: BufReader::new(inner),
+      state: State::StreamHeader,
+      bits: 0,
+      bit_count: 0,
+      block_size: 0,
+      stream_crc: 0,
+      output: Vec::new(),
+      delivered: 0
+    }
+  }
+
+  fn byte(&mut self) -> io::Result<u8> {
+    let byte = match self.inner.fill_buf()?.first() {
+      Some(&byte) => byte,
+      None => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "bzip2 stream ended early"))
+    };
+    self.inner.consume(1);
+
+    Ok(byte)
+  }
+
+  /// Read `count` bits, highest first.
+  fn bits(&mut self, count: u32) -> io::Result<u64> {
+    while self.bit_count < count {
+      self.bits = self.bits << 8 | u64::from(self.byte()?);
+      self.bit_count += 8;
+    }
+
+    self.bit_count -= count;
+    let value = (self.bits >> self.bit_count) & ((1 << count) - 1);
+
+    Ok(value)
+  }
+
+  fn bit(&mut self) -> io::Result<bool> {
+    Ok(self.bits(1)? == 1)
+  }
+
+  fn decode(&mut self, code: &Huffman) -> io::Result<usize> {
+    let mut value: i32 = 0;
+    let mut first: i32 = 0;
+    let mut index: i32 = 0;
+
+    for len in 1..=MAX_BITS {
+      value |= self.bits(1)? as i32;
+
+      let count = i32::from(code.counts[len]);
+      if value - count < first {
+        return Ok(usize::from(code.symbols[(index + (value - first)) as usize]));
+      }
+
+      index += count;
+      first += count;
+      first <<= 1;
+      value <<= 1;
+    }
+
+    Err(invalid("bad Huffman code"))
+  }
+
+  fn stream_header(&mut self) -> io::Result<()> {
+    if self.byte()? != b'B' || self.byte()? != b'Z' || self.byte()? != b'h' {
+      return Err(invalid("bad magic number"));
+    }
+
+    let level = self.byte()?;
+    if !(b'1'..=b'9').contains(&level) {
+      return Err(invalid("bad block size"));
+    }
+
+    self.block_size = usize::from(level - b'0') * 100_000;
+    self.stream_crc = 0;
+    Ok(())
+  }
+
+  /// Decompress the next block, giving back `false` if the stream ended
+  /// instead.
+  fn block(&mut self) -> io::Result<bool> {
+    let magic = self.bits(48)?;
+    let crc = self.bits(32)? as u32;
+
+    if magic == END_MAGIC {
+      if crc != self.stream_crc {
+        return Err(invalid("stream CRC mismatch"));
+      }
+
+      // Streams are padded out to a whole byte, and there can't be more
+      // than that left over, so the next stream starts from a clean slate.
+      self.bit_count = 0;
+      return Ok(false);
+    }
+    if magic != BLOCK_MAGIC {
+      return Err(invalid("bad block magic number"));
+    }
+
+    if self.bit()? {
+      return Err(io::Error::new(io::ErrorKind::InvalidData, "randomised bzip2 blocks aren't supported"));
+    }
+    let origin = self.bits(24)? as usize;
+
+    let block = self.symbols()?;
+    if origin >= block.len() {
+      return Err(invalid("bad block origin"));
+    }
+
+    let start = self.output.len();
+    self.inverse_bwt(&block, origin);
+
+    if crc != self::crc(&self.output[start..]) {
+      return Err(invalid("block CRC mismatch"));
+    }
+    self.stream_crc = self.stream_crc.rotate_left(1) ^ crc;
+
+    Ok(true)
+  }
+
+  /// Read a block's Huffman tables, and decode its symbols, undoing the
+  /// move-to-front and run-length steps that come after the Burrows-Wheeler
+  /// transform.
+  fn symbols(&mut self) -> io::Result<Vec<u8>> {
+    // Which bytes appear in the block, as a bitmap of bitmaps.
+    let mut used = Vec::new();
+    let ranges = self.bits(16)?;
+    for range in 0..16 {
+      if ranges & (0x8000 >> range) != 0 {
+        let bytes = self.bits(16)?;
+        used.extend((0..16).filter(|bit| bytes & (0x8000 >> bit) != 0).map(|bit| (range * 16 + bit) as u8));
+      }
+    }
+    if used.is_empty() {
+      return Err(invalid("block uses no bytes"));
+    }
+
+    let alphabet_size = used.len() + 2;
+    let table_count = self.bits(3)? as usize;
+    if !(2..=6).contains(&table_count) {
+      return Err(invalid("bad number of Huffman tables"));
+    }
+
+    // Which table each group of symbols uses, move-to-front coded.
+    let selector_count = self.bits(15)? as usize;
+    if selector_count == 0 {
+      return Err(invalid("no Huffman table selectors"));
+    }
+    let mut order: Vec<usize> = (0..table_count).collect();
+    let mut selectors = Vec::with_capacity(selector_count);
+    for _ in 0..selector_count {
+      let mut index = 0;
+      while self.bit()? {
+        index += 1;
+        if index >= table_count {
+          return Err(invalid("bad Huffman table selector"));
+        }
+      }
+
+      let table = order.remove(index);
+      order.insert(0, table);
+      selectors.push(table);
+    }
+
+    // Code lengths, each stored as a change from the one before.
+    let mut tables = Vec::with_capacity(table_count);
+    for _ in 0..table_count {
+      let mut len = self.bits(5)? as i32;
+      let mut lengths = vec![0; alphabet_size];
+
+      for length in &mut lengths {
+        loop {
+          if !(1..=MAX_BITS as i32).contains(&len) {
+            return Err(invalid("bad Huffman code length"));
+          }
+          if !self.bit()? {
+            break;
+          }
+          len += if self.bit()? { -1 } else { 1 };
+        }
+        *length = len as u8;
+      }
+
+      tables.push(Huffman::new(&lengths)?);
+    }
+
+    // Symbols 0 and 1 spell out run lengths in bijective base 2, and the last
+    // one ends the block; the rest are move-to-front indices, plus one.
+    let end_of_block = alphabet_size - 1;
+    let mut recent: Vec<u8> = (0..used.len()).map(|index| index as u8).collect();
+    let mut block = Vec::new();
+    let mut run = 0;
+    let mut run_weight = 1;
+
+    for decoded in 0.. {
+      let selector = *selectors.get(decoded / GROUP_SIZE).ok_or_else(|| invalid("ran out of Huffman table selectors"))?;
+      let symbol = self.decode(&tables[selector])?;
+
+      if symbol <= 1 {
+        run += run_weight << symbol;
+        run_weight <<= 1;
+        if run > self.block_size {
+          return Err(invalid("block is too large"));
+        }
+        continue;
+      }
+
+      if run > 0 {
+        block.resize(block.len() + run, used[usize::from(recent[0])]);
+        run = 0;
+        run_weight = 1;
+      }
+
+      if symbol == end_of_block {
+        break;
+      }
+
+      let index = symbol - 1;
+      let value = recent[index];
+      recent.copy_within(..index, 1);
+      recent[0] = value;
+      block.push(used[usize::from(value)]);
+
+      if block.len() > self.block_size {
+        return Err(invalid("block is too large"));
+      }
+    }
+
+    if block.len() > self.block_size {
+      return Err(invalid("block is too large"));
+    }
+    Ok(block)
+  }
+
+  /// Undo the Burrows-Wheeler transform, then the run-length encoding that
+  /// came before it, into `output`.
+  fn inverse_bwt(&mut self, block: &[u8], origin: usize) {
+    let mut starts = [0; 256];
+    for &byte in block {
+      starts[usize::from(byte)] += 1;
+    }
+    let mut total = 0;
+    for start in starts.iter_mut() {
+      let count = *start;
+      *start = total;
+      total += count;
+    }
+
+    // Each entry keeps its byte in the low 8 bits, and where to go next in
+    // the rest.
+    let mut links: Vec<u32> = block.iter().map(|&byte| u32::from(byte)).collect();
+    for (index, &byte) in block.iter().enumerate() {
+      let start = &mut starts[usize::from(byte)];
+      links[*start] |= (index as u32) << 8;
+      *start += 1;
+    }
+
+    // Four of the same byte in a row are followed by how many more of it
+    // there are.
+    let mut pos = links[origin] >> 8;
+    let mut last = None;
+    let mut run = 0;
+
+    for _ in 0..block.len() {
+      let link = links[pos as usize];
+      let byte = link as u8;
+      pos = link >> 8;
+
+      if run == 4 {
+        self.output.resize(self.output.len() + usize::from(byte), last.unwrap_or(0));
+        run = 0;
+        continue;
+      }
+
+      if Some(byte) == last {
+        run += 1;
+      } else {
+        last = Some(byte);
+        run = 1;
+      }
+      self.output.push(byte);
+    }
+  }
+
+  /// Move the decoder along, until there's either new output, or the
+  /// stream is finished.
+  fn step(&mut self) -> io::Result<()> {
+    let before = self.output.len();
+
+    while self.output.len() == before {
+      self.state = match ::std::mem::replace(&mut self.state, State::Done) {
+        State::StreamHeader => {
+          self.stream_header()?;
+          State::Block
+        },
+        State::Block => {
+          if self.block()? {
+            State::Block
+          } else if self.inner.fill_buf()?.is_empty() {
+            State::Done
+          } else {
+            State::StreamHeader
+          }
+        },
+        State::Done => return Ok(())
+      };
+    }
+
+    Ok(())
+  }
+}
+
+impl<R: Read> Read for Bzip2Decoder<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.delivered == self.output.len() {
+      self.output.clear();
+      self.delivered = 0;
+
+      if let Err(err) = self.step() {
+        self.state = State::Done;
+        return Err(err);
+      }
+      if self.output.is_empty() {
+        return Ok(0);
+      }
+    }
+
+    let available = &self.output[self.delivered..];
+    let amt = usize::min(available.len(), buf.len());
+    buf[..amt].copy_from_slice(&available[..amt]);
+    self.delivered += amt;
+
+    Ok(amt)
+  }
+}
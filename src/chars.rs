@@ -0,0 +1,102 @@
+use std::io::{self, BufRead};
+use std::str;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use reader::ArgfReader;
+
+#[derive(Debug)]
+pub enum CharsError {
+  /// The input wasn't valid UTF-8. Holds the bytes that couldn't be decoded.
+  NotUtf8(Vec<u8>),
+  Io(io::Error)
+}
+
+impl Display for CharsError {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      CharsError::NotUtf8(ref bytes) => write!(f, "invalid UTF-8 sequence {:02X?}", bytes),
+      CharsError::Io(ref err) => write!(f, "{}", err)
+    }
+  }
+}
+
+impl Error for CharsError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      CharsError::NotUtf8(_) => None,
+      CharsError::Io(ref err) => Some(err)
+    }
+  }
+}
+
+impl From<io::Error> for CharsError {
+  fn from(err: io::Error) -> Self {
+    CharsError::Io(err)
+  }
+}
+
+/// An iterator over every character of input, decoded as UTF-8.
+///
+/// Characters are decoded over the concatenation of all input, so a
+/// character whose bytes are split across two files still comes out whole.
+pub struct Chars {
+  reader: ArgfReader
+}
+
+impl Chars {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    Chars { reader }
+  }
+
+  fn peek_byte(&mut self) -> io::Result<Option<u8>> {
+    Ok(self.reader.fill_buf()?.first().cloned())
+  }
+
+  fn next_char(&mut self) -> Result<Option<char>, CharsError> {
+    let first = match self.peek_byte()? {
+      Some(byte) => byte,
+      None => return Ok(None)
+    };
+    self.reader.consume(1);
+
+    let width = match first {
+      0x00..=0x7F => return Ok(Some(char::from(first))),
+      0xC2..=0xDF => 2,
+      0xE0..=0xEF => 3,
+      0xF0..=0xF4 => 4,
+      _ => return Err(CharsError::NotUtf8(vec![first]))
+    };
+
+    let mut bytes = vec![first];
+
+    while bytes.len() < width {
+      match self.peek_byte()? {
+        // Leave anything that isn't a continuation byte for the next
+        // character to start with.
+        Some(byte) if byte & 0xC0 == 0x80 => {
+          self.reader.consume(1);
+          bytes.push(byte);
+        },
+        _ => return Err(CharsError::NotUtf8(bytes))
+      }
+    }
+
+    match str::from_utf8(&bytes) {
+      Ok(decoded) => Ok(decoded.chars().next()),
+      Err(_) => Err(CharsError::NotUtf8(bytes))
+    }
+  }
+}
+
+impl Iterator for Chars {
+  type Item = Result<char, CharsError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self.next_char() {
+      Ok(Some(c)) => Some(Ok(c)),
+      Ok(None) => None,
+      Err(err) => Some(Err(err))
+    }
+  }
+}
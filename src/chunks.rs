@@ -0,0 +1,46 @@
+use std::io::{self, Read};
+
+use reader::ArgfReader;
+
+/// An iterator over all of the input in fixed-size blocks of bytes.
+///
+/// Every block is exactly the same size, except maybe the last, however the
+/// input is split up into files: a block can have the end of one file and
+/// the start of the next in it. Each block is a new `Vec`.
+pub struct Chunks {
+  reader: ArgfReader,
+  size: usize
+}
+
+impl Chunks {
+  pub(crate) fn new(reader: ArgfReader, size: usize) -> Self {
+    assert!(size != 0, "chunk size must be non-zero");
+
+    Chunks { reader, size }
+  }
+}
+
+impl Iterator for Chunks {
+  type Item = io::Result<Vec<u8>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut chunk = vec![0; self.size];
+    let mut filled = 0;
+
+    while filled < self.size {
+      match self.reader.read(&mut chunk[filled..]) {
+        Ok(0) => break,
+        Ok(read) => filled += read,
+        Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+        Err(err) => return Some(Err(err))
+      }
+    }
+
+    if filled == 0 {
+      return None;
+    }
+
+    chunk.truncate(filled);
+    Some(Ok(chunk))
+  }
+}
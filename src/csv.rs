@@ -0,0 +1,207 @@
+use std::io::{self, BufRead};
+
+use reader::ArgfReader;
+
+/// An iterator over CSV records from all input, as if all the files were one
+/// long CSV file.
+///
+/// Fields are separated by commas, and can be quoted with `"`, in which case
+/// they can contain commas, newlines, and doubled-up `""` quotes. A `"`
+/// anywhere but the start of a field is just part of it. Records
+/// never span files, so a file which ends inside a quoted field is an error.
+/// Blank lines are skipped.
+///
+/// By default, the first record of the first file is treated as a header row,
+/// and is available from [`headers()`](#method.headers) rather than being
+/// returned as a record.
+pub struct CsvRecords {
+  reader: ArgfReader,
+  has_headers: bool,
+  skip_repeated_headers: bool,
+  headers: Option<Vec<String>>,
+  started: bool,
+  /// Which source the last record came from.
+  source_index: usize
+}
+
+impl CsvRecords {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    let source_index = reader.source_index();
+
+    CsvRecords {
+      reader,
+      has_headers: true,
+      skip_repeated_headers: false,
+      headers: None,
+      started: false,
+      source_index
+    }
+  }
+
+  /// Whether the first record of the first file is a header row. Defaults to
+  /// `true`. Has no effect once reading has started.
+  pub fn has_headers(mut self, has_headers: bool) -> Self {
+    self.has_headers = has_headers;
+    self
+  }
+
+  /// Leave out the first record of every file after the first, if it's the
+  /// same as the header row, for when each file carries its own copy of the
+  /// headers. Defaults to `false`.
+  pub fn skip_repeated_headers(mut self, skip: bool) -> Self {
+    self.skip_repeated_headers = skip;
+    self
+  }
+
+  /// The header row, if there is one.
+  pub fn headers(&mut self) -> io::Result<Option<&[String]>> {
+    self.start()?;
+
+    Ok(self.headers.as_deref())
+  }
+
+  fn start(&mut self) -> io::Result<()> {
+    if !self.started {
+      self.started = true;
+
+      if self.has_headers {
+        self.headers = self.read_record()?.map(|(headers, _)| headers);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Read the next non-blank record, along with whether it's the first
+  /// record of its source.
+  fn read_record(&mut self) -> io::Result<Option<(Vec<String>, bool)>> {
+    let mut record = RecordParser::default();
+    let mut line = Vec::new();
+
+    loop {
+      line.clear();
+      let read = self.reader.current_source().read_until(b'\n', &mut line)?;
+
+      if read == 0 {
+        if record.quoted {
+          return Err(io::Error::new(io::ErrorKind::InvalidData, "CSV file ends inside a quoted field"));
+        }
+
+        if !self.reader.next_source() {
+          return Ok(None);
+        }
+
+        continue;
+      }
+
+      record.feed(&line);
+
+      // A newline inside a quoted field is part of it, so keep going.
+      if record.quoted {
+        if !line.ends_with(b"\n") {
+          return Err(io::Error::new(io::ErrorKind::InvalidData, "CSV file ends inside a quoted field"));
+        }
+
+        continue;
+      }
+
+      if record.is_blank() {
+        continue;
+      }
+
+      let first = self.reader.source_index() != self.source_index;
+      self.source_index = self.reader.source_index();
+
+      return record.finish().map(|record| Some((record, first)));
+    }
+  }
+}
+
+/// Splits up a record as its lines come in, keeping track of quoting across
+/// them, so that quotes only ever get looked at in one place.
+///
+/// A `"` only starts a quoted field at the very start of a field; anywhere
+/// else, like in `5" screen`, it's just part of the field.
+#[derive(Default)]
+struct RecordParser {
+  fields: Vec<Vec<u8>>,
+  field: Vec<u8>,
+  /// Whether the current field has anything in it, even just quotes.
+  started: bool,
+  quoted: bool
+}
+
+impl RecordParser {
+  fn feed(&mut self, line: &[u8]) {
+    let mut bytes = line.iter().cloned().peekable();
+
+    while let Some(byte) = bytes.next() {
+      if self.quoted {
+        match byte {
+          b'"' if bytes.peek() == Some(&b'"') => {
+            bytes.next();
+            self.field.push(b'"');
+          },
+          b'"' => self.quoted = false,
+          _ => self.field.push(byte)
+        }
+
+        continue;
+      }
+
+      match byte {
+        b'"' if !self.started => {
+          self.quoted = true;
+          self.started = true;
+        },
+        b',' => {
+          self.fields.push(self.field.split_off(0));
+          self.started = false;
+        },
+        b'\r' if bytes.peek() == Some(&b'\n') => {},
+        b'\n' => {},
+        _ => {
+          self.field.push(byte);
+          self.started = true;
+        }
+      }
+    }
+  }
+
+  /// Whether the record is just an empty line.
+  fn is_blank(&self) -> bool {
+    self.fields.is_empty() && !self.started
+  }
+
+  fn finish(mut self) -> io::Result<Vec<String>> {
+    self.fields.push(self.field);
+
+    self.fields.into_iter().map(|field| {
+      String::from_utf8(field).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }).collect()
+  }
+}
+
+impl Iterator for CsvRecords {
+  type Item = io::Result<Vec<String>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Err(err) = self.start() {
+      return Some(Err(err));
+    }
+
+    loop {
+      match self.read_record() {
+        Ok(Some((record, first))) => {
+          if first && self.skip_repeated_headers && self.headers.as_ref() == Some(&record) {
+            continue;
+          }
+
+          return Some(Ok(record));
+        },
+        Ok(None) => return None,
+        Err(err) => return Some(Err(err))
+      }
+    }
+  }
+}
@@ -0,0 +1,168 @@
+use std::io::{self, Read};
+use std::char;
+
+const REPLACEMENT: &str = "\u{FFFD}";
+
+/// A text encoding that input can be decoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+  Utf8,
+  /// ISO-8859-1, where every byte is the character with the same value.
+  Latin1,
+  Utf16Le,
+  Utf16Be
+}
+
+impl Encoding {
+  /// Figure out the encoding from the byte order mark at the start of
+  /// `bytes`, if there is one. Gives back the encoding, along with the length
+  /// of the byte order mark.
+  pub fn for_bom(bytes: &[u8]) -> Option<(Encoding, usize)> {
+    if bytes.starts_with(b"\xEF\xBB\xBF") {
+      Some((Encoding::Utf8, 3))
+    } else if bytes.starts_with(b"\xFF\xFE") {
+      Some((Encoding::Utf16Le, 2))
+    } else if bytes.starts_with(b"\xFE\xFF") {
+      Some((Encoding::Utf16Be, 2))
+    } else {
+      None
+    }
+  }
+}
+
+/// A reader which decodes text in some encoding, and gives it back as UTF-8.
+///
+/// Bytes which aren't valid in the encoding get replaced with U+FFFD, so the
+/// output is always valid UTF-8. Characters can be split across reads of the
+/// underlying reader, so this works over input chained together from several
+/// files.
+pub struct DecodeReader<R> {
+  inner: R,
+  encoding: Encoding,
+  /// Bytes read from `inner` which don't make up a whole character yet.
+  undecoded: Vec<u8>,
+  decoded: Vec<u8>,
+  pos: usize,
+  eof: bool
+}
+
+impl<R> DecodeReader<R> where
+  R: Read
+{
+  pub fn new(inner: R, encoding: Encoding) -> Self {
+    DecodeReader { inner, encoding, undecoded: Vec::new(), decoded: Vec::new(), pos: 0, eof: false }
+  }
+
+  /// The encoding being decoded from.
+  pub fn encoding(&self) -> Encoding {
+    self.encoding
+  }
+
+  /// Get back the underlying reader.
+  pub fn into_inner(self) -> R {
+    self.inner
+  }
+
+  /// Decode as much of `undecoded` as possible into `decoded`.
+  fn decode(&mut self) {
+    let mut text = String::new();
+    let used = match self.encoding {
+      Encoding::Utf8 => decode_utf8(&self.undecoded, self.eof, &mut text),
+      Encoding::Latin1 => {
+        text.extend(self.undecoded.iter().map(|&byte| byte as char));
+        self.undecoded.len()
+      },
+      Encoding::Utf16Le => decode_utf16(&self.undecoded, self.eof, u16::from_le_bytes, &mut text),
+      Encoding::Utf16Be => decode_utf16(&self.undecoded, self.eof, u16::from_be_bytes, &mut text)
+    };
+
+    self.undecoded.drain(..used);
+    self.decoded.clear();
+    self.decoded.extend_from_slice(text.as_bytes());
+    self.pos = 0;
+  }
+}
+
+/// Decode UTF-8, leaving off a character that's been cut off at the end,
+/// unless there's nothing more coming. Gives back how much got decoded.
+fn decode_utf8(bytes: &[u8], eof: bool, text: &mut String) -> usize {
+  let mut used = 0;
+
+  loop {
+    match ::std::str::from_utf8(&bytes[used..]) {
+      Ok(valid) => {
+        text.push_str(valid);
+        return bytes.len();
+      },
+      Err(err) => {
+        let valid_len = err.valid_up_to();
+        text.push_str(::std::str::from_utf8(&bytes[used..used + valid_len]).unwrap());
+        used += valid_len;
+
+        match err.error_len() {
+          Some(invalid_len) => {
+            text.push_str(REPLACEMENT);
+            used += invalid_len;
+          },
+          None if eof => {
+            text.push_str(REPLACEMENT);
+            return bytes.len();
+          },
+          None => return used
+        }
+      }
+    }
+  }
+}
+
+/// Decode UTF-16, leaving off a half-read code unit or surrogate pair at the
+/// end, unless there's nothing more coming. Gives back how much got decoded.
+fn decode_utf16<F>(bytes: &[u8], eof: bool, unit: F, text: &mut String) -> usize where
+  F: Fn([u8; 2]) -> u16
+{
+  let mut units: Vec<u16> = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]])).collect();
+  let mut used = units.len() * 2;
+
+  if !eof {
+    if let Some(&last) = units.last() {
+      if (0xD800..0xDC00).contains(&last) {
+        units.pop();
+        used -= 2;
+      }
+    }
+  }
+
+  text.extend(char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)));
+
+  if eof && used < bytes.len() {
+    text.push_str(REPLACEMENT);
+    used = bytes.len();
+  }
+
+  used
+}
+
+impl<R> Read for DecodeReader<R> where
+  R: Read
+{
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    while self.pos >= self.decoded.len() {
+      if self.eof {
+        return Ok(0);
+      }
+
+      let mut chunk = [0; 8 * 1024];
+      let read = self.inner.read(&mut chunk)?;
+
+      self.eof = read == 0;
+      self.undecoded.extend_from_slice(&chunk[..read]);
+      self.decode();
+    }
+
+    let amt = usize::min(buf.len(), self.decoded.len() - self.pos);
+    buf[..amt].copy_from_slice(&self.decoded[self.pos..self.pos + amt]);
+    self.pos += amt;
+
+    Ok(amt)
+  }
+}
@@ -0,0 +1,152 @@
+//! Expanding shell-style wildcards in arguments, for platforms where the
+//! shell doesn't do it for us.
+//!
+//! Supports `*`, `?`, and character classes like `[abc]`, `[a-z]` and
+//! `[!abc]`, within each part of a path. Like a shell, wildcards don't match
+//! names starting with `.` unless the pattern does too.
+
+use std::io;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Whether the argument has any wildcards in it at all.
+pub(crate) fn is_pattern(arg: &Path) -> bool {
+  arg.components().any(|component| component_pattern(component).is_some())
+}
+
+/// Find every path matching the pattern, in sorted order.
+pub(crate) fn expand(pattern: &Path) -> io::Result<Vec<PathBuf>> {
+  let mut paths = vec![PathBuf::new()];
+
+  for component in pattern.components() {
+    let wildcard = match component_pattern(component) {
+      Some(wildcard) => wildcard,
+      None => {
+        for path in &mut paths {
+          path.push(component);
+        }
+        continue;
+      }
+    };
+
+    let wildcard_chars: Vec<char> = wildcard.chars().collect();
+    let mut matches = Vec::new();
+
+    for base in &paths {
+      let dir = if base.as_os_str().is_empty() { Path::new(".") } else { base.as_path() };
+
+      // Anything which isn't a readable directory just can't have matches.
+      let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => continue
+      };
+
+      for entry in entries {
+        let name = entry?.file_name();
+
+        if let Some(name) = name.to_str() {
+          if name.starts_with('.') && !wildcard.starts_with('.') {
+            continue;
+          }
+
+          if matches_pattern(&wildcard_chars, &name.chars().collect::<Vec<_>>()) {
+            matches.push(base.join(name));
+          }
+        }
+      }
+    }
+
+    matches.sort();
+    paths = matches;
+  }
+
+  // Literal parts after the last wildcard might not exist.
+  paths.retain(|path| path.exists());
+
+  Ok(paths)
+}
+
+fn component_pattern(component: Component<'_>) -> Option<&str> {
+  match component {
+    Component::Normal(name) => name.to_str().filter(|name| name.contains(&['*', '?', '['][..])),
+    _ => None
+  }
+}
+
+/// Match the whole name against the pattern. A failed match only ever goes
+/// back to just after the last `*`, letting it swallow one more character,
+/// which keeps this linear in the length of the name for each `*`.
+fn matches_pattern(pattern: &[char], name: &[char]) -> bool {
+  let mut rest = pattern;
+  let mut n = 0;
+  // The pattern after the last `*`, and where in the name it next tries to match.
+  let mut backtrack: Option<(&[char], usize)> = None;
+
+  while n < name.len() {
+    if rest.first() == Some(&'*') {
+      rest = &rest[1..];
+      backtrack = Some((rest, n));
+      continue;
+    }
+
+    if let Some(after) = match_one(rest, name[n]) {
+      rest = after;
+      n += 1;
+      continue;
+    }
+
+    match backtrack {
+      Some((after_star, start)) => {
+        rest = after_star;
+        n = start + 1;
+        backtrack = Some((after_star, n));
+      },
+      None => return false
+    }
+  }
+
+  rest.iter().all(|&c| c == '*')
+}
+
+/// Match a single character against the start of the pattern, giving back
+/// the rest of the pattern if it matched.
+fn match_one(pattern: &[char], c: char) -> Option<&[char]> {
+  match pattern.first() {
+    None | Some('*') => None,
+    Some('?') => Some(&pattern[1..]),
+    Some('[') => match match_class(&pattern[1..], c) {
+      Some((matched, rest)) => if matched { Some(rest) } else { None },
+      // An unclosed `[` is just a `[`.
+      None => if c == '[' { Some(&pattern[1..]) } else { None }
+    },
+    Some(&literal) => if c == literal { Some(&pattern[1..]) } else { None }
+  }
+}
+
+/// Check a character against the class at the start of `pattern`, just after
+/// the `[`. Gives back whether it matched, and the rest of the pattern after
+/// the class, or `None` if the class never gets closed.
+fn match_class(pattern: &[char], c: char) -> Option<(bool, &[char])> {
+  let (negated, mut pattern) = match pattern.first() {
+    Some('!') | Some('^') => (true, &pattern[1..]),
+    _ => (false, pattern)
+  };
+  let mut matched = false;
+  let mut first = true;
+
+  loop {
+    match pattern {
+      [] => return None,
+      [']', rest @ ..] if !first => return Some((matched != negated, rest)),
+      [start, '-', end, rest @ ..] if *end != ']' => {
+        matched |= *start <= c && c <= *end;
+        pattern = rest;
+      },
+      [single, rest @ ..] => {
+        matched |= c == *single;
+        pattern = rest;
+      }
+    }
+    first = false;
+  }
+}
@@ -0,0 +1,514 @@
+//! A small, dependency-free gzip decoder, used to transparently decompress
+//! `.gz` inputs. Only decompression is supported.
+//!
+//! See RFC 1951 (DEFLATE) and RFC 1952 (gzip) for the formats.
+
+use std::io::{self, Read, BufRead, BufReader};
+
+/// Back-references can reach at most this far back into the output.
+const WINDOW_SIZE: usize = 32 * 1024;
+
+/// How much output to produce at a time before handing it back to the caller.
+const OUTPUT_CHUNK: usize = 32 * 1024;
+
+const MAX_BITS: usize = 15;
+
+const LENGTH_BASE: [u16; 29] = [
+  3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+  35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258
+];
+const LENGTH_EXTRA: [u8; 29] = [
+  0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+  3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0
+];
+const DIST_BASE: [u16; 30] = [
+  1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+  257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577
+];
+const DIST_EXTRA: [u8; 30] = [
+  0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+  7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13
+];
+
+/// The order that code length code lengths are stored in.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+  16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15
+];
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+  let mut table = [0; 256];
+  let mut i = 0;
+
+  while i < 256 {
+    let mut crc = i as u32;
+    let mut bit = 0;
+
+    while bit < 8 {
+      crc = if crc & 1 == 1 { 0xEDB8_8320 ^ (crc >> 1) } else { crc >> 1 };
+      bit += 1;
+    }
+
+    table[i] = crc;
+    i += 1;
+  }
+
+  table
+}
+
+fn update_crc(crc: u32, bytes: &[u8]) -> u32 {
+  let mut crc = !crc;
+
+  for &byte in bytes {
+    crc = CRC_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8);
+  }
+
+  !crc
+}
+
+fn invalid(msg: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, format!("invalid gzip data: {}", msg))
+}
+
+/// A canonical Huffman code, stored as the number of codes of each length
+/// plus the symbols in code order.
+struct Huffman {
+  counts: [u16; MAX_BITS + 1],
+  symbols: Vec<u16>
+}
+
+impl Huffman {
+  fn new(lengths: &[u8]) -> io::Result<Self> {
+    let mut counts = [0; MAX_BITS + 1];
+
+    for &len in lengths {
+      counts[len as usize] += 1;
+    }
+    counts[0] = 0;
+
+    // Make sure the code isn't over-subscribed.
+    let mut left: i32 = 1;
+    for &count in &counts[1..] {
+      left = (left << 1) - i32::from(count);
+      if left < 0 {
+        return Err(invalid("over-subscribed Huffman code"));
+      }
+    }
+
+    let mut offsets = [0; MAX_BITS + 1];
+    for len in 1..MAX_BITS {
+      offsets[len + 1] = offsets[len] + counts[len];
+    }
+
+    let mut symbols = vec![0; lengths.len()];
+    for (symbol, &len) in lengths.iter().enumerate() {
+      if len != 0 {
+        symbols[offsets[len as usize] as usize] = symbol as u16;
+        offsets[len as usize] += 1;
+      }
+    }
+
+    Ok(Huffman { counts, symbols })
+  }
+
+  fn fixed_literals() -> Self {
+    let mut lengths = [0; 288];
+
+    for (symbol, len) in lengths.iter_mut().enumerate() {
+      *len = match symbol {
+        0..=143 => 8,
+        144..=255 => 9,
+        256..=279 => 7,
+        _ => 8
+      };
+    }
+
+    Huffman::new(&lengths).expect("fixed Huffman code is valid")
+  }
+
+  fn fixed_distances() -> Self {
+    Huffman::new(&[5; 30]).expect("fixed Huffman code is valid")
+  }
+}
+
+enum State {
+  Header,
+  BlockHeader,
+  Stored(usize),
+  Compressed(Huffman, Huffman),
+  Trailer,
+  Done
+}
+
+/// Decompresses a gzip stream read from `R`. Multiple concatenated gzip
+/// members are decompressed one after another, like `zcat` does, and zero
+/// bytes padding out the end are ignored.
+pub struct GzDecoder<R> {
+  inner: BufReader<R>,
+  state: State,
+  last_block: bool,
+  bits: u32,
+  bit_count: u32,
+  /// Everything decompressed that hasn't been trimmed yet; the tail of this
+  /// is the window that back-references refer into.
+  output: Vec<u8>,
+  /// How much of `output` has been handed back to the caller already.
+  delivered: usize,
+  crc: u32,
+  size: u32
+}
+
+impl<R: Read> GzDecoder<R> {
+  pub fn new(inner: R) -> Self {
+    GzDecoder {
+      inner: BufReader::new(inner),
+      state: State::Header,
+      last_block: false,
+      bits: 0,
+      bit_count: 0,
+      output: Vec::new(),
+      delivered: 0,
+      crc: 0,
+      size: 0
+    }
+  }
+
+  fn byte(&mut self) -> io::Result<u8> {
+    let byte = match self.inner.fill_buf()?.first() {
+      Some(&byte) => byte,
+      None => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "gzip stream ended early"))
+    };
+    self.inner.consume(1);
+
+    Ok(byte)
+  }
+
+  fn u16_le(&mut self) -> io::Result<u16> {
+    Ok(u16::from(self.byte()?) | u16::from(self.byte()?) << 8)
+  }
+
+  fn u32_le(&mut self) -> io::Result<u32> {
+    Ok(u32::from(self.u16_le()?) | u32::from(self.u16_le()?) << 16)
+  }
+
+  fn bits(&mut self, count: u32) -> io::Result<u32> {
+    while self.bit_count < count {
+      self.bits |= u32::from(self.byte()?) << self.bit_count;
+      self.bit_count += 8;
+    }
+
+    let value = self.bits & ((1 << count) - 1);
+    self.bits >>= count;
+    self.bit_count -= count;
+
+    Ok(value)
+  }
+
+  fn align_to_byte(&mut self) {
+    self.bits = 0;
+    self.bit_count = 0;
+  }
+
+  fn decode(&mut self, code: &Huffman) -> io::Result<u16> {
+    let mut value: i32 = 0;
+    let mut first: i32 = 0;
+    let mut index: i32 = 0;
+
+    for len in 1..=MAX_BITS {
+      value |= self.bits(1)? as i32;
+
+      let count = i32::from(code.counts[len]);
+      if value - count < first {
+        return Ok(code.symbols[(index + (value - first)) as usize]);
+      }
+
+      index += count;
+      first += count;
+      first <<= 1;
+      value <<= 1;
+    }
+
+    Err(invalid("bad Huffman code"))
+  }
+
+  fn header(&mut self) -> io::Result<()> {
+    if self.byte()? != 0x1F || self.byte()? != 0x8B {
+      return Err(invalid("bad magic number"));
+    }
+    if self.byte()? != 8 {
+      return Err(invalid("unknown compression method"));
+    }
+
+    let flags = self.byte()?;
+    // Modification time, extra flags and OS aren't needed.
+    for _ in 0..6 {
+      self.byte()?;
+    }
+
+    if flags & 0x04 != 0 {
+      let extra_len = self.u16_le()?;
+      for _ in 0..extra_len {
+        self.byte()?;
+      }
+    }
+    // Original filename, then comment, both NUL-terminated.
+    for &flag in &[0x08, 0x10] {
+      if flags & flag != 0 {
+        while self.byte()? != 0 {}
+      }
+    }
+    if flags & 0x02 != 0 {
+      self.u16_le()?;
+    }
+
+    self.crc = 0;
+    self.size = 0;
+    self.last_block = false;
+    Ok(())
+  }
+
+  fn block_header(&mut self) -> io::Result<State> {
+    self.last_block = self.bits(1)? == 1;
+
+    match self.bits(2)? {
+      0 => {
+        self.align_to_byte();
+        let len = self.u16_le()?;
+        let nlen = self.u16_le()?;
+
+        if len != !nlen {
+          return Err(invalid("stored block length mismatch"));
+        }
+
+        Ok(State::Stored(len as usize))
+      },
+      1 => Ok(State::Compressed(Huffman::fixed_literals(), Huffman::fixed_distances())),
+      2 => self.dynamic_codes(),
+      _ => Err(invalid("bad block type"))
+    }
+  }
+
+  fn dynamic_codes(&mut self) -> io::Result<State> {
+    let literal_count = self.bits(5)? as usize + 257;
+    let distance_count = self.bits(5)? as usize + 1;
+    let code_length_count = self.bits(4)? as usize + 4;
+
+    if literal_count > 286 || distance_count > 30 {
+      return Err(invalid("too many length or distance codes"));
+    }
+
+    let mut code_lengths = [0; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_length_count] {
+      code_lengths[index] = self.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+
+    let mut lengths = vec![0; literal_count + distance_count];
+    let mut index = 0;
+
+    while index < lengths.len() {
+      let symbol = self.decode(&code_length_code)?;
+
+      let (len, repeat) = match symbol {
+        0..=15 => (symbol as u8, 1),
+        16 => {
+          if index == 0 {
+            return Err(invalid("repeated length with no first length"));
+          }
+          (lengths[index - 1], 3 + self.bits(2)? as usize)
+        },
+        17 => (0, 3 + self.bits(3)? as usize),
+        _ => (0, 11 + self.bits(7)? as usize)
+      };
+
+      if index + repeat > lengths.len() {
+        return Err(invalid("too many code lengths"));
+      }
+      for length in &mut lengths[index..index + repeat] {
+        *length = len;
+      }
+      index += repeat;
+    }
+
+    if lengths[256] == 0 {
+      return Err(invalid("missing end-of-block code"));
+    }
+
+    let literals = Huffman::new(&lengths[..literal_count])?;
+    let distances = Huffman::new(&lengths[literal_count..])?;
+
+    Ok(State::Compressed(literals, distances))
+  }
+
+  /// Decompress symbols until either the block ends or there's a decent
+  /// amount of output. Returns whether the block ended.
+  fn compressed(&mut self, literals: &Huffman, distances: &Huffman) -> io::Result<bool> {
+    let target = self.output.len() + OUTPUT_CHUNK;
+
+    while self.output.len() < target {
+      let symbol = self.decode(literals)? as usize;
+
+      if symbol < 256 {
+        self.output.push(symbol as u8);
+      } else if symbol == 256 {
+        return Ok(true);
+      } else {
+        let symbol = symbol - 257;
+        if symbol >= LENGTH_BASE.len() {
+          return Err(invalid("bad length code"));
+        }
+        let len = LENGTH_BASE[symbol] as usize + self.bits(u32::from(LENGTH_EXTRA[symbol]))? as usize;
+
+        let symbol = self.decode(distances)? as usize;
+        if symbol >= DIST_BASE.len() {
+          return Err(invalid("bad distance code"));
+        }
+        let dist = DIST_BASE[symbol] as usize + self.bits(u32::from(DIST_EXTRA[symbol]))? as usize;
+
+        if dist > self.output.len() {
+          return Err(invalid("distance too far back"));
+        }
+
+        let start = self.output.len() - dist;
+        for i in 0..len {
+          let byte = self.output[start + i];
+          self.output.push(byte);
+        }
+      }
+    }
+
+    Ok(false)
+  }
+
+  fn stored(&mut self, remaining: usize) -> io::Result<usize> {
+    let available = {
+      let buf = self.inner.fill_buf()?;
+      if buf.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "gzip stream ended early"));
+      }
+      let amt = usize::min(buf.len(), remaining);
+      self.output.extend_from_slice(&buf[..amt]);
+      amt
+    };
+    self.inner.consume(available);
+
+    Ok(remaining - available)
+  }
+
+  fn trailer(&mut self) -> io::Result<()> {
+    self.align_to_byte();
+
+    let crc = self.u32_le()?;
+    let size = self.u32_le()?;
+
+    if crc != self.crc {
+      return Err(invalid("CRC mismatch"));
+    }
+    if size != self.size {
+      return Err(invalid("length mismatch"));
+    }
+    Ok(())
+  }
+
+  /// After a member, skip over any zero bytes that the stream's been padded
+  /// out with, like `gzip` does, giving back whether that's the end of the
+  /// input, rather than the start of another member.
+  fn skip_padding(&mut self) -> io::Result<bool> {
+    let mut padded = false;
+
+    loop {
+      let (len, zeros) = {
+        let buf = self.inner.fill_buf()?;
+        (buf.len(), buf.iter().take_while(|&&byte| byte == 0).count())
+      };
+
+      if len == 0 {
+        return Ok(true);
+      }
+      if zeros == 0 && !padded {
+        return Ok(false);
+      }
+      if zeros < len {
+        return Err(invalid("data after zero padding"));
+      }
+
+      self.inner.consume(len);
+      padded = true;
+    }
+  }
+
+  /// Move the decoder along, until there's either new output, or the
+  /// stream is finished.
+  fn step(&mut self) -> io::Result<()> {
+    let before = self.output.len();
+
+    while self.output.len() == before {
+      self.state = match ::std::mem::replace(&mut self.state, State::Done) {
+        State::Header => {
+          self.header()?;
+          State::BlockHeader
+        },
+        State::BlockHeader => self.block_header()?,
+        State::Stored(0) => self.end_of_block(),
+        State::Stored(remaining) => State::Stored(self.stored(remaining)?),
+        State::Compressed(literals, distances) => {
+          if self.compressed(&literals, &distances)? {
+            self.end_of_block()
+          } else {
+            State::Compressed(literals, distances)
+          }
+        },
+        State::Trailer => {
+          self.trailer()?;
+
+          if self.skip_padding()? { State::Done } else { State::Header }
+        },
+        State::Done => return Ok(())
+      };
+    }
+
+    self.checksum(before);
+    Ok(())
+  }
+
+  fn end_of_block(&self) -> State {
+    if self.last_block { State::Trailer } else { State::BlockHeader }
+  }
+
+  /// Fold any output since `from` into the running checksum.
+  fn checksum(&mut self, from: usize) {
+    let new = &self.output[from..];
+    self.crc = update_crc(self.crc, new);
+    self.size = self.size.wrapping_add(new.len() as u32);
+  }
+}
+
+impl<R: Read> Read for GzDecoder<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.delivered == self.output.len() {
+      // Only the last window's worth of output needs to stick around.
+      if self.output.len() > 2 * WINDOW_SIZE {
+        let trim = self.output.len() - WINDOW_SIZE;
+        self.output.drain(..trim);
+        self.delivered -= trim;
+      }
+
+      let before = self.output.len();
+      if let Err(err) = self.step() {
+        self.state = State::Done;
+        return Err(err);
+      }
+      if self.output.len() == before {
+        return Ok(0);
+      }
+    }
+
+    let available = &self.output[self.delivered..];
+    let amt = usize::min(available.len(), buf.len());
+    buf[..amt].copy_from_slice(&available[..amt]);
+    self.delivered += amt;
+
+    Ok(amt)
+  }
+}
@@ -0,0 +1,273 @@
+//! A small, dependency-free HTTP client, used to stream `http://` arguments
+//! into the input. Only plain `GET` requests over HTTP/1.0 are supported;
+//! there's no TLS, so `https://` arguments fail to open.
+
+use std::io::{self, Read, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+/// How many redirects to follow before giving up.
+const MAX_REDIRECTS: usize = 5;
+
+/// How long to wait on connecting, or on any one read or write, before
+/// giving up on a server that's stopped responding.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether the argument is a URL, rather than the name of a file.
+pub(crate) fn is_url(arg: &Path) -> bool {
+  arg.to_str().is_some_and(|arg| arg.starts_with("http://") || arg.starts_with("https://"))
+}
+
+/// Fetch the URL, giving back a reader over the body of the response.
+pub(crate) fn get(url: &Path) -> io::Result<Box<dyn Read + Send>> {
+  let mut url = url.to_str().unwrap_or_default().to_string();
+
+  for _ in 0..=MAX_REDIRECTS {
+    match request(&url)? {
+      Response::Body(body) => return Ok(body),
+      Response::Redirect(location) => url = location
+    }
+  }
+
+  Err(io::Error::other("too many redirects"))
+}
+
+enum Response {
+  Body(Box<dyn Read + Send>),
+  Redirect(String)
+}
+
+fn request(url: &str) -> io::Result<Response> {
+  if url.starts_with("https://") {
+    return Err(io::Error::new(io::ErrorKind::Unsupported, "https isn't supported, only http"));
+  }
+
+  let rest = url.strip_prefix("http://").ok_or_else(|| invalid("not an http URL"))?;
+  let rest = rest.split('#').next().unwrap_or_default();
+  let (authority, path) = match rest.find('/') {
+    Some(slash) => (&rest[..slash], &rest[slash..]),
+    None => (rest, "/")
+  };
+  let (host, port) = host_port(authority)?;
+
+  let mut stream = connect(host, port)?;
+  write!(stream, "GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: arg_input\r\nAccept-Encoding: identity\r\nConnection: close\r\n\r\n", path, authority)?;
+  stream.flush()?;
+
+  let mut reader = BufReader::new(stream);
+  let mut status_line = String::new();
+  reader.read_line(&mut status_line)?;
+
+  let status: u16 = status_line.split_whitespace().nth(1)
+    .and_then(|status| status.parse().ok())
+    .ok_or_else(|| invalid("not an HTTP response"))?;
+
+  let mut content_length = None;
+  let mut chunked = false;
+  let mut location = None;
+
+  loop {
+    let mut header = String::new();
+
+    if reader.read_line(&mut header)? == 0 {
+      return Err(invalid("response ends inside its headers"));
+    }
+
+    let header = header.trim_end();
+
+    if header.is_empty() {
+      break;
+    }
+
+    if let Some((name, value)) = header.split_once(':') {
+      let value = value.trim();
+
+      match name.trim().to_ascii_lowercase().as_str() {
+        "content-length" => content_length = value.parse::<u64>().ok(),
+        "transfer-encoding" => chunked = value.eq_ignore_ascii_case("chunked"),
+        "location" => location = Some(value.to_string()),
+        _ => ()
+      }
+    }
+  }
+
+  match status {
+    200..=299 => {},
+    301 | 302 | 303 | 307 | 308 => {
+      let location = location.ok_or_else(|| invalid("redirect without a location"))?;
+
+      return Ok(Response::Redirect(resolve(authority, path, &location)));
+    },
+    _ => {
+      let kind = match status {
+        404 | 410 => io::ErrorKind::NotFound,
+        401 | 403 => io::ErrorKind::PermissionDenied,
+        _ => io::ErrorKind::Other
+      };
+
+      return Err(io::Error::new(kind, format!("server responded with {}", status_line.trim_end())));
+    }
+  }
+
+  Ok(Response::Body(if chunked {
+    Box::new(Chunked { inner: reader, remaining: 0, done: false })
+  } else if let Some(len) = content_length {
+    Box::new(FixedLength { inner: reader, remaining: len })
+  } else {
+    Box::new(reader)
+  }))
+}
+
+/// Where a redirect to `location` goes, from the URL with the given
+/// `authority` and `path`. Relative redirects stay on the same server, and
+/// ones without a leading `/` are relative to the directory of `path`.
+fn resolve(authority: &str, path: &str, location: &str) -> String {
+  let has_scheme = location.split_once(':').is_some_and(|(scheme, _)| {
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+      && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+  });
+
+  if has_scheme {
+    return location.to_string();
+  }
+
+  // Scheme-relative, like `//host/file`.
+  if location.starts_with("//") {
+    return format!("http:{}", location);
+  }
+
+  if location.starts_with('/') {
+    return format!("http://{}{}", authority, location);
+  }
+
+  let path = path.split('?').next().unwrap_or_default();
+
+  if location.starts_with('?') {
+    return format!("http://{}{}{}", authority, path, location);
+  }
+
+  let dir = &path[..path.rfind('/').map_or(0, |slash| slash + 1)];
+
+  format!("http://{}{}{}", authority, dir, location)
+}
+
+/// Connect to the first address for the host that answers in time.
+fn connect(host: &str, port: u16) -> io::Result<TcpStream> {
+  let mut last_err = None;
+
+  for addr in (host, port).to_socket_addrs()? {
+    match TcpStream::connect_timeout(&addr, TIMEOUT) {
+      Ok(stream) => {
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        return Ok(stream);
+      },
+      Err(err) => last_err = Some(err)
+    }
+  }
+
+  Err(last_err.unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host has no addresses")))
+}
+
+fn host_port(authority: &str) -> io::Result<(&str, u16)> {
+  if authority.is_empty() || authority.contains('@') {
+    return Err(invalid("unsupported URL"));
+  }
+
+  let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+    // An IPv6 address, like `[::1]:8080`.
+    let (host, rest) = bracketed.split_once(']').ok_or_else(|| invalid("unsupported URL"))?;
+    (host, rest.strip_prefix(':'))
+  } else {
+    match authority.rsplit_once(':') {
+      Some((host, port)) => (host, Some(port)),
+      None => (authority, None)
+    }
+  };
+
+  match port {
+    Some(port) => Ok((host, port.parse().map_err(|_| invalid("bad port in URL"))?)),
+    None => Ok((host, 80))
+  }
+}
+
+fn invalid(msg: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// A body with a `Content-Length`, which has to be all there: a connection
+/// closing early is an error, not the end of the body.
+struct FixedLength<R> {
+  inner: R,
+  remaining: u64
+}
+
+impl<R> Read for FixedLength<R> where
+  R: Read
+{
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.remaining == 0 || buf.is_empty() {
+      return Ok(0);
+    }
+
+    let max = u64::min(buf.len() as u64, self.remaining) as usize;
+    let read = self.inner.read(&mut buf[..max])?;
+
+    if read == 0 {
+      return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "response ends before its Content-Length"));
+    }
+
+    self.remaining -= read as u64;
+
+    Ok(read)
+  }
+}
+
+/// Decodes a body sent with `Transfer-Encoding: chunked`, which some servers
+/// use even for HTTP/1.0.
+struct Chunked<R> {
+  inner: R,
+  /// How much of the current chunk is left to read.
+  remaining: u64,
+  done: bool
+}
+
+impl<R> Read for Chunked<R> where
+  R: BufRead
+{
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.done || buf.is_empty() {
+      return Ok(0);
+    }
+
+    if self.remaining == 0 {
+      let mut size_line = String::new();
+      self.inner.read_line(&mut size_line)?;
+
+      let size = size_line.split(';').next().unwrap_or_default().trim();
+      self.remaining = u64::from_str_radix(size, 16).map_err(|_| invalid("bad chunk size"))?;
+
+      if self.remaining == 0 {
+        self.done = true;
+        return Ok(0);
+      }
+    }
+
+    let max = u64::min(buf.len() as u64, self.remaining) as usize;
+    let read = self.inner.read(&mut buf[..max])?;
+
+    if read == 0 {
+      return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "response ends inside a chunk"));
+    }
+
+    self.remaining -= read as u64;
+
+    if self.remaining == 0 {
+      let mut crlf = String::new();
+      self.inner.read_line(&mut crlf)?;
+    }
+
+    Ok(read)
+  }
+}
@@ -0,0 +1,230 @@
+use std::io::{self, Write, BufRead, BufWriter};
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use reader::ArgfReader;
+use lines::strip_newline;
+
+enum Output {
+  /// Output for a file being edited, which replaces it once it's done.
+  File {
+    temp: PathBuf,
+    target: PathBuf,
+    backup: Option<PathBuf>,
+    writer: BufWriter<File>
+  },
+  /// `stdin` can't be edited in place, so output for it just gets passed
+  /// along to `stdout`.
+  Stdout(io::Stdout)
+}
+
+impl Output {
+  fn for_file(target: &Path, backup_suffix: &str) -> io::Result<Self> {
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    let temp = target.with_file_name(format!(".{}.{}.tmp", name, process::id()));
+
+    let file = OpenOptions::new().write(true).create_new(true).open(&temp)?;
+    file.set_permissions(fs::metadata(target)?.permissions())?;
+
+    let backup = if backup_suffix.is_empty() {
+      None
+    } else if backup_suffix.contains('*') {
+      Some(target.with_file_name(backup_suffix.replace('*', &name)))
+    } else {
+      Some(target.with_file_name(format!("{}{}", name, backup_suffix)))
+    };
+
+    Ok(Output::File { temp, target: target.to_path_buf(), backup, writer: BufWriter::new(file) })
+  }
+
+  /// Replace the file being edited with everything that's been written.
+  fn commit(self) -> io::Result<()> {
+    match self {
+      Output::File { temp, target, backup, writer } => {
+        let file = writer.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()?;
+
+        // Back up the original without ever moving it, so that there's
+        // always something at the original path.
+        if let Some(backup) = backup {
+          let _ = fs::remove_file(&backup);
+
+          if fs::hard_link(&target, &backup).is_err() {
+            fs::copy(&target, &backup)?;
+          }
+        }
+
+        fs::rename(&temp, &target)
+      },
+      Output::Stdout(mut stdout) => stdout.flush()
+    }
+  }
+
+  fn writer(&mut self) -> &mut dyn Write {
+    match *self {
+      Output::File { ref mut writer, .. } => writer,
+      Output::Stdout(ref mut stdout) => stdout
+    }
+  }
+}
+
+/// Edit files in place, like Ruby's `ruby -i` or `sed -i`.
+///
+/// Iterating gives back each line of each file, without its newline, and
+/// anything written to the editor becomes the new contents of the file that
+/// the last line came from. Output is written to a temporary file next to the
+/// original, which only replaces the original once the input file has been
+/// completely read, so a crash partway through never leaves a file truncated.
+/// Every file that gets read from gets replaced, even if nothing was written
+/// for it.
+///
+/// `stdin` can't be edited in place, so while reading from `stdin`, output
+/// goes to `stdout` instead.
+///
+/// Originals can be kept around as backups with
+/// [`backup_suffix()`](#method.backup_suffix).
+///
+/// ```no_run
+/// use std::io::Write;
+///
+/// let mut editor = arg_input::input_inplace(&["notes.txt"]).unwrap();
+///
+/// while let Some(line) = editor.next() {
+///   let line = line.unwrap();
+///   writeln!(editor, "{}", line.to_uppercase()).unwrap();
+/// }
+/// ```
+pub struct InplaceEditor {
+  reader: ArgfReader,
+  output: Option<Output>,
+  /// Which source `output` is for.
+  output_index: usize,
+  backup_suffix: String,
+  done: bool
+}
+
+impl InplaceEditor {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    InplaceEditor {
+      reader,
+      output: None,
+      output_index: 0,
+      backup_suffix: String::new(),
+      done: false
+    }
+  }
+
+  /// Keep a backup of each original file, like `ruby -i.bak`. Before `foo.txt`
+  /// gets replaced, the original is saved as `foo.txt` plus the suffix.
+  ///
+  /// If the suffix contains any `*`s, each one is replaced with the file's
+  /// name instead, so `orig_*` backs up `foo.txt` as `orig_foo.txt`, and
+  /// `old/*` backs it up into the (already existing) `old` directory. An
+  /// empty suffix means no backups, which is the default.
+  pub fn backup_suffix(mut self, suffix: &str) -> Self {
+    self.backup_suffix = suffix.to_string();
+    self
+  }
+
+  /// The name of the file currently being edited, or `None` for `stdin`.
+  pub fn current_filename(&self) -> Option<&Path> {
+    self.reader.current_filename()
+  }
+
+  /// Replace the file currently being edited with whatever has been written
+  /// for it so far, and stop editing. Anything in that file that hasn't been
+  /// read yet is dropped, and files that haven't been reached are left alone.
+  ///
+  /// Dropping the editor without calling this leaves the file currently
+  /// being edited untouched instead.
+  pub fn finish(mut self) -> io::Result<()> {
+    self.done = true;
+    self.commit()
+  }
+
+  fn output(&mut self) -> io::Result<&mut Output> {
+    let index = self.reader.source_index();
+
+    if self.output.is_none() || self.output_index != index {
+      let output = match self.reader.current_filename() {
+        Some(path) => Output::for_file(path, &self.backup_suffix)?,
+        None => Output::Stdout(io::stdout())
+      };
+
+      self.output = Some(output);
+      self.output_index = index;
+    }
+
+    match self.output {
+      Some(ref mut output) => Ok(output),
+      None => unreachable!()
+    }
+  }
+
+  fn commit(&mut self) -> io::Result<()> {
+    match self.output.take() {
+      Some(output) => output.commit(),
+      None => Ok(())
+    }
+  }
+
+  fn next_line(&mut self) -> io::Result<Option<String>> {
+    let mut line = String::new();
+
+    while !self.done {
+      // Starting to read a file at all means it gets replaced.
+      self.output()?;
+
+      if self.reader.current_source().read_line(&mut line)? > 0 {
+        strip_newline(&mut line);
+        return Ok(Some(line));
+      }
+
+      self.commit()?;
+
+      if !self.reader.next_source() {
+        self.done = true;
+      }
+    }
+
+    Ok(None)
+  }
+}
+
+impl Iterator for InplaceEditor {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self.next_line() {
+      Ok(Some(line)) => Some(Ok(line)),
+      Ok(None) => None,
+      Err(err) => Some(Err(err))
+    }
+  }
+}
+
+impl Write for InplaceEditor {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    if self.done {
+      return io::stdout().write(buf);
+    }
+
+    self.output()?.writer().write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    match self.output {
+      Some(ref mut output) => output.writer().flush(),
+      None => Ok(())
+    }
+  }
+}
+
+impl Drop for InplaceEditor {
+  fn drop(&mut self) {
+    if let Some(Output::File { ref temp, .. }) = self.output {
+      let _ = fs::remove_file(temp);
+    }
+  }
+}
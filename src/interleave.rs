@@ -0,0 +1,55 @@
+use std::io::{self, BufRead, BufReader, Read};
+
+use lines::strip_newline;
+
+/// An iterator over the lines of every input, taking one line from each in
+/// turn, like `paste` does, rather than reading them one after another.
+///
+/// Once a file runs out of lines, it drops out of the rotation, and the
+/// rest carry on without it, so the lines of longer files come out one
+/// after another at the end. A file which fails to read drops out too, after
+/// its error has been given back.
+pub struct InterleavedLines {
+  readers: Vec<BufReader<Box<dyn Read + Send>>>,
+  /// Which reader the next line comes from.
+  next: usize
+}
+
+impl InterleavedLines {
+  pub(crate) fn new(readers: Vec<Box<dyn Read + Send>>) -> Self {
+    InterleavedLines { readers: readers.into_iter().map(BufReader::new).collect(), next: 0 }
+  }
+}
+
+impl Iterator for InterleavedLines {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while !self.readers.is_empty() {
+      let index = self.next % self.readers.len();
+      let mut line = String::new();
+
+      match self.readers[index].read_line(&mut line) {
+        Ok(0) => {
+          // The one after it moves into its place, so is next anyway.
+          self.readers.remove(index);
+          self.next = index;
+        },
+        Ok(_) => {
+          strip_newline(&mut line);
+          self.next = index + 1;
+
+          return Some(Ok(line));
+        },
+        Err(err) => {
+          self.readers.remove(index);
+          self.next = index;
+
+          return Some(Err(err));
+        }
+      }
+    }
+
+    None
+  }
+}
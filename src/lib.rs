@@ -9,88 +9,412 @@
 //! file arguments. If you need a little more control (for example, you're using `docopt`
 //! to parse command line arguments instead), use [`input()`](fn.input.html) or
 //! [`input_lines()`](fn.input_lines.html)
+//!
+//! With the `gzip` feature enabled, any input file whose name ends in `.gz` is
+//! transparently decompressed. Set
+//! [`auto_decompress()`](struct.InputBuilder.html#method.auto_decompress) to
+//! go by what's in the file instead.
+//!
+//! With the `zstd` and `bzip2` features enabled, the same goes for files
+//! ending in `.zst` and `.bz2`.
+//!
+//! With the `glob` feature enabled, [`input_globbed()`](fn.input_globbed.html)
+//! expands wildcards in arguments, for when the shell doesn't.
+//!
+//! With the `mmap` feature enabled, [`input_mmap()`](fn.input_mmap.html) maps
+//! input files into memory, instead of reading them.
+//!
+//! With the `encoding` feature enabled,
+//! [`input_lines_encoded()`](fn.input_lines_encoded.html) reads text which
+//! isn't UTF-8.
+//!
+//! With the `csv` feature enabled, [`input_csv()`](fn.input_csv.html) reads
+//! CSV records from all input.
+//!
+//! With the `http` feature enabled, `http://` arguments are fetched and
+//! streamed in along with everything else. There's no TLS, so `https://`
+//! arguments fail to open.
+//!
+//! `argf()` and `input()` give back an [`ArgfReader`](struct.ArgfReader.html),
+//! which also knows which file it's currently reading from.
+//!
+//! With the `async` feature enabled, [`input_async()`](fn.input_async.html)
+//! gives back input that async code can read without blocking, whatever the
+//! runtime, and [`input_lines_stream()`](fn.input_lines_stream.html) a
+//! stream of its lines. An `ArgfReader` can also just be sent to a blocking thread, for
+//! example with `tokio::task::spawn_blocking()`.
+//!
+//! With the `sha256` feature enabled, [`input_sha256()`](fn.input_sha256.html)
+//! hashes all input, like `cat *.bin | sha256sum`.
+//!
+//! [`prelude`](prelude/index.html) brings in the most common functions and
+//! types in one go, with `use arg_input::prelude::*;`.
+
+pub mod prelude;
+
+mod reader;
+mod lines;
+mod builder;
+mod chars;
+mod seekable;
+mod inplace;
+mod walk;
+mod names;
+mod parallel;
+mod ndjson;
+mod words;
+mod chunks;
+mod interleave;
+mod retry;
+mod open;
+mod tail;
+#[cfg(feature = "gzip")]
+mod gzip;
+#[cfg(feature = "zstd")]
+mod zstd;
+#[cfg(feature = "bzip2")]
+mod bzip2;
+#[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+mod sniff;
+#[cfg(feature = "glob")]
+mod glob;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "encoding")]
+mod encoding;
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "async")]
+mod async_input;
+#[cfg(feature = "sha256")]
+mod sha256;
+
+pub use reader::{ArgfReader, SourceInfo};
+pub use lines::{PathLines, NumberedLines, RawLines, Paragraphs, SkipLines, FilteredLines, Matches, Match, TaggedLines, TaggedLine, StreamingLines, LineError, LossyLines};
+pub use builder::{InputBuilder, SortKey, DEV_STDIN_ALIASES, STDIN_ALIAS_VAR};
+pub use chars::{Chars, CharsError};
+pub use seekable::SeekableArgf;
+pub use inplace::InplaceEditor;
+pub use ndjson::{NdjsonValues, NdjsonError};
+pub use words::Words;
+pub use chunks::Chunks;
+pub use interleave::InterleavedLines;
+pub use retry::TRANSIENT_ERRORS;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapInput;
+#[cfg(feature = "encoding")]
+pub use encoding::{Encoding, DecodeReader};
+#[cfg(feature = "csv")]
+pub use csv::CsvRecords;
+#[cfg(feature = "async")]
+pub use async_input::{AsyncInput, ReadFuture, AsyncLines, NextLine};
 
 use std::env::args_os;
-use std::iter::ExactSizeIterator;
+use std::iter::{self, ExactSizeIterator};
 use std::io::{self, Read};
-use std::io::{BufReader, BufRead};
+use std::io::BufRead;
+#[cfg(feature = "encoding")]
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::ffi::OsString;
 use std::fs::File;
-use std::path::Path;
 use std::error::Error;
-use std::fmt::{self, Display, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::convert::From;
 
+use open::Opener;
+#[cfg(feature = "sha256")]
+use sha256::Sha256;
+
 #[derive(Debug)]
 pub struct FailReadFileError {
   pub inner: io::Error,
-  pub filename: String
+  /// Where the file was in the list of inputs, starting from 0.
+  pub arg_index: usize,
+  /// The name of the file, made printable.
+  pub filename: String,
+  /// The exact name of the file, even if it isn't valid Unicode.
+  pub os_filename: OsString
+}
+
+impl FailReadFileError {
+  pub(crate) fn new(arg_index: usize, path: &Path, inner: io::Error) -> Self {
+    FailReadFileError {
+      inner,
+      arg_index,
+      filename: path.to_string_lossy().to_string(),
+      os_filename: path.as_os_str().to_os_string()
+    }
+  }
 }
 
 impl Display for FailReadFileError {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    writeln!(f, "could not read file {}", self.filename)?;
+    writeln!(f, "could not read argument #{} ({})", self.arg_index + 1, self.filename)?;
     write!(f, "caused by: {}", self.inner)?;
     Ok(())
   }
 }
 
 impl Error for FailReadFileError {
-  fn description(&self) -> &str {
-    "failed to read file"
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(&self.inner)
+  }
+}
+
+/// A file which opened fine, but then failed partway through being read,
+/// say because of a disk error. These show up as the inner error of the
+/// `io::Error` that reading gives back:
+///
+/// ```no_run
+/// use arg_input::FailReadAtError;
+///
+/// for line in arg_input::input_lines(&["a.txt", "b.txt"]).unwrap() {
+///   if let Err(err) = line {
+///     if let Some(err) = err.get_ref().and_then(|err| err.downcast_ref::<FailReadAtError>()) {
+///       eprintln!("{} broke at line {}", err.filename, err.file_line);
+///     }
+///   }
+/// }
+/// ```
+///
+/// Where it happened is as far as reading had got, not counting anything
+/// that had already been read ahead into the buffer.
+#[derive(Debug)]
+pub struct FailReadAtError {
+  pub inner: io::Error,
+  /// Where the file was in the list of inputs, starting from 0.
+  pub arg_index: usize,
+  /// The name of the file, made printable, or `-` for `stdin`.
+  pub filename: String,
+  /// The exact name of the file, even if it isn't valid Unicode.
+  pub os_filename: OsString,
+  /// The line being read, starting from 1, counting across all input.
+  pub line: u64,
+  /// The line being read, starting from 1, counting from the start of this
+  /// file.
+  pub file_line: u64,
+  /// How many bytes had been read, across all input.
+  pub offset: u64,
+  /// How many bytes had been read from this file.
+  pub file_offset: u64
+}
+
+impl FailReadAtError {
+  pub(crate) fn new(arg_index: usize, path: &Path, line: u64, file_line: u64, offset: u64, file_offset: u64, inner: io::Error) -> Self {
+    FailReadAtError {
+      inner,
+      arg_index,
+      filename: path.to_string_lossy().to_string(),
+      os_filename: path.as_os_str().to_os_string(),
+      line,
+      file_line,
+      offset,
+      file_offset
+    }
+  }
+}
+
+impl Display for FailReadAtError {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    writeln!(f, "could not read argument #{} ({}) at line {}, byte {}", self.arg_index + 1, self.filename, self.file_line, self.file_offset)?;
+    write!(f, "caused by: {}", self.inner)?;
+    Ok(())
   }
+}
 
-  fn cause(&self) -> Option<&Error> {
+impl Error for FailReadAtError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
     Some(&self.inner)
   }
 }
 
+/// What went wrong when setting up input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputErrorKind {
+  /// One or more files couldn't be opened. See `InputError::badfiles`.
+  BadFiles,
+  /// No inputs were given, so `stdin` would have been read instead, but it's
+  /// an interactive terminal.
+  StdinIsTerminal,
+  /// No inputs were given, and falling back to `stdin` wasn't allowed.
+  NoInputs
+}
+
 #[derive(Debug)]
 pub struct InputError {
-  pub badfiles: Vec<FailReadFileError>
+  pub kind: InputErrorKind,
+  pub badfiles: Vec<FailReadFileError>,
+  /// How many inputs there were in all, if known.
+  inputs: Option<usize>
+}
+
+impl InputError {
+  pub(crate) fn new(kind: InputErrorKind) -> Self {
+    InputError { kind, badfiles: Vec::new(), inputs: None }
+  }
+
+  /// Keep track of how many inputs there were, for the summary.
+  pub(crate) fn of_inputs(mut self, inputs: usize) -> Self {
+    self.inputs = Some(inputs);
+    self
+  }
+
+  /// A one-line description of what went wrong, like
+  /// `failed to read 3 of 10 inputs`, without the details of each file.
+  pub fn summary(&self) -> String {
+    match self.kind {
+      InputErrorKind::BadFiles => {
+        let failed = self.badfiles.len();
+
+        match self.inputs {
+          Some(inputs) => format!("failed to read {} of {} {}", failed, inputs, plural(inputs, "input")),
+          None => format!("failed to read {} {}", failed, plural(failed, "input"))
+        }
+      },
+      InputErrorKind::StdinIsTerminal => "no input files given, and stdin is a terminal".to_string(),
+      InputErrorKind::NoInputs => "no input files given".to_string()
+    }
+  }
+}
+
+fn plural(count: usize, word: &str) -> String {
+  if count == 1 { word.to_string() } else { format!("{}s", word) }
 }
 
 impl Display for InputError {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    for e in &self.badfiles {
-      writeln!(f, "{}", e)?;
+    match self.kind {
+      InputErrorKind::BadFiles => {
+        writeln!(f, "{}:", self.summary())?;
+
+        for e in &self.badfiles {
+          writeln!(f, "  #{} ({}): {}", e.arg_index + 1, e.filename, e.inner)?;
+        }
+      },
+      _ => writeln!(f, "{}", self.summary())?
     }
     Ok(())
   }
 }
 
 impl Error for InputError {
-  fn description(&self) -> &str {
-    "failed to read one or more files"
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    self.badfiles.first().map(|err| err as &(dyn Error + 'static))
+  }
+}
+
+impl From<Vec<FailReadFileError>> for InputError {
+  fn from(err: Vec<FailReadFileError>) -> Self {
+    InputError { kind: InputErrorKind::BadFiles, badfiles: err, inputs: None }
+  }
+}
+
+/// One of the inputs, opened but not read from yet.
+#[derive(Debug)]
+pub enum InputFile {
+  /// A file, along with its name.
+  File(PathBuf, File),
+  /// `stdin`, which doesn't get opened.
+  Stdin
+}
+
+/// One of the sources for [`input_mixed()`](fn.input_mixed.html) to chain
+/// together.
+pub enum InputSource {
+  /// A file, opened the same way as by [`input()`](fn.input.html). This is
+  /// always a filename, even if it's `-`.
+  Path(PathBuf),
+  /// Anything else to read from, like an in-memory buffer or a socket. It
+  /// has to be `Send`, so that the reader can move between threads.
+  Reader(Box<dyn Read + Send>),
+  Stdin
+}
+
+impl Debug for InputSource {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      InputSource::Path(ref path) => f.debug_tuple("Path").field(path).finish(),
+      InputSource::Reader(_) => f.debug_tuple("Reader").finish(),
+      InputSource::Stdin => write!(f, "Stdin")
+    }
   }
+}
+
+/// One of the sources that input would be read from, once arguments have
+/// been expanded, but before anything gets opened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedSource {
+  File(PathBuf),
+  Stdin
+}
 
-  fn cause(&self) -> Option<&Error> {
-    let first = self.badfiles.first();
+/// Every input which could be opened, in order, along with the errors for
+/// the ones which couldn't.
+pub struct InputReport {
+  /// The name of each input that opened, with a reader over it. `stdin` gets
+  /// the name `-`.
+  pub readers: Vec<(PathBuf, Box<dyn Read + Send>)>,
+  pub errors: Vec<FailReadFileError>
+}
+
+/// Something that went wrong either while setting up input, or while
+/// actually reading it.
+#[derive(Debug)]
+pub enum InputReadError {
+  /// The input couldn't be set up in the first place.
+  Input(InputError),
+  /// Reading failed partway through.
+  Io(io::Error)
+}
 
-    // There's some weird casting that I have to do here
-    // that I don't fully understand.
-    match first {
-      Some(err) => Some(err),
-      None => None
+impl Display for InputReadError {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      InputReadError::Input(ref err) => write!(f, "{}", err),
+      InputReadError::Io(ref err) => write!(f, "could not read input: {}", err)
     }
   }
 }
 
-impl From<Vec<FailReadFileError>> for InputError {
-  fn from(err: Vec<FailReadFileError>) -> Self {
-    InputError { badfiles: err }
+impl Error for InputReadError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      InputReadError::Input(ref err) => Some(err),
+      InputReadError::Io(ref err) => Some(err)
+    }
   }
 }
 
-/// Add the attempt_map() function to all iterators.
-trait TryIterator {
+impl From<InputError> for InputReadError {
+  fn from(err: InputError) -> Self {
+    InputReadError::Input(err)
+  }
+}
+
+impl From<io::Error> for InputReadError {
+  fn from(err: io::Error) -> Self {
+    InputReadError::Io(err)
+  }
+}
+
+/// Add the attempt_map() and attempt_map_partial() functions to all iterators.
+pub(crate) trait TryIterator {
   type Item;
   type JIter: ExactSizeIterator<Item=Self::Item>;
 
   /// Attempt to map the function over the given iterator, which might fail.
-  /// If all attempts succeed, give back all the success. Otherwise, give
-  /// back all the errors.
+  /// The function also gets the position of each item. If all attempts
+  /// succeed, give back all the success. Otherwise, give back all the errors.
   fn attempt_map<F, T, E>(self, mapper: F) -> Result<Vec<T>, Vec<E>> where
-    F: Fn(Self::Item) -> Result<T, E>;
+    F: Fn(usize, Self::Item) -> Result<T, E>;
+
+  /// Attempt to map the function over the given iterator, which might fail.
+  /// Give back both all the successes and all the errors.
+  fn attempt_map_partial<F, T, E>(self, mapper: F) -> (Vec<T>, Vec<E>) where
+    F: Fn(usize, Self::Item) -> Result<T, E>;
 }
 
 impl<I> TryIterator for I where
@@ -100,31 +424,42 @@ impl<I> TryIterator for I where
   type JIter = I;
 
   fn attempt_map<F, T, E>(self, mapper: F) -> Result<Vec<T>, Vec<E>> where
-    F: Fn(Self::Item) -> Result<T, E>
+    F: Fn(usize, Self::Item) -> Result<T, E>
+  {
+    let (successes, failures) = self.attempt_map_partial(mapper);
+
+    if failures.is_empty() { Ok(successes) } else { Err(failures) }
+  }
+
+  fn attempt_map_partial<F, T, E>(self, mapper: F) -> (Vec<T>, Vec<E>) where
+    F: Fn(usize, Self::Item) -> Result<T, E>
   {
-    let mut any_failure = false;
     let mut successes = Vec::new();
     let mut failures = Vec::new();
 
-    for obj in self {
-      match mapper(obj) {
-        Ok(output) => {
-          if !any_failure {
-            successes.push(output);
-          }
-        },
-        Err(err) => {
-          any_failure = true;
-          failures.push(err);
-        }
+    for (index, obj) in self.enumerate() {
+      match mapper(index, obj) {
+        Ok(output) => successes.push(output),
+        Err(err) => failures.push(err)
       };
     }
 
-    if any_failure { Err(failures) } else { Ok(successes) }
+    (successes, failures)
   }
 }
 
-pub type Lines = io::Lines<BufReader<Box<Read>>>;
+/// The lines of input, as given back by [`input_lines()`](fn.input_lines.html).
+///
+/// This reads straight from the concrete `ArgfReader`, without any boxing.
+/// The reader can be swapped out, say for `Lines<BufReader<File>>`, to name
+/// the lines of something else with the same type.
+pub type Lines<R = ArgfReader> = io::Lines<R>;
+pub type Split = io::Split<ArgfReader>;
+pub type Bytes = io::Bytes<ArgfReader>;
+pub type Limited = io::Take<ArgfReader>;
+pub type Head = iter::Take<Lines>;
+#[cfg(feature = "encoding")]
+pub type EncodedLines = io::Lines<BufReader<DecodeReader<ArgfReader>>>;
 
 /// Act like [`input_lines()`](fn.input_lines.html), but automatically
 /// pull arguments from the command line. 
@@ -132,9 +467,27 @@ pub type Lines = io::Lines<BufReader<Box<Read>>>;
 /// See [`argf()`](fn.argf.html) for caveats.
 pub fn argf_lines() -> Result<Lines, InputError> {
   let chained = argf()?;
-  let buffered = BufReader::new(chained);
 
-  Ok(buffered.lines())
+  Ok(chained.lines())
+}
+
+/// Act like [`input_bytes()`](fn.input_bytes.html), but automatically pull
+/// arguments from the command line.
+///
+/// See [`argf()`](fn.argf.html) for caveats.
+pub fn argf_bytes() -> Result<Bytes, InputError> {
+  let chained = argf()?;
+
+  Ok(chained.bytes())
+}
+
+/// Act like [`input_lines_with_capacity()`](fn.input_lines_with_capacity.html),
+/// but automatically pull arguments from the command line.
+///
+/// See [`argf()`](fn.argf.html) for caveats.
+pub fn argf_lines_with_capacity(capacity: usize) -> Result<Lines, InputError> {
+  let args = args_os().skip(1);
+  input_lines_with_capacity(args, capacity)
 }
 
 /// Act like [`input()`](fn.input.html), but automatically pull arguments
@@ -145,13 +498,29 @@ pub fn argf_lines() -> Result<Lines, InputError> {
 /// treated like file names. If this is not the case and you need more fine-grained
 /// control (e.g. you're using `docopt` to parse command-line arguments instead),
 /// use `input()`.
-pub fn argf() -> Result<Box<Read>, InputError> {
+pub fn argf() -> Result<ArgfReader, InputError> {
   let args = args_os().skip(1);
   input(args)
 }
 
+/// Act like [`input_to_string()`](fn.input_to_string.html), but
+/// automatically pull arguments from the command line.
+///
+/// See [`argf()`](fn.argf.html) for caveats.
+pub fn argf_to_string() -> Result<String, InputReadError> {
+  let args = args_os().skip(1);
+  input_to_string(args)
+}
+
 /// Return an iterator over all lines of input. 
 ///
+/// Lines are split on `\n`, and a `\r` is stripped off only when it comes
+/// right before that `\n`, so files with Windows line endings give the same
+/// lines as ones with Unix line endings.
+///
+/// The iterator can be sent to another thread. To get a stream of lines in
+/// async code, see [`input_lines_stream()`](fn.input_lines_stream.html).
+///
 /// See [`input()`](fn.input.html) for how this handles its arguments/errors.
 pub fn input_lines<I, J, S>(inputs: I) -> Result<Lines, InputError> where
   I: IntoIterator<Item=S, IntoIter=J>,
@@ -159,56 +528,1085 @@ pub fn input_lines<I, J, S>(inputs: I) -> Result<Lines, InputError> where
   S: AsRef<Path>
 {
   let chained = input(inputs)?;
-  let buffered = BufReader::new(chained);
 
-  Ok(buffered.lines())
+  Ok(chained.lines())
 }
 
-/// Return a `Read` instance with all the input files/`stdin` chained together.
+/// Act like [`input_lines()`](fn.input_lines.html), but buffer `capacity`
+/// bytes of input at a time, instead of the default 8 KiB.
 ///
-/// If any of the files fail to open, returns a `Vec` of all the IO errors
-/// instead.
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_lines_with_capacity<I, J, S>(inputs: I, capacity: usize) -> Result<Lines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = InputBuilder::new().buffer_capacity(capacity).build(inputs)?;
+
+  Ok(chained.lines())
+}
+
+/// Return an iterator over only the first `n` lines of input, counting across
+/// all files, like `head`.
 ///
-/// If *no* files are specified as inputs, this reads solely from `stdin`.
-/// Otherwise, ignores `stdin` and concatenates the contents of all files
-/// specified as arguments.
-/// The argument "-" is special, and is an alias for `stdin`; this can be
-/// used to reinsert `stdin` into the contents returned, if so desired.
-pub fn input<I, J, S>(inputs: I) -> Result<Box<Read>, InputError> where
+/// Nothing gets read past the end of the `n`th line. With lazy input, this
+/// means that files after that point never even get opened:
+///
+/// ```no_run
+/// use std::io::BufRead;
+/// use arg_input::InputBuilder;
+///
+/// let head = InputBuilder::new()
+///   .lazy(true)
+///   .build(&["a.txt", "b.txt"])
+///   .unwrap()
+///   .lines()
+///   .take(10);
+/// ```
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_head<I, J, S>(inputs: I, n: usize) -> Result<Head, InputError> where
   I: IntoIterator<Item=S, IntoIter=J>,
   J: ExactSizeIterator<Item=S>,
   S: AsRef<Path>
 {
-  let iter = inputs.into_iter();
+  let lines = input_lines(inputs)?;
+
+  Ok(lines.take(n))
+}
 
-  if iter.len() == 0 {
-    Ok(Box::new(io::stdin()))
-  } else {
-    let reads = iter.attempt_map(|path| from_arg(path.as_ref()))?;
+/// Give back the last `n` lines of all the input put together, like `tail`.
+/// Only those lines have to be valid UTF-8. Lines can span files, the same
+/// way as with [`input_lines()`](fn.input_lines.html).
+///
+/// Input generally gets read from start to end, keeping only the last `n`
+/// lines seen so far, since things like `stdin` can't be read backwards.
+/// When every input is a regular file that gets read as-is, though, this
+/// seeks backwards from the end instead, so big files don't get read in
+/// full.
+///
+/// Fails if any of the files can't be opened, the same way as
+/// [`input()`](fn.input.html), or if reading fails.
+pub fn input_tail<I, J, S>(inputs: I, n: usize) -> Result<Vec<String>, InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let inputs: Vec<PathBuf> = inputs.into_iter().map(|input| input.as_ref().to_path_buf()).collect();
+  let opener = Opener::default();
 
-    Ok(chain_all_reads(reads))
+  if inputs.iter().all(|input| opener.opens_as_is(input)) {
+    // Anything that can't be seeked, like `stdin`, gets read the slow way.
+    if let Ok(mut seekable) = input_seekable(&inputs) {
+      return Ok(tail::tail_seekable(&mut seekable, n)?);
+    }
   }
+
+  let chained = input(inputs)?;
+
+  Ok(tail::tail_lines(chained, n)?)
 }
 
-fn chain_all_reads<I>(reads: I) -> Box<Read> where
-  I: IntoIterator<Item=Box<Read>>
+/// Return an iterator over all lines of input except the first `n`, counting
+/// across all files, say to leave out a header.
+///
+/// See [`SkipLines`](struct.SkipLines.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_skip_lines<I, J, S>(inputs: I, n: usize) -> Result<SkipLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
 {
-  reads.into_iter().fold(Box::new(io::empty()), |read, next| {
-    Box::new(read.chain(next))
-  })
+  let chained = input(inputs)?;
+
+  Ok(SkipLines::new(chained, n))
 }
 
-fn from_arg<'a>(arg: &'a Path) -> Result<Box<Read>, FailReadFileError> {
-  let str_repr = arg.to_string_lossy();
-  if str_repr == "-" {
-    Ok(Box::new(io::stdin()))
-  } else {
-    let file = File::open(arg).map_err(|err| {
-      FailReadFileError {
-        inner: err,
-        filename: arg.to_string_lossy().to_string()
-      }
-    })?;
-    Ok(Box::new(file))
+/// Return an iterator over only the lines of input for which `predicate`
+/// gives back `true`.
+///
+/// This is like calling `filter()` on [`input_lines()`](fn.input_lines.html),
+/// but lines which don't match are never allocated. See
+/// [`FilteredLines`](struct.FilteredLines.html), and [`input()`](fn.input.html)
+/// for how this handles its arguments/errors.
+pub fn input_lines_filtered<I, J, S, F>(inputs: I, predicate: F) -> Result<FilteredLines<F>, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  F: FnMut(&str) -> bool
+{
+  let chained = input(inputs)?;
+
+  Ok(FilteredLines::new(chained, predicate))
+}
+
+/// Return an iterator over the lines of input which match a predicate, each
+/// along with the file it came from and its line number in that file, for
+/// searching through logs like `grep -n`.
+///
+/// This takes a predicate on each line instead of a `&Regex` behind a
+/// `regex` feature, since the crate doesn't depend on `regex`. The predicate
+/// can use whichever engine you like, say `|line| pattern.is_match(line)`.
+/// Every line is read into the same buffer, so ones that don't match are
+/// skipped without allocating.
+///
+/// ```no_run
+/// let matches = arg_input::input_matches(&["app.log"], |line| line.contains("ERROR")).unwrap();
+///
+/// for found in matches {
+///   let found = found.unwrap();
+///   println!("{}:{}:{}", found.path.display(), found.lineno, found.line);
+/// }
+/// ```
+///
+/// See [`Matches`](struct.Matches.html), and [`input()`](fn.input.html) for
+/// how this handles its arguments/errors.
+pub fn input_matches<I, J, S, F>(inputs: I, predicate: F) -> Result<Matches<F>, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  F: FnMut(&str) -> bool
+{
+  let chained = input(inputs)?;
+
+  Ok(Matches::new(chained, predicate))
+}
+
+/// Call `f` on every line of input, in order, without allocating a new
+/// `String` for each one.
+///
+/// Every line gets read into the same buffer, and `f` is handed a slice of
+/// it, with the line ending stripped off like with
+/// [`input_lines()`](fn.input_lines.html). The slice is only valid until `f`
+/// returns; copy it if it needs to be kept.
+///
+/// Fails if any of the files can't be opened, the same way as
+/// [`input()`](fn.input.html), or if reading fails partway through.
+pub fn input_for_each_line<I, J, S, F>(inputs: I, mut f: F) -> Result<(), InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  F: FnMut(&str)
+{
+  let mut chained = input(inputs)?;
+  let mut line = String::new();
+
+  loop {
+    line.clear();
+
+    if chained.read_line(&mut line)? == 0 {
+      return Ok(());
+    }
+
+    lines::strip_newline(&mut line);
+    f(&line);
+  }
+}
+
+/// Call `f` on every line of input, like
+/// [`input_for_each_line()`](fn.input_for_each_line.html), until `f` fails
+/// with a `BrokenPipe` error, for tools which write each line out as they go.
+///
+/// Once whatever's reading the output goes away, like `head` in
+/// `mytool huge.log | head`, writing fails with `BrokenPipe`. When that
+/// happens, this stops reading and returns `Ok(())`, so the program can exit
+/// quietly instead of reading all its input for nothing. Any other error `f`
+/// gives back gets returned as-is.
+///
+/// ```no_run
+/// use std::io::{self, Write};
+///
+/// let stdout = io::stdout();
+/// let mut out = stdout.lock();
+///
+/// arg_input::input_until_broken_pipe(&["huge.log"], |line| {
+///   writeln!(out, "{}", line)
+/// }).unwrap();
+/// ```
+pub fn input_until_broken_pipe<I, J, S, F>(inputs: I, mut f: F) -> Result<(), InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  F: FnMut(&str) -> io::Result<()>
+{
+  let mut chained = input(inputs)?;
+  let mut line = String::new();
+
+  loop {
+    line.clear();
+
+    if chained.read_line(&mut line)? == 0 {
+      return Ok(());
+    }
+
+    lines::strip_newline(&mut line);
+
+    match f(&line) {
+      Ok(()) => (),
+      Err(ref err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+      Err(err) => return Err(InputReadError::from(err))
+    }
   }
 }
+
+/// Return an iterator over all lines of input, decoded from the given
+/// encoding.
+///
+/// All the input gets decoded as one stream, so a character can be split
+/// across two files. Anything that isn't valid in the encoding gets replaced
+/// with U+FFFD, rather than causing an error. Use
+/// [`Encoding::for_bom()`](enum.Encoding.html#method.for_bom) to pick the
+/// encoding from a byte order mark.
+///
+/// Only available with the `encoding` feature.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+#[cfg(feature = "encoding")]
+pub fn input_lines_encoded<I, J, S>(inputs: I, encoding: Encoding) -> Result<EncodedLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+  let decoded = BufReader::new(DecodeReader::new(chained, encoding));
+
+  Ok(decoded.lines())
+}
+
+/// Return a `Read` instance over all input, transcoded to UTF-8 based on the
+/// byte order mark at the start of the first file.
+///
+/// If the first file starts with a UTF-16 byte order mark, all of the input
+/// is decoded as UTF-16 in that byte order. Otherwise it's all assumed to be
+/// UTF-8. Either way, the byte order mark itself is left out, and anything
+/// which isn't valid gets replaced with U+FFFD. Only the start of the first
+/// file is looked at; byte order marks in later files are left alone.
+///
+/// Only available with the `encoding` feature.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+#[cfg(feature = "encoding")]
+pub fn input_decoded<I, J, S>(inputs: I) -> Result<DecodeReader<ArgfReader>, InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let mut chained = input(inputs)?;
+  let (encoding, bom_len) = Encoding::for_bom(chained.peek_source(3)?).unwrap_or((Encoding::Utf8, 0));
+
+  chained.consume(bom_len);
+
+  Ok(DecodeReader::new(chained, encoding))
+}
+
+/// Return an iterator over the CSV records in all input, as if all the files
+/// were one big CSV file with a single header row.
+///
+/// Only available with the `csv` feature. See
+/// [`CsvRecords`](struct.CsvRecords.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+#[cfg(feature = "csv")]
+pub fn input_csv<I, J, S>(inputs: I) -> Result<CsvRecords, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(CsvRecords::new(chained))
+}
+
+/// Return an iterator over all lines of input, keeping the `\n` or `\r\n`
+/// at the end of each line.
+///
+/// Unlike [`input_lines()`](fn.input_lines.html), this makes it possible to
+/// tell whether the input ended with a newline or not.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_lines_raw<I, J, S>(inputs: I) -> Result<RawLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(RawLines::new(chained))
+}
+
+/// Return an iterator over all lines of input, each tagged with the path of
+/// the file that it came from.
+///
+/// Lines read from `stdin` are tagged with the path `-`.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_lines_with_paths<I, J, S>(inputs: I) -> Result<PathLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(PathLines::new(chained))
+}
+
+/// Return an iterator over all lines of input, each tagged with the path of
+/// the file that it came from, and whether it's the last line of that file.
+///
+/// See [`TaggedLines`](struct.TaggedLines.html), and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_lines_tagged<I, J, S>(inputs: I) -> Result<TaggedLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(TaggedLines::new(chained))
+}
+
+/// Return an iterator over all lines of input, like
+/// [`input_lines()`](fn.input_lines.html), except that anything which isn't
+/// valid UTF-8 gets replaced with `U+FFFD REPLACEMENT CHARACTER`, instead of
+/// being an error. Each line gets replaced on its own, so a few bad bytes
+/// don't stop the rest of the input from being read.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_lines_lossy<I, J, S>(inputs: I) -> Result<LossyLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(LossyLines::new(chained))
+}
+
+/// Return an iterator over all lines of input, which doesn't give up when a
+/// file can't be opened. Instead, each file gets opened once reading gets to
+/// it, and one that can't be opened gives back a
+/// [`LineError::Open`](enum.LineError.html) in its place, so that the lines
+/// of all the other files still come through, in order.
+///
+/// Since nothing gets opened up front, this can't fail.
+pub fn input_lines_streaming<I, J, S>(inputs: I) -> StreamingLines where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  StreamingLines::new(input_lazy(inputs))
+}
+
+/// Return an iterator over all lines of input, which keeps track of both the
+/// overall line number and the line number within the current file.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_lines_numbered<I, J, S>(inputs: I) -> Result<NumberedLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(NumberedLines::new(chained))
+}
+
+/// Return an iterator over newline-delimited JSON values in all input, one
+/// per line, using `parse` to turn each line into a value.
+///
+/// This takes a parse function instead of deserializing into any
+/// `T: DeserializeOwned` itself, since the crate doesn't depend on `serde`.
+/// With `serde_json`, `parse` would usually be
+/// `|line| serde_json::from_str::<T>(line)`. See
+/// [`NdjsonValues`](struct.NdjsonValues.html) for how blank lines and parse
+/// errors are handled, and [`input()`](fn.input.html) for how this handles
+/// its arguments/errors.
+pub fn input_ndjson<I, J, S, F, T, E>(inputs: I, parse: F) -> Result<NdjsonValues<F>, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  F: FnMut(&str) -> Result<T, E>
+{
+  let lines = input_lines_numbered(inputs)?;
+
+  Ok(NdjsonValues::new(lines, parse))
+}
+
+/// Return an iterator over all paragraphs of input, separated by blank lines.
+///
+/// See [`Paragraphs`](struct.Paragraphs.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_paragraphs<I, J, S>(inputs: I) -> Result<Paragraphs, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(Paragraphs::new(chained))
+}
+
+/// Return an iterator over all whitespace-separated words of input.
+///
+/// See [`Words`](struct.Words.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_words<I, J, S>(inputs: I) -> Result<Words, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(Words::new(chained))
+}
+
+/// Return an iterator over all of the input in blocks of `size` bytes, say
+/// for hashing, or for reading a format made of fixed-size records.
+///
+/// Panics if `size` is 0. See [`Chunks`](struct.Chunks.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_chunks<I, J, S>(inputs: I, size: usize) -> Result<Chunks, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(Chunks::new(chained, size))
+}
+
+/// Return an iterator over every byte of input.
+///
+/// The underlying reader is buffered, so this doesn't do a separate read
+/// for every byte.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_bytes<I, J, S>(inputs: I) -> Result<Bytes, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(chained.bytes())
+}
+
+/// Return an iterator over every character of input, decoded as UTF-8.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_chars<I, J, S>(inputs: I) -> Result<Chars, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(Chars::new(chained))
+}
+
+/// Return an iterator over all input, split up on the given byte.
+///
+/// Like with [`input_lines()`](fn.input_lines.html), the split is over the
+/// concatenation of all input, so a record can span across files. The
+/// delimiter itself isn't included in each record.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_split<I, J, S>(inputs: I, delim: u8) -> Result<Split, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(chained.split(delim))
+}
+
+/// Return an iterator over all NUL-separated records of input, like the
+/// output of `find -print0`.
+///
+/// See [`input_split()`](fn.input_split.html).
+pub fn input_zero_terminated<I, J, S>(inputs: I) -> Result<Split, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  input_split(inputs, 0)
+}
+
+/// Return a `Read` instance over at most the first `max_bytes` bytes of
+/// input, like `head -c`. The limit counts across all files, not per file;
+/// once it's reached, the reader reports EOF, and nothing more gets read.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_limited<I, J, S>(inputs: I, max_bytes: u64) -> Result<Limited, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(chained.take(max_bytes))
+}
+
+/// Return a `Read` instance over at most the first `max_bytes` bytes of
+/// each file, like `head -c` run on every file separately, say for sampling
+/// the headers of lots of files. Files shorter than that contribute all of
+/// their contents.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_head_bytes_per_file<I, J, S>(inputs: I, max_bytes: u64) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().head_bytes_per_file(max_bytes).build(inputs)
+}
+
+/// Act like [`input()`](fn.input.html), but lock `stdin` before handing the
+/// reader over to `f`, and keep it locked until `f` returns, if any of the
+/// inputs is `stdin`. That way, other threads can't read any of `stdin` out
+/// from under the reader, even in between its reads.
+///
+/// ```no_run
+/// use std::io::BufRead;
+///
+/// // Reads from `stdin` go through the lock held for `f`.
+/// let total = arg_input::input_locked(&["a.txt", "-"], |reader| {
+///   reader.lines().count()
+/// });
+/// ```
+///
+/// Holding the lock for the whole time the reader is in use is the point,
+/// so anything else which reads `stdin` in the meantime waits until `f`
+/// returns, including other code in `f` which reads `stdin` directly rather
+/// than through the reader: the lock can't be taken twice, even on the same
+/// thread. The reader reads through the held lock instead of locking again.
+///
+/// The lock stays with this thread, since `ArgfReader` has to stay `Send`,
+/// and a lock can't be sent. So `f` should do its reading on this thread
+/// too: a reader sent off to another thread would block on its first read
+/// from `stdin` until `f` returns.
+pub fn input_locked<I, J, S, F, T>(inputs: I, f: F) -> Result<T, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  F: FnOnce(ArgfReader) -> T
+{
+  InputBuilder::new().build_locked(inputs, f)
+}
+
+/// Act like [`input()`](fn.input.html), but take any iterator of inputs,
+/// including ones which don't know how long they are, like
+/// `args_os().skip(1).filter(...)`, without collecting them into a `Vec`
+/// first.
+///
+/// `stdin` still only gets read if the iterator turns out to be empty.
+pub fn input_any<I, S>(inputs: I) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_any(inputs)
+}
+
+/// Chain together files, readers that are already open, and `stdin`, in the
+/// order they're given, for when not all of the input comes from files.
+///
+/// ```no_run
+/// use std::io::Cursor;
+/// use arg_input::InputSource;
+///
+/// let reader = arg_input::input_mixed(vec![
+///   InputSource::Reader(Box::new(Cursor::new("header\n"))),
+///   InputSource::Path("body.txt".into()),
+///   InputSource::Stdin
+/// ]);
+/// ```
+///
+/// Files which fail to open are errors, the same way as with
+/// [`input()`](fn.input.html), and `stdin` only gets read in place of
+/// everything else if there are no sources at all. While reading from one
+/// of the readers, [`current_filename()`](struct.ArgfReader.html#method.current_filename)
+/// gives back `None`, as it does for `stdin`.
+pub fn input_mixed<I>(sources: I) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=InputSource>
+{
+  InputBuilder::new().build_mixed(sources)
+}
+
+/// Return a `Read` instance with all the input files/`stdin` chained together.
+///
+/// If any of the files fail to open, returns a `Vec` of all the IO errors
+/// instead.
+///
+/// If *no* files are specified as inputs, this reads solely from `stdin`.
+/// Otherwise, ignores `stdin` and concatenates the contents of all files
+/// specified as arguments.
+/// The argument "-" is special, and is an alias for `stdin`; this can be
+/// used to reinsert `stdin` into the contents returned, if so desired.
+/// `stdin` is only ever read once, though: if "-" is given more than once,
+/// the first one reads all of `stdin`, and the rest are empty. A file which
+/// really is called `-` can be read by passing it as `./-`.
+///
+/// Empty files contribute nothing at all: no extra newline, and no lines,
+/// words, or paragraphs of their own, so overall line numbers carry on from
+/// the file before as if they weren't there.
+///
+/// Named pipes, like the ones from `<(...)` in a shell, get streamed like
+/// `stdin`, and are never treated as having a size. Opening one waits until
+/// something opens it for writing, so it only gets opened once reading gets
+/// to it, and a failure to open it then comes back from that read. One with
+/// no writer holds up reading rather than looking empty.
+pub fn input<I, J, S>(inputs: I) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build(inputs)
+}
+
+/// Return all input as an [`ArgfReader`](struct.ArgfReader.html), a concrete
+/// type which implements `Read` itself, so it can be kept in a struct field
+/// and read from without going through a trait object.
+///
+/// This is exactly [`input()`](fn.input.html), which gives back the same
+/// type; see there for how this handles its arguments/errors.
+pub fn input_reader<I, J, S>(inputs: I) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  input(inputs)
+}
+
+/// Read all of the input into a `String`.
+///
+/// Fails if any of the files can't be opened, the same way as
+/// [`input()`](fn.input.html), or if reading fails or the input isn't valid
+/// UTF-8.
+pub fn input_to_string<I, J, S>(inputs: I) -> Result<String, InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let mut chained = input(inputs)?;
+  let mut contents = String::new();
+
+  chained.read_to_string(&mut contents)?;
+
+  Ok(contents)
+}
+
+/// Read all of the input into a `Vec`.
+///
+/// If all the inputs are regular files, the `Vec` is sized up front to fit
+/// them all. Fails if any of the files can't be opened, the same way as
+/// [`input()`](fn.input.html), or if reading fails.
+pub fn input_to_bytes<I, J, S>(inputs: I) -> Result<Vec<u8>, InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let mut chained = input(inputs)?;
+  let capacity = chained.total_len().unwrap_or(0);
+  let mut contents = Vec::with_capacity(capacity as usize);
+
+  chained.read_to_end(&mut contents)?;
+
+  Ok(contents)
+}
+
+/// Count the lines in all of the input, like `wc -l`, without reading any of
+/// them into a `String`.
+///
+/// Like `wc -l`, this counts newlines, so a last line with no newline at the
+/// end doesn't get counted. That includes the last line of each file, not
+/// just the last file: `a` and `b` with no newlines count as 0 lines, even
+/// though [`input_lines()`](fn.input_lines.html) would give back `ab`.
+///
+/// Fails if any of the files can't be opened, the same way as
+/// [`input()`](fn.input.html), or if reading fails.
+pub fn count_lines<I, J, S>(inputs: I) -> Result<u64, InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let mut chained = input(inputs)?;
+  let mut count = 0;
+
+  loop {
+    let amt = {
+      let buf = chained.fill_buf()?;
+
+      if buf.is_empty() {
+        return Ok(count);
+      }
+
+      count += buf.iter().filter(|&&byte| byte == b'\n').count() as u64;
+      buf.len()
+    };
+
+    chained.consume(amt);
+  }
+}
+
+/// Feed all of the input through `hasher`, a buffer at a time, and give it
+/// back, so that `myhash *.bin` can work like `cat *.bin | sha256sum`.
+///
+/// Any hasher which takes its input through `io::Write` works, like the ones
+/// from the RustCrypto crates (`sha2`, `md-5`, and so on), which implement
+/// it for exactly this:
+///
+/// ```ignore
+/// use sha2::{Digest, Sha256};
+///
+/// let hash = arg_input::input_digest(&["a.bin", "b.bin"], Sha256::new())?.finalize();
+/// ```
+///
+/// With the `sha256` feature,
+/// [`input_sha256()`](fn.input_sha256.html) does this with a SHA-256 hasher
+/// of our own.
+///
+/// Fails if any of the files can't be opened, the same way as
+/// [`input()`](fn.input.html), or if reading or hashing fails.
+pub fn input_digest<I, J, S, H>(inputs: I, mut hasher: H) -> Result<H, InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  H: io::Write
+{
+  let mut chained = input(inputs)?;
+
+  io::copy(&mut chained, &mut hasher)?;
+  hasher.flush()?;
+
+  Ok(hasher)
+}
+
+/// Hash all of the input with SHA-256, giving back the 32 bytes of the hash.
+///
+/// Only available with the `sha256` feature. Fails the same way as
+/// [`input_digest()`](fn.input_digest.html).
+#[cfg(feature = "sha256")]
+pub fn input_sha256<I, J, S>(inputs: I) -> Result<[u8; 32], InputReadError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let hasher = input_digest(inputs, Sha256::new())?;
+
+  Ok(hasher.finish())
+}
+
+/// Read a list of filenames separated by NUL bytes from the given reader,
+/// like the output of `find -print0`, and chain together the contents of all
+/// those files.
+///
+/// Since names are only separated by NULs, they can have spaces or newlines
+/// in them. Once the list has been read, this behaves like
+/// [`input()`](fn.input.html) given those names, except that an empty list
+/// gives back empty input, rather than reading `stdin`, and each name is only
+/// ever a file: `-` is a file called `-`, and nothing gets expanded like
+/// arguments would be.
+pub fn input_from_name_list<R>(names: R) -> Result<ArgfReader, InputReadError> where
+  R: Read
+{
+  InputBuilder::new().build_from_name_list(names)
+}
+
+/// Call `f` separately on each of the inputs, on multiple threads at once,
+/// giving back the results in the same order as the inputs.
+///
+/// Files are spread out over as many threads as there are cores, but `stdin`
+/// can't be shared, so it's always read on the calling thread. Since each file
+/// gets its own call, nothing here reads across file boundaries.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_par_map<I, J, S, F, T>(inputs: I, f: F) -> Result<Vec<T>, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>,
+  F: Fn(&mut dyn Read) -> T + Sync,
+  T: Send
+{
+  InputBuilder::new().par_map(inputs, f)
+}
+
+/// Act like [`input()`](fn.input.html), but also give back the size and
+/// modification time of each source, in the order they'll be read in, for
+/// planning work or showing totals before reading anything.
+///
+/// Files which were left out because they couldn't be opened aren't
+/// included.
+pub fn input_with_metadata<I, J, S>(inputs: I) -> Result<(ArgfReader, Vec<SourceInfo>), InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+  let info = chained.source_info();
+
+  Ok((chained, info))
+}
+
+/// Give back the sources that [`input()`](fn.input.html) would read from,
+/// in the order it would read them, without opening or reading any of them,
+/// say for a `--dry-run` that lists them.
+///
+/// Arguments go through the same expansion they would for reading, like
+/// response files, globbing and de-duplication when an
+/// [`InputBuilder`](struct.InputBuilder.html) turns those on. Since nothing
+/// gets opened, files which don't exist are still listed; only arguments
+/// which fail to expand, like a response file that can't be read, are
+/// errors.
+pub fn resolve_sources<I, J, S>(inputs: I) -> Result<Vec<ResolvedSource>, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().resolve(inputs)
+}
+
+/// Open all the given inputs, and give back the opened files, instead of a
+/// reader over them, say to lock them before reading.
+///
+/// Files are opened as-is, without any decompression. `-` gives back
+/// `InputFile::Stdin`, as does having no inputs at all. Since what comes
+/// back is an open `File`, a named pipe does get opened here, which waits
+/// until something opens it for writing.
+///
+/// See [`input()`](fn.input.html) for how this handles its arguments/errors.
+pub fn input_files<I, J, S>(inputs: I) -> Result<Vec<InputFile>, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_files(inputs)
+}
+
+/// Open all the given inputs, and give back a separate reader for each one
+/// that opened, along with the errors for the ones that didn't, rather than
+/// chaining them together.
+///
+/// Files get decompressed like with [`input()`](fn.input.html). `stdin` only
+/// gets read once, so if `-` is given more than once, only the first one has
+/// anything in it.
+pub fn input_report<I, J, S>(inputs: I) -> InputReport where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_report(inputs)
+}
+
+/// Return an iterator over the lines of all the inputs, taking one line from
+/// each input in turn, rather than all of one input and then the next, say
+/// for merging or zipping files together. Inputs which run out of lines
+/// drop out of the rotation, and the rest carry on without them.
+///
+/// Lines never span files here. See [`input()`](fn.input.html) for how this
+/// handles its arguments/errors.
+pub fn input_interleave_lines<I, J, S>(inputs: I) -> Result<InterleavedLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let iter = inputs.into_iter();
+  let len = iter.len();
+  let report = input_report(iter);
+
+  if !report.errors.is_empty() {
+    return Err(InputError::from(report.errors).of_inputs(len));
+  }
+
+  Ok(InterleavedLines::new(report.readers.into_iter().map(|(_, reader)| reader).collect()))
+}
+
+/// Act like [`input()`](fn.input.html), but don't give up if some of the files
+/// fail to open.
+///
+/// Gives back a reader over all the files that *could* be opened, along with
+/// the errors for all the ones that couldn't. If there were inputs, but none
+/// of them could be opened, there's no reader.
+pub fn input_partial<I, J, S>(inputs: I) -> (Option<ArgfReader>, Vec<FailReadFileError>) where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_partial(inputs)
+}
+
+/// Act like [`input()`](fn.input.html), but skip over any files that fail to
+/// open, the same way tools like `cat` and `grep` do.
+///
+/// Gives back a reader over all the files that could be opened, along with
+/// the errors for all the ones that couldn't, so that they can be reported.
+/// If none of the files could be opened, the reader is empty; it does *not*
+/// fall back to reading `stdin`.
+pub fn input_skipping<I, J, S>(inputs: I) -> (ArgfReader, Vec<FailReadFileError>) where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_skipping(inputs)
+}
+
+/// Act like [`input()`](fn.input.html), but strip off a UTF-8 byte order mark
+/// at the start of the input, if there is one.
+///
+/// See [`InputBuilder::strip_bom()`](struct.InputBuilder.html#method.strip_bom).
+pub fn input_text<I, J, S>(inputs: I) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().strip_bom(true).build(inputs)
+}
+
+/// Open all the given files, so that their concatenation can be seeked
+/// through as well as read.
+///
+/// Since `stdin` can't be seeked, this fails if any of the inputs is `-`, or
+/// if there are no inputs at all. Files are read as-is, without any
+/// decompression.
+pub fn input_seekable<I, J, S>(inputs: I) -> Result<SeekableArgf, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_seekable(inputs)
+}
+
+/// Edit all the given files in place.
+///
+/// Files are read as-is, so with the `gzip` feature enabled, say, a file
+/// ending in `.gz` is an error, rather than having its decompressed contents
+/// written back over it. See [`InplaceEditor`](struct.InplaceEditor.html) for
+/// details, and [`input()`](fn.input.html) for how this handles its
+/// arguments/errors.
+pub fn input_inplace<I, J, S>(inputs: I) -> Result<InplaceEditor, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = InputBuilder::new().build_inplace(inputs)?;
+
+  Ok(InplaceEditor::new(chained))
+}
+
+/// Act like [`input()`](fn.input.html), but expand wildcards like `*.txt` in
+/// the arguments first, the way a Unix shell would.
+///
+/// Only available with the `glob` feature. See
+/// [`InputBuilder::glob()`](struct.InputBuilder.html#method.glob).
+#[cfg(feature = "glob")]
+pub fn input_globbed<I, J, S>(inputs: I) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().glob(true).build(inputs)
+}
+
+/// Map all the given files into memory, so that their contents can be looked
+/// at directly, without reading them. `stdin` gets read into memory instead.
+///
+/// Only available with the `mmap` feature. Files are used as-is, without any
+/// decompression. See [`MmapInput`](struct.MmapInput.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+///
+/// # Safety
+///
+/// Nothing else may truncate or write to any of the files while the
+/// `MmapInput` is alive. Contents which change underneath a `&[u8]` are
+/// undefined behaviour, and reading past the end of a truncated file kills
+/// the process with `SIGBUS`.
+#[cfg(feature = "mmap")]
+pub unsafe fn input_mmap<I, J, S>(inputs: I) -> Result<MmapInput, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_mmap(inputs)
+}
+
+/// Read all input from async code, without blocking the executor.
+///
+/// Only available with the `async` feature. Files are still opened before
+/// this returns, but all the reading happens on a thread of its own. See
+/// [`AsyncInput`](struct.AsyncInput.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+#[cfg(feature = "async")]
+pub fn input_async<I, J, S>(inputs: I) -> Result<AsyncInput, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let chained = input(inputs)?;
+
+  Ok(AsyncInput::new(chained))
+}
+
+/// Return a stream of all lines of input for async code, like
+/// [`input_lines()`](fn.input_lines.html).
+///
+/// Only available with the `async` feature. See
+/// [`AsyncLines`](struct.AsyncLines.html) for details, and
+/// [`input()`](fn.input.html) for how this handles its arguments/errors.
+#[cfg(feature = "async")]
+pub fn input_lines_stream<I, J, S>(inputs: I) -> Result<AsyncLines, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  let lines = input_lines(inputs)?;
+
+  Ok(AsyncLines::new(lines))
+}
+
+/// Act like [`input()`](fn.input.html), but if there are no inputs and
+/// `stdin` is an interactive terminal, fail instead of waiting on the user to
+/// type something in.
+///
+/// See [`InputBuilder::refuse_terminal_stdin()`](struct.InputBuilder.html#method.refuse_terminal_stdin).
+pub fn input_or_err_if_tty<I, J, S>(inputs: I) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().refuse_terminal_stdin(true).build(inputs)
+}
+
+/// Act like [`input()`](fn.input.html), but read from the given reader
+/// wherever `stdin` would have been read from.
+///
+/// This covers both the case where no inputs are given at all, and the `-`
+/// argument. It's mostly useful for testing code that would otherwise read
+/// from the real `stdin`.
+pub fn input_with_stdin<I, J, S>(inputs: I, stdin: Box<dyn Read + Send>) -> Result<ArgfReader, InputError> where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_with_stdin(inputs, stdin)
+}
+
+/// Return a `Read` instance with all the input files/`stdin` chained together,
+/// only opening each file once reading actually reaches it.
+///
+/// This behaves like [`input()`](fn.input.html), except for *when* errors
+/// show up. Rather than opening every file up front and failing with an
+/// `InputError` if any of them can't be opened, a file that fails to open
+/// causes a read to fail with an `io::Error` when the reader gets to it;
+/// the error wraps a `FailReadFileError` saying which file it was. Any
+/// input that came before that file will already have been read. Reading
+/// again afterwards carries on with the next file.
+///
+/// Files that are never reached are never opened. This is cheaper when only
+/// part of the input is needed, but means that bad arguments might not be
+/// noticed at all.
+pub fn input_lazy<I, J, S>(inputs: I) -> ArgfReader where
+  I: IntoIterator<Item=S, IntoIter=J>,
+  J: ExactSizeIterator<Item=S>,
+  S: AsRef<Path>
+{
+  InputBuilder::new().build_lazy(inputs, Box::new(io::stdin()))
+}
@@ -0,0 +1,552 @@
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use reader::ArgfReader;
+use FailReadFileError;
+
+/// Chop off a trailing `\n` or `\r\n`, the same way `io::Lines` does.
+pub(crate) fn strip_newline(line: &mut String) {
+  if line.ends_with('\n') {
+    line.pop();
+    if line.ends_with('\r') {
+      line.pop();
+    }
+  }
+}
+
+/// Read the next line of input into `line`, without letting it span across
+/// sources. Returns `false` once all sources are exhausted.
+pub(crate) fn read_source_line(reader: &mut ArgfReader, line: &mut String) -> io::Result<bool> {
+  loop {
+    if reader.current_source().read_line(line)? > 0 {
+      strip_newline(line);
+      return Ok(true);
+    }
+
+    if !reader.next_source() {
+      return Ok(false);
+    }
+  }
+}
+
+/// Read past the next line of input, without keeping it anywhere. Returns
+/// `false` if there was nothing left to read.
+pub(crate) fn skip_line(reader: &mut ArgfReader) -> io::Result<bool> {
+  let mut skipped = false;
+
+  loop {
+    let (used, done) = {
+      let available = reader.fill_buf()?;
+
+      if available.is_empty() {
+        return Ok(skipped);
+      }
+
+      match available.iter().position(|&byte| byte == b'\n') {
+        Some(newline) => (newline + 1, true),
+        None => (available.len(), false)
+      }
+    };
+
+    reader.consume(used);
+    skipped = true;
+
+    if done {
+      return Ok(true);
+    }
+  }
+}
+
+/// An iterator over all lines of input, each tagged with the file it came
+/// from.
+///
+/// Lines never span files; if a file doesn't end in a newline, its last line
+/// ends with it. Lines from `stdin` are tagged with the path `-`.
+pub struct PathLines {
+  reader: ArgfReader
+}
+
+impl PathLines {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    PathLines { reader }
+  }
+}
+
+impl Iterator for PathLines {
+  type Item = io::Result<(PathBuf, String)>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut line = String::new();
+
+    match read_source_line(&mut self.reader, &mut line) {
+      Ok(true) => {
+        let path = self.reader.current_filename()
+          .map(|path| path.to_path_buf())
+          .unwrap_or_else(|| PathBuf::from("-"));
+
+        Some(Ok((path, line)))
+      },
+      Ok(false) => None,
+      Err(err) => Some(Err(err))
+    }
+  }
+}
+
+/// A line of input from [`TaggedLines`](struct.TaggedLines.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedLine {
+  pub text: String,
+  /// Whether this is the last line of the file it came from.
+  pub is_file_end: bool,
+  /// The file the line came from, or `-` for `stdin`.
+  pub path: PathBuf
+}
+
+/// An iterator over all lines of input, each tagged with the file it came
+/// from, and whether it's the last line of that file, say for printing
+/// something between files.
+///
+/// Finding out whether a line is the last one means reading ahead, so on
+/// something like a pipe or a terminal, a line only comes back once the
+/// next one has started arriving, or the input has ended. Lines never span
+/// files. An empty file has no lines, so never gets a last line either.
+pub struct TaggedLines {
+  reader: ArgfReader
+}
+
+impl TaggedLines {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    TaggedLines { reader }
+  }
+}
+
+impl Iterator for TaggedLines {
+  type Item = io::Result<TaggedLine>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut text = String::new();
+
+    match read_source_line(&mut self.reader, &mut text) {
+      Ok(true) => {},
+      Ok(false) => return None,
+      Err(err) => return Some(Err(err))
+    }
+
+    let is_file_end = match self.reader.current_source().fill_buf() {
+      Ok(rest) => rest.is_empty(),
+      Err(err) => return Some(Err(err))
+    };
+    let path = self.reader.current_filename()
+      .map(|path| path.to_path_buf())
+      .unwrap_or_else(|| PathBuf::from("-"));
+
+    Some(Ok(TaggedLine { text, is_file_end, path }))
+  }
+}
+
+/// An iterator over all lines of input, which keeps count of line numbers,
+/// like Ruby's `ARGF.lineno`.
+///
+/// Line numbers start at 1. [`lineno()`](#method.lineno) keeps counting up
+/// across file boundaries, while [`file_lineno()`](#method.file_lineno)
+/// starts over at each new file. Lines never span files.
+pub struct NumberedLines {
+  reader: ArgfReader,
+  lineno: usize,
+  file_lineno: usize,
+  source_index: usize
+}
+
+impl NumberedLines {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    let source_index = reader.source_index();
+
+    NumberedLines { reader, lineno: 0, file_lineno: 0, source_index }
+  }
+
+  /// The line number of the last line returned, counting across all input.
+  /// `0` if no lines have been read yet.
+  pub fn lineno(&self) -> usize {
+    self.lineno
+  }
+
+  /// The line number of the last line returned, within the file it came
+  /// from. `0` if no lines have been read yet.
+  pub fn file_lineno(&self) -> usize {
+    self.file_lineno
+  }
+
+  /// The name of the file the last line came from, or `None` for `stdin`.
+  ///
+  /// See [`ArgfReader::current_filename()`](struct.ArgfReader.html#method.current_filename).
+  pub fn current_filename(&self) -> Option<&Path> {
+    self.reader.current_filename()
+  }
+}
+
+impl Iterator for NumberedLines {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut line = String::new();
+
+    match read_source_line(&mut self.reader, &mut line) {
+      Ok(true) => {
+        if self.reader.source_index() != self.source_index {
+          self.source_index = self.reader.source_index();
+          self.file_lineno = 0;
+        }
+
+        self.lineno += 1;
+        self.file_lineno += 1;
+
+        Some(Ok(line))
+      },
+      Ok(false) => None,
+      Err(err) => Some(Err(err))
+    }
+  }
+}
+
+/// An iterator over all lines of input, *including* their line terminators.
+///
+/// The last line of input only ends in a newline if the input actually does.
+/// Like with [`Lines`](type.Lines.html), a line can span across files.
+pub struct RawLines {
+  reader: ArgfReader
+}
+
+impl RawLines {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    RawLines { reader }
+  }
+}
+
+impl Iterator for RawLines {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut line = String::new();
+
+    match self.reader.read_line(&mut line) {
+      Ok(0) => None,
+      Ok(_) => Some(Ok(line)),
+      Err(err) => Some(Err(err))
+    }
+  }
+}
+
+/// An iterator over all lines of input, where anything that isn't valid
+/// UTF-8 gets replaced with `U+FFFD REPLACEMENT CHARACTER`, instead of
+/// being an error, say for logs with the odd bit of garbage in them.
+///
+/// Otherwise, this works like [`Lines`](type.Lines.html); lines can span
+/// files.
+pub struct LossyLines {
+  reader: ArgfReader,
+  buf: Vec<u8>
+}
+
+impl LossyLines {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    LossyLines { reader, buf: Vec::new() }
+  }
+}
+
+impl Iterator for LossyLines {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.buf.clear();
+
+    match self.reader.read_until(b'\n', &mut self.buf) {
+      Ok(0) => None,
+      Ok(_) => {
+        let mut line = String::from_utf8_lossy(&self.buf).into_owned();
+        strip_newline(&mut line);
+        Some(Ok(line))
+      },
+      Err(err) => Some(Err(err))
+    }
+  }
+}
+
+/// An iterator over all lines of input after the first few, which get
+/// skipped over.
+///
+/// Skipped lines are still read, but never copied anywhere. Otherwise, this
+/// works like [`Lines`](type.Lines.html); lines can span files.
+pub struct SkipLines {
+  reader: ArgfReader,
+  skip: usize
+}
+
+impl SkipLines {
+  pub(crate) fn new(reader: ArgfReader, skip: usize) -> Self {
+    SkipLines { reader, skip }
+  }
+}
+
+impl Iterator for SkipLines {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.skip > 0 {
+      self.skip -= 1;
+
+      match skip_line(&mut self.reader) {
+        Ok(true) => {},
+        Ok(false) => {
+          self.skip = 0;
+          return None;
+        },
+        Err(err) => return Some(Err(err))
+      }
+    }
+
+    let mut line = String::new();
+
+    match self.reader.read_line(&mut line) {
+      Ok(0) => None,
+      Ok(_) => {
+        strip_newline(&mut line);
+        Some(Ok(line))
+      },
+      Err(err) => Some(Err(err))
+    }
+  }
+}
+
+/// An iterator over only the lines of input which match a predicate.
+///
+/// Every line gets read into the same buffer, and only lines which match are
+/// copied out of it, so rejected lines don't cost an allocation each.
+/// Otherwise, this works like [`Lines`](type.Lines.html); lines can span
+/// files.
+pub struct FilteredLines<F> {
+  reader: ArgfReader,
+  predicate: F,
+  line: String
+}
+
+impl<F> FilteredLines<F> {
+  pub(crate) fn new(reader: ArgfReader, predicate: F) -> Self {
+    FilteredLines { reader, predicate, line: String::new() }
+  }
+}
+
+impl<F> Iterator for FilteredLines<F> where
+  F: FnMut(&str) -> bool
+{
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      self.line.clear();
+
+      match self.reader.read_line(&mut self.line) {
+        Ok(0) => return None,
+        Ok(_) => strip_newline(&mut self.line),
+        Err(err) => return Some(Err(err))
+      }
+
+      if (self.predicate)(&self.line) {
+        return Some(Ok(self.line.clone()));
+      }
+    }
+  }
+}
+
+/// A line of input which matched, from [`Matches`](struct.Matches.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+  /// The file the line came from, or `-` for `stdin`.
+  pub path: PathBuf,
+  /// The line number within that file, starting at 1.
+  pub lineno: usize,
+  pub line: String
+}
+
+/// An iterator over the lines of input which match a predicate, like `grep`,
+/// each along with where it came from.
+///
+/// Like with [`FilteredLines`](struct.FilteredLines.html), lines which don't
+/// match are never copied out of the buffer they get read into. Unlike it,
+/// lines never span files, so that each one has a single file and line
+/// number.
+pub struct Matches<F> {
+  reader: ArgfReader,
+  predicate: F,
+  line: String,
+  file_lineno: usize,
+  source_index: usize
+}
+
+impl<F> Matches<F> {
+  pub(crate) fn new(reader: ArgfReader, predicate: F) -> Self {
+    let source_index = reader.source_index();
+
+    Matches { reader, predicate, line: String::new(), file_lineno: 0, source_index }
+  }
+}
+
+impl<F> Iterator for Matches<F> where
+  F: FnMut(&str) -> bool
+{
+  type Item = io::Result<Match>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      self.line.clear();
+
+      match read_source_line(&mut self.reader, &mut self.line) {
+        Ok(true) => {},
+        Ok(false) => return None,
+        Err(err) => return Some(Err(err))
+      }
+
+      if self.reader.source_index() != self.source_index {
+        self.source_index = self.reader.source_index();
+        self.file_lineno = 0;
+      }
+      self.file_lineno += 1;
+
+      if (self.predicate)(&self.line) {
+        let path = self.reader.current_filename()
+          .map(|path| path.to_path_buf())
+          .unwrap_or_else(|| PathBuf::from("-"));
+
+        return Some(Ok(Match { path, lineno: self.file_lineno, line: self.line.clone() }));
+      }
+    }
+  }
+}
+
+/// An iterator over all paragraphs of input: blocks of lines separated by one
+/// or more blank lines, like Perl and Ruby's paragraph mode.
+///
+/// Paragraphs can span across files, like lines in [`Lines`](type.Lines.html)
+/// can. Blank lines at the start and end of input are ignored. The lines of
+/// each paragraph are joined back together with `\n`, without a newline at
+/// the end.
+pub struct Paragraphs {
+  reader: ArgfReader
+}
+
+impl Paragraphs {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    Paragraphs { reader }
+  }
+}
+
+impl Iterator for Paragraphs {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut paragraph = String::new();
+    let mut line = String::new();
+
+    loop {
+      line.clear();
+
+      match self.reader.read_line(&mut line) {
+        Ok(0) => break,
+        Ok(_) => strip_newline(&mut line),
+        Err(err) => return Some(Err(err))
+      }
+
+      if line.is_empty() {
+        if paragraph.is_empty() {
+          continue;
+        } else {
+          break;
+        }
+      }
+
+      if !paragraph.is_empty() {
+        paragraph.push('\n');
+      }
+      paragraph.push_str(&line);
+    }
+
+    if paragraph.is_empty() { None } else { Some(Ok(paragraph)) }
+  }
+}
+
+/// Something that went wrong partway through
+/// [`StreamingLines`](struct.StreamingLines.html).
+#[derive(Debug)]
+pub enum LineError {
+  /// A file couldn't be opened, once reading got to it.
+  Open(FailReadFileError),
+  /// Reading failed partway through a file.
+  Io(io::Error)
+}
+
+impl LineError {
+  fn new(err: io::Error) -> Self {
+    if !err.get_ref().is_some_and(|inner| inner.is::<FailReadFileError>()) {
+      return LineError::Io(err);
+    }
+
+    match err.into_inner().map(|inner| inner.downcast::<FailReadFileError>()) {
+      Some(Ok(open)) => LineError::Open(*open),
+      // Checked just above.
+      _ => unreachable!()
+    }
+  }
+}
+
+impl Display for LineError {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      LineError::Open(ref err) => write!(f, "{}", err),
+      LineError::Io(ref err) => write!(f, "could not read input: {}", err)
+    }
+  }
+}
+
+impl Error for LineError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      LineError::Open(ref err) => Some(err),
+      LineError::Io(ref err) => Some(err)
+    }
+  }
+}
+
+/// An iterator over all lines of input, which opens each file only once
+/// reading gets to it, and gives back an error in its place if it can't be
+/// opened, rather than failing before reading anything.
+///
+/// Lines and errors come back in the order of the inputs. After an error,
+/// whatever's left of that file is skipped, and reading carries on with the
+/// next one. Lines never span files.
+pub struct StreamingLines {
+  reader: ArgfReader
+}
+
+impl StreamingLines {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    StreamingLines { reader }
+  }
+}
+
+impl Iterator for StreamingLines {
+  type Item = Result<String, LineError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut line = String::new();
+
+    match read_source_line(&mut self.reader, &mut line) {
+      Ok(true) => Some(Ok(line)),
+      Ok(false) => None,
+      Err(err) => {
+        self.reader.skip_current_file();
+        Some(Err(LineError::new(err)))
+      }
+    }
+  }
+}
@@ -0,0 +1,183 @@
+use std::io::{self, Read};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::ops::Deref;
+
+use open::Opener;
+
+/// The contents of one input, either mapped into memory or read into it.
+pub(crate) enum Chunk {
+  #[cfg(unix)]
+  Mapped(Mapping),
+  Read(Vec<u8>)
+}
+
+impl Chunk {
+  /// Map a file into memory, if possible. Anything that can't be mapped,
+  /// like a pipe, gets read into memory instead.
+  pub(crate) fn open(path: &Path, opener: &Opener) -> io::Result<Chunk> {
+    // Whatever a custom opener gives back can only be read.
+    if opener.custom.is_some() {
+      let mut contents = Vec::new();
+      opener.open(path)?.read_to_end(&mut contents)?;
+
+      return Ok(Chunk::Read(contents));
+    }
+
+    let mut file = opener.open_file(path)?;
+    let metadata = file.metadata()?;
+
+    #[cfg(unix)]
+    {
+      if metadata.is_file() && metadata.len() > 0 {
+        if let Ok(mapping) = Mapping::new(&file, metadata.len()) {
+          return Ok(Chunk::Mapped(mapping));
+        }
+      }
+    }
+
+    let mut contents = Vec::with_capacity(metadata.len() as usize);
+    file.read_to_end(&mut contents)?;
+
+    Ok(Chunk::Read(contents))
+  }
+}
+
+impl Deref for Chunk {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    match *self {
+      #[cfg(unix)]
+      Chunk::Mapped(ref mapping) => mapping,
+      Chunk::Read(ref contents) => contents
+    }
+  }
+}
+
+/// A read-only, private mapping of a whole file.
+#[cfg(unix)]
+pub(crate) struct Mapping {
+  ptr: *mut sys::c_void,
+  len: usize
+}
+
+// The mapping is read-only, and nothing here ever writes through it, so it
+// can be shared like a `&[u8]`. Whatever anyone else does to the file is up
+// to the caller of `input_mmap()` to rule out.
+#[cfg(unix)]
+unsafe impl Send for Mapping {}
+#[cfg(unix)]
+unsafe impl Sync for Mapping {}
+
+#[cfg(unix)]
+impl Mapping {
+  fn new(file: &File, len: u64) -> io::Result<Mapping> {
+    use std::os::unix::io::AsRawFd;
+    use std::ptr;
+
+    if len > usize::MAX as u64 {
+      return Err(io::Error::new(io::ErrorKind::InvalidInput, "file is too big to map"));
+    }
+
+    let len = len as usize;
+    let ptr = unsafe {
+      sys::mmap(ptr::null_mut(), len, sys::PROT_READ, sys::MAP_PRIVATE, file.as_raw_fd(), 0)
+    };
+
+    if ptr == sys::MAP_FAILED {
+      Err(io::Error::last_os_error())
+    } else {
+      Ok(Mapping { ptr, len })
+    }
+  }
+}
+
+#[cfg(unix)]
+impl Deref for Mapping {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    unsafe { ::std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+  }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+  fn drop(&mut self) {
+    unsafe {
+      sys::munmap(self.ptr, self.len);
+    }
+  }
+}
+
+#[cfg(unix)]
+mod sys {
+  pub use std::ffi::c_void;
+  use std::os::raw::{c_int, c_long};
+
+  pub const PROT_READ: c_int = 1;
+  pub const MAP_PRIVATE: c_int = 2;
+  pub const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+  extern "C" {
+    // Without large file support, which the plain `mmap` symbol never has,
+    // `off_t` is a `long`. The offset is always 0 here anyway.
+    pub fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: c_long) -> *mut c_void;
+    pub fn munmap(addr: *mut c_void, len: usize) -> c_int;
+  }
+}
+
+/// All of the input, held in memory at once. Files are mapped into memory
+/// where possible, rather than being read, so nothing gets copied until it's
+/// actually looked at; `stdin` gets read into memory as normal.
+///
+/// The input is a series of chunks, one for each source. The length of each
+/// file is checked when it's mapped, so if a file grows afterwards, the new
+/// part isn't seen. Changes to the part that *is* mapped may or may not show
+/// up, and a file which gets truncated while it's mapped kills the process
+/// with `SIGBUS` when the missing part gets looked at; that's why
+/// [`input_mmap()`](fn.input_mmap.html) is `unsafe`.
+pub struct MmapInput {
+  chunks: Vec<(Option<PathBuf>, Chunk)>
+}
+
+impl MmapInput {
+  pub(crate) fn new(chunks: Vec<(Option<PathBuf>, Chunk)>) -> Self {
+    MmapInput { chunks }
+  }
+
+  /// The total length of all input put together.
+  pub fn len(&self) -> usize {
+    self.chunks.iter().map(|(_, chunk)| chunk.len()).sum()
+  }
+
+  /// Whether all input put together is empty.
+  pub fn is_empty(&self) -> bool {
+    self.chunks.iter().all(|(_, chunk)| chunk.is_empty())
+  }
+
+  /// The contents of each source, in order.
+  pub fn chunks(&self) -> impl Iterator<Item=&[u8]> {
+    self.chunks.iter().map(|(_, chunk)| &**chunk)
+  }
+
+  /// The contents of each source, in order, along with the name of the file
+  /// it came from, or `None` for `stdin`.
+  pub fn files(&self) -> impl Iterator<Item=(Option<&Path>, &[u8])> {
+    self.chunks.iter().map(|(path, chunk)| (path.as_ref().map(|path| path.as_path()), &**chunk))
+  }
+
+  /// All of the input as one slice, if it's all in one place already: that
+  /// is, if at most one source has anything in it. Otherwise, use
+  /// [`chunks()`](#method.chunks).
+  pub fn as_bytes(&self) -> Option<&[u8]> {
+    let mut non_empty = self.chunks().filter(|chunk| !chunk.is_empty());
+
+    match (non_empty.next(), non_empty.next()) {
+      (None, _) => Some(&[]),
+      (Some(chunk), None) => Some(chunk),
+      (Some(_), Some(_)) => None
+    }
+  }
+}
@@ -0,0 +1,35 @@
+//! Reading lists of filenames, rather than the contents of files.
+
+use std::path::PathBuf;
+
+/// Split a list of filenames up on the given separator, leaving out any
+/// empty names.
+pub(crate) fn split_names(list: &[u8], separator: u8) -> Vec<PathBuf> {
+  list.split(|&byte| byte == separator)
+    .filter(|name| !name.is_empty())
+    .map(path_from_bytes)
+    .collect()
+}
+
+/// Split a list of filenames up into lines, which can end in either `\n` or
+/// `\r\n`, leaving out any blank lines.
+pub(crate) fn split_name_lines(list: &[u8]) -> Vec<PathBuf> {
+  list.split(|&byte| byte == b'\n')
+    .map(|name| name.strip_suffix(b"\r").unwrap_or(name))
+    .filter(|name| !name.is_empty())
+    .map(path_from_bytes)
+    .collect()
+}
+
+#[cfg(unix)]
+fn path_from_bytes(name: &[u8]) -> PathBuf {
+  use std::ffi::OsStr;
+  use std::os::unix::ffi::OsStrExt;
+
+  PathBuf::from(OsStr::from_bytes(name))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(name: &[u8]) -> PathBuf {
+  PathBuf::from(String::from_utf8_lossy(name).into_owned())
+}
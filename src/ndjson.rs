@@ -0,0 +1,82 @@
+use std::io;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use lines::NumberedLines;
+
+/// Something that went wrong while reading newline-delimited JSON.
+#[derive(Debug)]
+pub enum NdjsonError<E> {
+  /// Reading the input failed.
+  Io(io::Error),
+  /// A line couldn't be parsed. `lineno` counts across all input, starting
+  /// from 1.
+  Parse { lineno: usize, error: E }
+}
+
+impl<E> Display for NdjsonError<E> where
+  E: Display
+{
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      NdjsonError::Io(ref err) => write!(f, "could not read input: {}", err),
+      NdjsonError::Parse { lineno, ref error } => write!(f, "could not parse line {}: {}", lineno, error)
+    }
+  }
+}
+
+impl<E> Error for NdjsonError<E> where
+  E: Error + 'static
+{
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      NdjsonError::Io(ref err) => Some(err),
+      NdjsonError::Parse { ref error, .. } => Some(error)
+    }
+  }
+}
+
+/// An iterator over the values in newline-delimited JSON input, one per
+/// line, parsed with whatever function it's given.
+///
+/// Lines which are blank, or only whitespace, are skipped. A line which fails
+/// to parse gives back an error, but the iterator carries on with the next
+/// line afterwards.
+pub struct NdjsonValues<F> {
+  lines: NumberedLines,
+  parse: F
+}
+
+impl<F> NdjsonValues<F> {
+  pub(crate) fn new(lines: NumberedLines, parse: F) -> Self {
+    NdjsonValues { lines, parse }
+  }
+
+  /// The line number of the last value returned, counting across all input.
+  pub fn lineno(&self) -> usize {
+    self.lines.lineno()
+  }
+}
+
+impl<F, T, E> Iterator for NdjsonValues<F> where
+  F: FnMut(&str) -> Result<T, E>
+{
+  type Item = Result<T, NdjsonError<E>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let line = match self.lines.next()? {
+        Ok(line) => line,
+        Err(err) => return Some(Err(NdjsonError::Io(err)))
+      };
+
+      if line.trim().is_empty() {
+        continue;
+      }
+
+      let lineno = self.lines.lineno();
+
+      return Some((self.parse)(&line).map_err(|error| NdjsonError::Parse { lineno, error }));
+    }
+  }
+}
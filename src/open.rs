@@ -0,0 +1,266 @@
+//! Opening file arguments, the same way whether that happens up front or
+//! once reading gets to them.
+
+use std::io::{self, Read};
+use std::fs::{self, File};
+#[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::fmt::{self, Debug, Formatter};
+
+#[cfg(feature = "gzip")]
+use gzip::GzDecoder;
+#[cfg(feature = "zstd")]
+use zstd::ZstdDecoder;
+#[cfg(feature = "bzip2")]
+use bzip2::Bzip2Decoder;
+#[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+use sniff::AutoDecoder;
+use retry::Retry;
+#[cfg(feature = "http")]
+use http;
+
+/// Turns a file argument into a reader, in place of opening it as a file.
+pub(crate) type OpenCallback = Arc<dyn Fn(&Path) -> io::Result<Box<dyn Read + Send>> + Send + Sync>;
+
+/// Lets `Opener` stay `Debug`, even though closures aren't.
+#[derive(Clone)]
+pub(crate) struct Custom(pub(crate) OpenCallback);
+
+impl Debug for Custom {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    write!(f, "Custom")
+  }
+}
+
+/// Everything from the builder that changes how files get opened.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Opener {
+  pub(crate) retry: Retry,
+  pub(crate) custom: Option<Custom>,
+  /// Whether `/dev/fd/N` and `fd:N` mean file descriptor `N`.
+  #[cfg(unix)]
+  pub(crate) fd_args: bool,
+  /// Whether to pick a decompressor from the first few bytes of each file,
+  /// instead of from its name.
+  #[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+  pub(crate) auto_decompress: bool
+}
+
+impl Opener {
+  /// Open a file argument as-is. Directories can be opened like files on
+  /// some platforms, and only fail once they're read from, so catch them
+  /// here, before opening anything.
+  pub(crate) fn open_file(&self, path: &Path) -> io::Result<File> {
+    #[cfg(unix)]
+    {
+      if let (Some(fd), true) = (fd_arg(path), self.fd_args) {
+        let file = dup_fd(fd)?;
+
+        if file.metadata()?.is_dir() {
+          return Err(is_a_directory());
+        }
+
+        return Ok(file);
+      }
+    }
+
+    if self.retry.run(|| fs::metadata(path))?.is_dir() {
+      return Err(is_a_directory());
+    }
+
+    self.retry.run(|| File::open(path))
+  }
+
+  /// Like `open_file()`, but anything that isn't a regular file, like a
+  /// named pipe, only gets opened once it's first read from, since opening a
+  /// pipe waits until something opens it for writing.
+  fn open_path(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+    #[cfg(unix)]
+    {
+      if self.fd_args && fd_arg(path).is_some() {
+        return Ok(Box::new(self.open_file(path)?));
+      }
+    }
+
+    let metadata = self.retry.run(|| fs::metadata(path))?;
+
+    if metadata.is_dir() {
+      Err(is_a_directory())
+    } else if metadata.is_file() {
+      Ok(Box::new(self.retry.run(|| File::open(path))?))
+    } else {
+      Ok(Box::new(Deferred { opener: self.clone(), path: path.to_path_buf(), file: None }))
+    }
+  }
+
+  /// Open a file argument for reading, decompressing it if need be.
+  pub(crate) fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+    if let Some(Custom(ref custom)) = self.custom {
+      return self.retry.run(|| custom(path));
+    }
+
+    #[cfg(feature = "http")]
+    let reader: Box<dyn Read + Send> = if http::is_url(path) {
+      self.retry.run(|| http::get(path))?
+    } else {
+      self.open_path(path)?
+    };
+    #[cfg(not(feature = "http"))]
+    let reader: Box<dyn Read + Send> = self.open_path(path)?;
+
+    #[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+    {
+      if self.auto_decompress {
+        return Ok(Box::new(AutoDecoder::new(reader)));
+      }
+    }
+
+    #[cfg(feature = "gzip")]
+    {
+      if path.extension() == Some(OsStr::new("gz")) {
+        return Ok(Box::new(GzDecoder::new(reader)));
+      }
+    }
+
+    #[cfg(feature = "zstd")]
+    {
+      if path.extension() == Some(OsStr::new("zst")) {
+        return Ok(Box::new(ZstdDecoder::new(reader)));
+      }
+    }
+
+    #[cfg(feature = "bzip2")]
+    {
+      if path.extension() == Some(OsStr::new("bz2")) {
+        return Ok(Box::new(Bzip2Decoder::new(reader)));
+      }
+    }
+
+    Ok(reader)
+  }
+
+  /// Whether `open()` gives back the same bytes as `open_file()` would for
+  /// this path, without fetching or decompressing anything.
+  pub(crate) fn opens_as_is(&self, path: &Path) -> bool {
+    if self.custom.is_some() {
+      return false;
+    }
+
+    #[cfg(feature = "http")]
+    {
+      if http::is_url(path) {
+        return false;
+      }
+    }
+
+    #[cfg(any(feature = "gzip", feature = "zstd", feature = "bzip2"))]
+    {
+      if self.auto_decompress {
+        return false;
+      }
+    }
+
+    #[cfg(feature = "gzip")]
+    {
+      if path.extension() == Some(OsStr::new("gz")) {
+        return false;
+      }
+    }
+
+    #[cfg(feature = "zstd")]
+    {
+      if path.extension() == Some(OsStr::new("zst")) {
+        return false;
+      }
+    }
+
+    #[cfg(feature = "bzip2")]
+    {
+      if path.extension() == Some(OsStr::new("bz2")) {
+        return false;
+      }
+    }
+
+    let _ = path;
+    true
+  }
+}
+
+/// A file which only gets opened once it's first read from.
+struct Deferred {
+  opener: Opener,
+  path: PathBuf,
+  file: Option<File>
+}
+
+impl Read for Deferred {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let file = match self.file {
+      Some(ref mut file) => file,
+      None => self.file.insert(self.opener.open_file(&self.path)?)
+    };
+
+    file.read(buf)
+  }
+}
+
+pub(crate) fn is_a_directory() -> io::Error {
+  io::Error::new(io::ErrorKind::IsADirectory, "is a directory")
+}
+
+/// The file descriptor an argument like `/dev/fd/3` or `fd:3` names.
+#[cfg(unix)]
+fn fd_arg(path: &Path) -> Option<::std::os::unix::io::RawFd> {
+  let arg = path.to_str()?;
+  let fd = arg.strip_prefix("/dev/fd/").or_else(|| arg.strip_prefix("fd:"))?;
+
+  // Only plain numbers; `str::parse` would take `+3` too.
+  if fd.is_empty() || !fd.bytes().all(|byte| byte.is_ascii_digit()) {
+    return None;
+  }
+
+  fd.parse().ok()
+}
+
+/// Get a `File` for a descriptor we were handed, without taking it over.
+///
+/// The descriptor gets duplicated, and the `File` only owns the duplicate, so
+/// dropping it (or naming the same descriptor twice) never closes the
+/// original out from under whoever gave it to us.
+#[cfg(unix)]
+fn dup_fd(fd: ::std::os::unix::io::RawFd) -> io::Result<File> {
+  use std::os::unix::io::FromRawFd;
+
+  // `fcntl()` only looks the number up, so a descriptor that isn't open just
+  // fails with `EBADF`, rather than anything pretending to own it.
+  let dup = unsafe { sys::fcntl(fd, sys::F_DUPFD_CLOEXEC, 0) };
+
+  if dup < 0 {
+    return Err(io::Error::last_os_error());
+  }
+
+  // The duplicate is brand new, so nothing else owns it.
+  Ok(unsafe { File::from_raw_fd(dup) })
+}
+
+#[cfg(unix)]
+mod sys {
+  use std::os::raw::c_int;
+
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  pub const F_DUPFD_CLOEXEC: c_int = 1030;
+  #[cfg(any(target_os = "macos", target_os = "ios"))]
+  pub const F_DUPFD_CLOEXEC: c_int = 67;
+  #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+  pub const F_DUPFD_CLOEXEC: c_int = 17;
+  #[cfg(target_os = "netbsd")]
+  pub const F_DUPFD_CLOEXEC: c_int = 12;
+  #[cfg(target_os = "openbsd")]
+  pub const F_DUPFD_CLOEXEC: c_int = 10;
+
+  extern "C" {
+    pub fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+  }
+}
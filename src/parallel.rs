@@ -0,0 +1,61 @@
+//! Running a function over every source at once, on multiple threads.
+
+use std::io::Read;
+use std::sync::Mutex;
+use std::thread;
+
+use reader::{SharedStdin, Source, SourceReader};
+
+/// Call `f` on the reader for each source, giving back the results in the
+/// same order as the sources.
+///
+/// Files get spread out over a handful of worker threads, while `stdin`,
+/// which can't be shared, gets read on the calling thread.
+pub(crate) fn map_sources<F, T>(sources: Vec<Source>, mut stdin: SharedStdin, f: F) -> Vec<T> where
+  F: Fn(&mut dyn Read) -> T + Sync,
+  T: Send
+{
+  let mut files = Vec::new();
+  let mut stdin_positions = Vec::new();
+
+  for (position, source) in sources.into_iter().enumerate() {
+    match source.reader {
+      SourceReader::Open(reader) => files.push((position, reader)),
+      SourceReader::Stdin => stdin_positions.push(position),
+      SourceReader::Pending | SourceReader::Failed { .. } => unreachable!("sources are opened before mapping over them")
+    }
+  }
+
+  let count = files.len() + stdin_positions.len();
+  let workers = thread::available_parallelism().map_or(1, |workers| workers.get()).min(files.len());
+
+  let queue = Mutex::new(files.into_iter());
+  let results = Mutex::new((0..count).map(|_| None).collect::<Vec<Option<T>>>());
+
+  thread::scope(|scope| {
+    for _ in 0..workers {
+      scope.spawn(|| {
+        loop {
+          let next = queue.lock().unwrap().next();
+          let (position, mut reader) = match next {
+            Some(job) => job,
+            None => break
+          };
+
+          let result = f(&mut reader);
+          results.lock().unwrap()[position] = Some(result);
+        }
+      });
+    }
+
+    for position in stdin_positions {
+      let result = f(&mut stdin);
+      results.lock().unwrap()[position] = Some(result);
+    }
+  });
+
+  results.into_inner().unwrap()
+    .into_iter()
+    .map(|result| result.expect("every source gets mapped"))
+    .collect()
+}
@@ -0,0 +1,17 @@
+//! The items most programs need, to bring in all at once:
+//!
+//! ```no_run
+//! use arg_input::prelude::*;
+//!
+//! fn main() -> Result<(), InputReadError> {
+//!   for line in argf_lines()? {
+//!     println!("{}", line?);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+
+pub use {argf, argf_lines, argf_to_string, input, input_lines, input_to_string};
+pub use {ArgfReader, InputBuilder};
+pub use {FailReadAtError, FailReadFileError, InputError, InputErrorKind, InputReadError};
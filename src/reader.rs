@@ -0,0 +1,880 @@
+use std::io::{self, Read, BufRead, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::cell::OnceCell;
+use std::time::SystemTime;
+
+use {FailReadAtError, FailReadFileError};
+use open::Opener;
+use lines::strip_newline;
+
+pub(crate) const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// One of the inputs being chained together: either a named file, or `stdin`.
+pub(crate) struct Source {
+  pub(crate) path: Option<PathBuf>,
+  /// Which of the inputs this source came from.
+  pub(crate) arg_index: usize,
+  pub(crate) reader: SourceReader,
+  /// Bytes already read from `reader` while peeking ahead, which haven't
+  /// been read by anyone yet.
+  lookahead: Vec<u8>,
+  /// Whether reading has gotten to this source yet.
+  started: bool,
+  /// Whether this source's banner has been put at the front of `lookahead`
+  /// yet, which peeking can do before reading gets to it.
+  bannered: bool,
+  /// How many more bytes can be read from `reader`, if there's a limit.
+  remaining: Option<u64>,
+  /// Whether `reader` has given back any bytes yet.
+  produced: bool,
+  /// Whether `reader` has reached its end.
+  ended: bool
+}
+
+impl Source {
+  pub(crate) fn new(path: Option<PathBuf>, arg_index: usize, reader: SourceReader) -> Self {
+    Source { path, arg_index, reader, lookahead: Vec::new(), started: false, bannered: false, remaining: None, produced: false, ended: false }
+  }
+
+  pub(crate) fn is_stdin(&self) -> bool {
+    matches!(self.reader, SourceReader::Stdin)
+  }
+}
+
+pub(crate) enum SourceReader {
+  /// A file that hasn't been opened yet.
+  Pending,
+  Open(Box<dyn Read + Send>),
+  /// Reads from the `ArgfReader`'s `stdin`, which all `stdin` sources share.
+  Stdin,
+  /// An argument which couldn't even be expanded, like a pattern matching
+  /// nothing, failing once reading gets to it, and then reading as empty.
+  Failed { kind: io::ErrorKind, message: String, reported: bool }
+}
+
+/// What's known about one of the inputs before reading it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceInfo {
+  /// The name of the file, or `-` for `stdin`.
+  pub path: PathBuf,
+  /// The size of the file on disk. Compressed files give their compressed
+  /// size. `None` for `stdin`, for anything that isn't a regular file, like
+  /// a named pipe, or if the size can't be found out.
+  pub len: Option<u64>,
+  /// When the file was last modified. `None` for `stdin`, or if the platform
+  /// doesn't keep track.
+  pub modified: Option<SystemTime>,
+  pub is_stdin: bool
+}
+
+/// Called with the name of each source when reading first gets to it.
+pub(crate) type FileOpenCallback = Arc<dyn Fn(&Path) + Send + Sync>;
+
+/// Gives back the banner line to put before each source.
+pub(crate) type BannerCallback = Arc<dyn Fn(&Path) -> String + Send + Sync>;
+
+/// Where everything read gets copied to. Every reader built from the same
+/// builder shares it.
+pub(crate) type TeeWriter = Arc<Mutex<Box<dyn Write + Send>>>;
+
+/// Copies everything read from an `ArgfReader` into a writer, like `tee`.
+pub(crate) struct Tee {
+  writer: TeeWriter,
+  ignore_errors: bool,
+  /// A failure to write, waiting to be given back by the next read: the
+  /// read which caused it has already taken its bytes out of the input, so
+  /// it still hands them over.
+  error: Option<io::Error>
+}
+
+impl Tee {
+  pub(crate) fn new(writer: TeeWriter, ignore_errors: bool) -> Self {
+    Tee { writer, ignore_errors, error: None }
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    // Once writing has failed, there's no point carrying on until the
+    // caller's heard about it.
+    if bytes.is_empty() || self.error.is_some() {
+      return;
+    }
+
+    let result = self.writer.lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+      .write_all(bytes);
+
+    if let Err(err) = result {
+      if !self.ignore_errors {
+        self.error = Some(err);
+      }
+    }
+  }
+}
+
+/// The one `stdin` shared between every `stdin` source.
+///
+/// `stdin` only gets read once: after the first time it runs out, it stays
+/// empty, even if it's a terminal that could give back more.
+pub(crate) struct SharedStdin {
+  reader: Box<dyn Read + Send>,
+  done: bool
+}
+
+impl SharedStdin {
+  pub(crate) fn new(reader: Box<dyn Read + Send>) -> Self {
+    SharedStdin { reader, done: false }
+  }
+}
+
+impl Read for SharedStdin {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.done {
+      return Ok(0);
+    }
+
+    let read = self.reader.read(buf)?;
+    if read == 0 && !buf.is_empty() {
+      self.done = true;
+    }
+
+    Ok(read)
+  }
+}
+
+/// A `Read` instance over all input files/`stdin`, chained together.
+///
+/// Unlike a plain chain of readers, this keeps track of which source is
+/// currently being read from, like Ruby's `ARGF.filename`.
+///
+/// `ArgfReader` is buffered internally, and its buffer never holds bytes
+/// from more than one source at a time.
+///
+/// Since this is a concrete type, rather than a `Box<dyn Read>`, it can be
+/// kept in a struct field by name, and reads don't go through dynamic
+/// dispatch. The line iterators use its buffer directly, instead of wrapping
+/// it in another `BufReader`.
+pub struct ArgfReader {
+  sources: Vec<Source>,
+  stdin: SharedStdin,
+  current: usize,
+  buf: Vec<u8>,
+  pos: usize,
+  cap: usize,
+  /// Whether to strip a byte order mark at the start of the input.
+  pub(crate) strip_bom: bool,
+  /// Whether the start of the input still needs checking for a byte order
+  /// mark.
+  bom_pending: bool,
+  /// Set once the last source has been skipped, so that there's nothing
+  /// left to read.
+  finished: bool,
+  /// Whatever the last call to `peek()` gave back.
+  peeked: Vec<u8>,
+  bytes_read: u64,
+  /// How many newlines have been read, across all sources.
+  lines_read: u64,
+  /// How many bytes have been read from the current source.
+  source_offset: u64,
+  /// How many newlines have been read from the current source.
+  source_lines: u64,
+  pub(crate) on_file_open: Option<FileOpenCallback>,
+  pub(crate) banner: Option<BannerCallback>,
+  pub(crate) opener: Opener,
+  pub(crate) tee: Option<Tee>,
+  /// The most bytes to read from each source.
+  per_source_limit: Option<u64>,
+  /// The total size of the input, once `progress()` has looked it up.
+  progress_total: OnceCell<Option<u64>>
+}
+
+impl ArgfReader {
+  pub(crate) fn with_capacity(capacity: usize, sources: Vec<Source>, stdin: Box<dyn Read + Send>) -> Self {
+    ArgfReader {
+      sources,
+      stdin: SharedStdin::new(stdin),
+      current: 0,
+      buf: vec![0; capacity],
+      pos: 0,
+      cap: 0,
+      strip_bom: false,
+      bom_pending: true,
+      finished: false,
+      peeked: Vec::new(),
+      bytes_read: 0,
+      lines_read: 0,
+      source_offset: 0,
+      source_lines: 0,
+      on_file_open: None,
+      banner: None,
+      opener: Opener::default(),
+      tee: None,
+      per_source_limit: None,
+      progress_total: OnceCell::new()
+    }
+  }
+
+  /// Read at most `limit` bytes from each source, before moving on to the
+  /// next one.
+  pub(crate) fn set_per_source_limit(&mut self, limit: Option<u64>) {
+    self.per_source_limit = limit;
+
+    for source in &mut self.sources {
+      source.remaining = limit;
+    }
+  }
+
+  /// The name of the file currently being read from, or `None` if we're
+  /// reading from `stdin`.
+  ///
+  /// Before any reading happens, this is the first source. Once all input
+  /// has been exhausted, this stays on the last source.
+  pub fn current_filename(&self) -> Option<&Path> {
+    self.sources.get(self.current)
+      .and_then(|source| source.path.as_ref())
+      .map(|path| path.as_path())
+  }
+
+  /// How many bytes have been read so far, across all sources. Bytes that
+  /// have only been peeked at don't count, and neither does a byte order
+  /// mark that got stripped.
+  ///
+  /// This goes back to zero on a `rewind()`.
+  pub fn bytes_read(&self) -> u64 {
+    self.bytes_read
+  }
+
+  /// How far through the input reading has got, from 0 to 1, say for a
+  /// progress bar: [`bytes_read()`](#method.bytes_read) out of the combined
+  /// size of all the files.
+  ///
+  /// That's only known if every source is a regular file, read as it is on
+  /// disk, so this is `None` if any of them is `stdin`, a pipe, or gets
+  /// decompressed. Sizes get looked up the first time this is called, and
+  /// not again after that. Banners count as part of the input, so this can
+  /// reach 1 a little early, but it never goes past 1.
+  pub fn progress(&self) -> Option<f64> {
+    let total = (*self.progress_total.get_or_init(|| {
+      let as_is = self.sources.iter().all(|source| {
+        source.path.as_ref().is_some_and(|path| self.opener.opens_as_is(path))
+      });
+
+      if as_is { self.total_len() } else { None }
+    }))?;
+
+    if total == 0 {
+      return Some(1.0);
+    }
+
+    Some(f64::min(self.bytes_read as f64 / total as f64, 1.0))
+  }
+
+  /// How many bytes have been read so far from the file currently being
+  /// read from, which goes back to zero whenever reading moves on to the
+  /// next source. Together with
+  /// [`current_filename()`](#method.current_filename), this is enough for
+  /// messages like "parse error at byte 1234 of foo.txt".
+  ///
+  /// Just like [`bytes_read()`](#method.bytes_read), this leaves out a
+  /// stripped byte order mark, but counts a banner as part of its file.
+  pub fn current_file_offset(&self) -> u64 {
+    self.source_offset
+  }
+
+  /// Look up the size and modification time of every source.
+  pub(crate) fn source_info(&self) -> Vec<SourceInfo> {
+    self.sources.iter().map(|source| {
+      match source.path {
+        Some(ref path) => {
+          let metadata = fs::metadata(path).ok();
+
+          SourceInfo {
+            path: path.clone(),
+            // Pipes and devices claim to be empty, whatever's in them.
+            len: metadata.as_ref().filter(|metadata| metadata.is_file()).map(|metadata| metadata.len()),
+            modified: metadata.and_then(|metadata| metadata.modified().ok()),
+            is_stdin: false
+          }
+        },
+        None => SourceInfo { path: PathBuf::from("-"), len: None, modified: None, is_stdin: source.is_stdin() }
+      }
+    }).collect()
+  }
+
+  /// How many sources there are to read from, counting `stdin` as one.
+  ///
+  /// Files which were left out because they couldn't be opened aren't
+  /// counted.
+  pub fn source_count(&self) -> usize {
+    self.sources.len()
+  }
+
+  /// Whether any of the sources is `stdin`, either because there were no
+  /// inputs, or because `-` was one of them. This doesn't depend on whether
+  /// anything has actually been read from it yet.
+  pub fn used_stdin(&self) -> bool {
+    self.sources.iter().any(Source::is_stdin)
+  }
+
+  /// Read the next line of input into `line`, without its line terminator,
+  /// like the lines from [`input_lines()`](fn.input_lines.html). Whatever
+  /// was in `line` before gets cleared out first, so the same `String` can
+  /// be used for every line, without allocating a new one each time.
+  ///
+  /// Returns `false` once there's no input left.
+  pub fn read_next_line_into(&mut self, line: &mut String) -> io::Result<bool> {
+    line.clear();
+
+    if self.read_line(line)? == 0 {
+      return Ok(false);
+    }
+
+    strip_newline(line);
+    Ok(true)
+  }
+
+  /// Read the next `size` bytes of input into `chunk`, like the chunks from
+  /// [`input_chunks()`](fn.input_chunks.html), reusing it rather than
+  /// allocating a new one. Whatever was in `chunk` before gets cleared out
+  /// first. It only ends up shorter than `size` at the end of the input.
+  ///
+  /// Returns `false` once there's no input left.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `size` is 0.
+  pub fn read_next_chunk_into(&mut self, chunk: &mut Vec<u8>, size: usize) -> io::Result<bool> {
+    assert!(size != 0, "chunk size must be non-zero");
+
+    chunk.clear();
+    self.by_ref().take(size as u64).read_to_end(chunk)?;
+
+    Ok(!chunk.is_empty())
+  }
+
+  /// The names of the sources which turned out to have nothing in them at
+  /// all, in order, say for warning about files which were truncated by
+  /// accident. `stdin` gets the name `-`.
+  ///
+  /// A source only counts once reading has got to the end of it, so this is
+  /// only complete after all the input has been read. Sources which were
+  /// skipped over, or cut short by a limit, aren't included.
+  pub fn empty_sources(&self) -> Vec<PathBuf> {
+    self.sources.iter()
+      .filter(|source| source.ended && !source.produced)
+      .map(|source| source.path.clone().unwrap_or_else(|| PathBuf::from("-")))
+      .collect()
+  }
+
+  /// Go back to the start of the input, so that it can all be read again.
+  ///
+  /// Every file that's already been opened gets opened again from scratch.
+  /// Since `stdin` can't be rewound, this fails if any of the sources is
+  /// `stdin`, or if a file can't be reopened.
+  pub fn rewind(&mut self) -> io::Result<()> {
+    if self.sources.iter().any(Source::is_stdin) {
+      return Err(io::Error::new(io::ErrorKind::InvalidInput, "stdin can't be rewound"));
+    }
+
+    if self.sources.iter().any(|source| source.path.is_none() && !source.is_stdin()) {
+      return Err(io::Error::new(io::ErrorKind::InvalidInput, "readers given directly can't be rewound"));
+    }
+
+    for source in &mut self.sources {
+      match source.reader {
+        SourceReader::Open(_) => {
+          let path = source.path.clone().unwrap_or_default();
+          let file = self.opener.open(&path).map_err(|err| open_error(source.arg_index, &path, err))?;
+          source.reader = SourceReader::Open(file);
+        },
+        SourceReader::Failed { ref mut reported, .. } => *reported = false,
+        _ => ()
+      }
+      source.lookahead.clear();
+      source.started = false;
+      source.bannered = false;
+      source.remaining = self.per_source_limit;
+      source.produced = false;
+      source.ended = false;
+    }
+
+    self.current = 0;
+    self.pos = 0;
+    self.cap = 0;
+    self.source_offset = 0;
+    self.source_lines = 0;
+    self.bytes_read = 0;
+    self.lines_read = 0;
+    self.bom_pending = true;
+    self.finished = false;
+
+    Ok(())
+  }
+
+  /// Stop reading the current file, and move on to the next one, like Ruby's
+  /// `ARGF.skip`. Anything left unread in the current file is thrown away.
+  ///
+  /// If this is the last file, there's nothing left to read afterwards.
+  pub fn skip_current_file(&mut self) {
+    if !self.next_source() {
+      self.pos = 0;
+      self.cap = 0;
+      self.finished = true;
+    }
+  }
+
+  /// Look at the next `amt` bytes of input, without consuming them. If the
+  /// current file is too short, this looks ahead into the files after it.
+  ///
+  /// Gives back fewer than `amt` bytes only if the input runs out first.
+  pub fn peek(&mut self, amt: usize) -> io::Result<&[u8]> {
+    let mut peeked = match self.peek_source(amt) {
+      Ok(peeked) => peeked.to_vec(),
+      Err(err) => return Err(self.read_error(err))
+    };
+    peeked.truncate(amt);
+
+    if !self.finished {
+      let multiple = self.sources.len() > 1;
+
+      for source in self.sources.iter_mut().skip(self.current + 1) {
+        if peeked.len() >= amt {
+          break;
+        }
+
+        // Whatever reading would give back, banner included.
+        if let Some(ref banner) = self.banner {
+          if multiple && !source.bannered {
+            source.bannered = true;
+            let name = source.path.as_deref().unwrap_or_else(|| Path::new("-"));
+            source.lookahead.splice(0..0, banner_line(banner, name));
+          }
+        }
+
+        let needed = amt - peeked.len();
+        let mut chunk = vec![0; needed];
+
+        while source.lookahead.len() < needed {
+          let read = read_source_raw(source, &mut self.stdin, &self.opener, &mut chunk)?;
+
+          if read == 0 {
+            break;
+          }
+
+          source.lookahead.extend_from_slice(&chunk[..read]);
+        }
+
+        let available = usize::min(needed, source.lookahead.len());
+        peeked.extend_from_slice(&source.lookahead[..available]);
+      }
+    }
+
+    self.peeked = peeked;
+    Ok(&self.peeked)
+  }
+
+  /// The combined size of all the sources, if they're all regular files.
+  pub(crate) fn total_len(&self) -> Option<u64> {
+    self.sources.iter().map(|source| {
+      let metadata = source.path.as_ref().and_then(|path| fs::metadata(path).ok())?;
+
+      if metadata.is_file() { Some(metadata.len()) } else { None }
+    }).sum()
+  }
+
+  /// Look at up to `amt` bytes of the current source, without consuming
+  /// them, or looking ahead into the sources after it.
+  pub(crate) fn peek_source(&mut self, amt: usize) -> io::Result<&[u8]> {
+    // Make sure any byte order mark has been dealt with first.
+    self.fill_source_buf()?;
+    self.fill_source_buf_to(amt)
+  }
+
+  /// The position of the current source among all sources.
+  pub(crate) fn source_index(&self) -> usize {
+    self.current
+  }
+
+  /// Like `fill_source_buf()`, but with errors wrapped up like reading gives
+  /// them back.
+  pub(crate) fn fill_current_source(&mut self) -> io::Result<&[u8]> {
+    if let Some(err) = self.tee_error() {
+      return Err(err);
+    }
+
+    if let Err(err) = self.fill_source_buf() {
+      return Err(self.read_error(err));
+    }
+
+    Ok(&self.buf[self.pos..self.cap])
+  }
+
+  /// Take a failure to write to the tee, if there's one that hasn't been
+  /// given back yet.
+  fn tee_error(&mut self) -> Option<io::Error> {
+    self.tee.as_mut().and_then(|tee| tee.error.take())
+  }
+
+  /// Wrap up a failure partway through reading the current source, keeping
+  /// track of where reading had got to. Failures to open a file are already
+  /// wrapped up, so they get left alone.
+  fn read_error(&self, err: io::Error) -> io::Error {
+    let wrapped = err.get_ref().is_some_and(|inner| inner.is::<FailReadFileError>() || inner.is::<FailReadAtError>());
+
+    if wrapped || err.kind() == io::ErrorKind::Interrupted {
+      return err;
+    }
+
+    let source = match self.sources.get(self.current) {
+      Some(source) => source,
+      None => return err
+    };
+    let path = source.path.as_deref().unwrap_or_else(|| Path::new("-"));
+    let kind = err.kind();
+
+    let at = FailReadAtError::new(source.arg_index, path, self.lines_read + 1, self.source_lines + 1, self.bytes_read, self.source_offset, err);
+
+    io::Error::new(kind, at)
+  }
+
+  /// Get a `BufRead` over just the source currently being read from, which
+  /// reports EOF at the end of that source instead of moving on to the next.
+  pub(crate) fn current_source(&mut self) -> CurrentSource<'_> {
+    CurrentSource { reader: self }
+  }
+
+  /// Move on to the next source, if there is one. Anything left unread in
+  /// the current source is left behind.
+  pub(crate) fn next_source(&mut self) -> bool {
+    if self.current + 1 < self.sources.len() {
+      self.current += 1;
+      self.pos = 0;
+      self.cap = 0;
+      self.source_offset = 0;
+      self.source_lines = 0;
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Let whoever's interested know if reading is getting to the current
+  /// source for the first time, and put its banner in front of it.
+  fn start_source(&mut self) -> io::Result<()> {
+    let multiple = self.sources.len() > 1;
+
+    if let Some(source) = self.sources.get_mut(self.current) {
+      if !source.started {
+        source.started = true;
+
+        let name = source.path.as_deref().unwrap_or_else(|| Path::new("-"));
+
+        if let Some(ref on_file_open) = self.on_file_open {
+          on_file_open(name);
+        }
+
+        if let Some(ref banner) = self.banner {
+          if multiple && !source.bannered {
+            source.bannered = true;
+            let line = banner_line(banner, name);
+
+            // The byte order mark would come after the banner otherwise,
+            // so deal with it now.
+            if self.strip_bom && self.bom_pending {
+              self.bom_pending = false;
+
+              while source.lookahead.len() < UTF8_BOM.len() {
+                let mut chunk = [0; 3];
+                let read = read_source_raw(source, &mut self.stdin, &self.opener, &mut chunk[..UTF8_BOM.len() - source.lookahead.len()])?;
+
+                if read == 0 {
+                  break;
+                }
+
+                source.lookahead.extend_from_slice(&chunk[..read]);
+              }
+
+              if source.lookahead.starts_with(UTF8_BOM) {
+                source.lookahead.drain(..UTF8_BOM.len());
+              }
+            }
+
+            source.lookahead.splice(0..0, line);
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn fill_source_buf(&mut self) -> io::Result<&[u8]> {
+    if self.finished {
+      return Ok(&[]);
+    }
+
+    if self.strip_bom && self.bom_pending {
+      // With a banner, starting the source deals with the byte order mark.
+      self.start_source()?;
+    }
+
+    if self.strip_bom && self.bom_pending {
+      self.bom_pending = false;
+
+      if self.fill_source_buf_to(UTF8_BOM.len())?.starts_with(UTF8_BOM) {
+        self.pos += UTF8_BOM.len();
+      }
+    }
+
+    if self.pos >= self.cap {
+      self.start_source()?;
+
+      let source = match self.sources.get_mut(self.current) {
+        Some(source) => source,
+        None => return Ok(&[])
+      };
+      let read = read_source(source, &mut self.stdin, &self.opener, &mut self.buf)?;
+
+      self.pos = 0;
+      self.cap = read;
+    }
+
+    Ok(&self.buf[self.pos..self.cap])
+  }
+
+  /// Make sure that at least `amt` bytes of the current source are buffered,
+  /// unless the source runs out first. The buffer grows if it's too small.
+  fn fill_source_buf_to(&mut self, amt: usize) -> io::Result<&[u8]> {
+    if self.cap - self.pos < amt {
+      self.buf.copy_within(self.pos..self.cap, 0);
+      self.cap -= self.pos;
+      self.pos = 0;
+
+      if self.buf.len() < amt {
+        self.buf.resize(amt, 0);
+      }
+
+      self.start_source()?;
+
+      if let Some(source) = self.sources.get_mut(self.current) {
+        while self.cap < amt {
+          let read = read_source(source, &mut self.stdin, &self.opener, &mut self.buf[self.cap..])?;
+
+          if read == 0 {
+            break;
+          }
+
+          self.cap += read;
+        }
+      }
+    }
+
+    Ok(&self.buf[self.pos..self.cap])
+  }
+}
+
+/// The line a banner puts in front of a source.
+fn banner_line(banner: &BannerCallback, name: &Path) -> Vec<u8> {
+  let mut line = banner(name).into_bytes();
+  line.push(b'\n');
+  line
+}
+
+fn read_source(source: &mut Source, stdin: &mut SharedStdin, opener: &Opener, buf: &mut [u8]) -> io::Result<usize> {
+  if !source.lookahead.is_empty() {
+    let amt = usize::min(buf.len(), source.lookahead.len());
+    buf[..amt].copy_from_slice(&source.lookahead[..amt]);
+    source.lookahead.drain(..amt);
+
+    return Ok(amt);
+  }
+
+  read_source_raw(source, stdin, opener, buf)
+}
+
+/// Read straight from the source, ignoring anything that's been peeked at.
+fn read_source_raw(source: &mut Source, stdin: &mut SharedStdin, opener: &Opener, buf: &mut [u8]) -> io::Result<usize> {
+  if let SourceReader::Failed { kind, ref message, ref mut reported } = source.reader {
+    if *reported {
+      return Ok(0);
+    }
+    *reported = true;
+
+    let path = source.path.clone().unwrap_or_default();
+    return Err(open_error(source.arg_index, &path, io::Error::new(kind, message.clone())));
+  }
+
+  if let SourceReader::Pending = source.reader {
+    let path = source.path.clone().unwrap_or_default();
+
+    match opener.open(&path) {
+      Ok(file) => source.reader = SourceReader::Open(file),
+      Err(err) => {
+        // Don't try to open this file again; whoever's reading can carry
+        // on with the rest of the input if they want to.
+        source.reader = SourceReader::Open(Box::new(io::empty()));
+
+        return Err(open_error(source.arg_index, &path, err));
+      }
+    }
+  }
+
+  let buf = match source.remaining {
+    Some(remaining) if remaining < buf.len() as u64 => &mut buf[..remaining as usize],
+    _ => buf
+  };
+
+  if buf.is_empty() {
+    return Ok(0);
+  }
+
+  let read = match source.reader {
+    SourceReader::Open(ref mut reader) => reader.read(buf)?,
+    SourceReader::Stdin => stdin.read(buf)?,
+    SourceReader::Pending | SourceReader::Failed { .. } => 0
+  };
+
+  if let Some(ref mut remaining) = source.remaining {
+    *remaining -= read as u64;
+  }
+
+  if read > 0 {
+    source.produced = true;
+  } else {
+    source.ended = true;
+  }
+
+  Ok(read)
+}
+
+fn count_newlines(bytes: &[u8]) -> u64 {
+  bytes.iter().filter(|&&byte| byte == b'\n').count() as u64
+}
+
+/// Wrap up a failure to open a file as an `io::Error`, keeping track of
+/// which file it was.
+fn open_error(arg_index: usize, path: &Path, err: io::Error) -> io::Error {
+  let kind = err.kind();
+
+  io::Error::new(kind, FailReadFileError::new(arg_index, path, err))
+}
+
+impl Read for ArgfReader {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if let Some(err) = self.tee_error() {
+      return Err(err);
+    }
+
+    self.read_unwrapped(buf).map_err(|err| self.read_error(err))
+  }
+}
+
+impl ArgfReader {
+  fn read_unwrapped(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if buf.is_empty() || self.finished {
+      return Ok(0);
+    }
+
+    // Skip our own buffer entirely for large reads, like `BufReader` does.
+    if self.pos >= self.cap && buf.len() >= self.buf.len() && !(self.strip_bom && self.bom_pending) {
+      loop {
+        self.start_source()?;
+
+        let read = match self.sources.get_mut(self.current) {
+          Some(source) => read_source(source, &mut self.stdin, &self.opener, buf)?,
+          None => 0
+        };
+
+        if read > 0 || !self.next_source() {
+          self.bytes_read += read as u64;
+          let lines = count_newlines(&buf[..read]);
+          self.lines_read += lines;
+          self.source_lines += lines;
+          self.source_offset += read as u64;
+          if let Some(ref mut tee) = self.tee {
+            tee.write(&buf[..read]);
+          }
+          return Ok(read);
+        }
+      }
+    }
+
+    self.fill_buf_unwrapped()?;
+
+    let read = {
+      let mut available = &self.buf[self.pos..self.cap];
+      available.read(buf)?
+    };
+    self.consume(read);
+
+    Ok(read)
+  }
+
+  fn fill_buf_unwrapped(&mut self) -> io::Result<()> {
+    while self.fill_source_buf()?.is_empty() {
+      if !self.next_source() {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl BufRead for ArgfReader {
+  fn fill_buf(&mut self) -> io::Result<&[u8]> {
+    if let Some(err) = self.tee_error() {
+      return Err(err);
+    }
+
+    if let Err(err) = self.fill_buf_unwrapped() {
+      return Err(self.read_error(err));
+    }
+
+    Ok(&self.buf[self.pos..self.cap])
+  }
+
+  fn consume(&mut self, amt: usize) {
+    let amt = usize::min(amt, self.cap - self.pos);
+
+    let lines = count_newlines(&self.buf[self.pos..self.pos + amt]);
+    self.lines_read += lines;
+    self.source_lines += lines;
+    if let Some(ref mut tee) = self.tee {
+      tee.write(&self.buf[self.pos..self.pos + amt]);
+    }
+    self.pos += amt;
+    self.bytes_read += amt as u64;
+    self.source_offset += amt as u64;
+  }
+}
+
+/// A `BufRead` over only the current source of an `ArgfReader`.
+pub(crate) struct CurrentSource<'a> {
+  reader: &'a mut ArgfReader
+}
+
+impl<'a> Read for CurrentSource<'a> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let read = {
+      let mut available = self.fill_buf()?;
+      available.read(buf)?
+    };
+    self.consume(read);
+
+    Ok(read)
+  }
+}
+
+impl<'a> BufRead for CurrentSource<'a> {
+  fn fill_buf(&mut self) -> io::Result<&[u8]> {
+    self.reader.fill_current_source()
+  }
+
+  fn consume(&mut self, amt: usize) {
+    self.reader.consume(amt)
+  }
+}
@@ -0,0 +1,52 @@
+//! Trying to open files again, for filesystems which sometimes fail to open
+//! a file for no good reason.
+
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+/// The kinds of error which
+/// [`InputBuilder::retry()`](struct.InputBuilder.html#method.retry) tries
+/// again on, unless told otherwise.
+pub const TRANSIENT_ERRORS: &[io::ErrorKind] = &[
+  io::ErrorKind::Interrupted,
+  io::ErrorKind::WouldBlock,
+  io::ErrorKind::TimedOut
+];
+
+/// How many times to try opening a file again, and how long to wait first.
+#[derive(Debug, Clone)]
+pub(crate) struct Retry {
+  pub(crate) attempts: u32,
+  pub(crate) delay: Duration,
+  pub(crate) kinds: Vec<io::ErrorKind>
+}
+
+impl Default for Retry {
+  fn default() -> Self {
+    Retry { attempts: 0, delay: Duration::from_secs(0), kinds: TRANSIENT_ERRORS.to_vec() }
+  }
+}
+
+impl Retry {
+  /// Keep calling `open` until it works, it fails with an error that isn't
+  /// worth trying again on, or we run out of attempts. The wait doubles
+  /// after every attempt.
+  pub(crate) fn run<F, T>(&self, mut open: F) -> io::Result<T> where
+    F: FnMut() -> io::Result<T>
+  {
+    let mut delay = self.delay;
+    let mut attempt = 0;
+
+    loop {
+      match open() {
+        Err(ref err) if attempt < self.attempts && self.kinds.contains(&err.kind()) => {
+          thread::sleep(delay);
+          delay = delay.saturating_mul(2);
+          attempt += 1;
+        },
+        result => return result
+      }
+    }
+  }
+}
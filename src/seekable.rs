@@ -0,0 +1,127 @@
+use std::io::{self, Read, Seek, SeekFrom};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+pub(crate) struct SeekableFile {
+  pub(crate) path: PathBuf,
+  pub(crate) file: File,
+  pub(crate) len: u64
+}
+
+/// A reader over the concatenation of a set of files, which can also seek
+/// anywhere within that concatenation.
+///
+/// Only regular files can be read this way; `stdin` can't be seeked. The
+/// length of each file is checked when it's opened, so if a file changes size
+/// while it's being read, offsets past that file will be off.
+pub struct SeekableArgf {
+  files: Vec<SeekableFile>,
+  /// The logical offset in the stream that each file starts at.
+  starts: Vec<u64>,
+  len: u64,
+  current: usize,
+  pos: u64
+}
+
+impl SeekableArgf {
+  pub(crate) fn new(files: Vec<SeekableFile>) -> Self {
+    let mut starts = Vec::with_capacity(files.len());
+    let mut len = 0;
+
+    for file in &files {
+      starts.push(len);
+      len += file.len;
+    }
+
+    SeekableArgf { files, starts, len, current: 0, pos: 0 }
+  }
+
+  /// The total length of all files put together.
+  pub fn len(&self) -> u64 {
+    self.len
+  }
+
+  /// Whether all the files put together are empty.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// The name of the file that the current position is in.
+  pub fn current_filename(&self) -> Option<&Path> {
+    self.files.get(self.current).map(|file| file.path.as_path())
+  }
+
+  fn current_end(&self) -> u64 {
+    self.starts[self.current] + self.files[self.current].len
+  }
+}
+
+impl Read for SeekableArgf {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.files.is_empty() || buf.is_empty() {
+      return Ok(0);
+    }
+
+    loop {
+      let remaining = self.current_end().saturating_sub(self.pos);
+
+      if remaining > 0 {
+        let amt = u64::min(buf.len() as u64, remaining) as usize;
+        let read = self.files[self.current].file.read(&mut buf[..amt])?;
+
+        // A file that's shorter than it was when opened just ends early.
+        if read > 0 {
+          self.pos += read as u64;
+          return Ok(read);
+        }
+      }
+
+      if self.current + 1 >= self.files.len() {
+        return Ok(0);
+      }
+
+      self.current += 1;
+      self.pos = self.starts[self.current];
+      self.files[self.current].file.seek(SeekFrom::Start(0))?;
+    }
+  }
+}
+
+impl Seek for SeekableArgf {
+  fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+    let target = match pos {
+      SeekFrom::Start(offset) => Some(offset),
+      SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+      SeekFrom::Current(offset) => self.pos.checked_add_signed(offset)
+    };
+
+    let target = match target {
+      Some(target) => target,
+      None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position"))
+    };
+
+    if self.files.is_empty() {
+      self.pos = target;
+      return Ok(target);
+    }
+
+    // The last file starting at or before the target. Skipping over empty
+    // files makes sure reading picks up in the file that actually has the
+    // byte at `target`.
+    let index = match self.starts.binary_search(&target) {
+      Ok(mut index) => {
+        while index + 1 < self.files.len() && self.starts[index + 1] == target {
+          index += 1;
+        }
+        index
+      },
+      Err(index) => index - 1
+    };
+
+    self.files[index].file.seek(SeekFrom::Start(target - self.starts[index]))?;
+    self.current = index;
+    self.pos = target;
+
+    Ok(target)
+  }
+}
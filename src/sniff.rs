@@ -0,0 +1,110 @@
+//! Picking a decompressor by looking at the first few bytes of the data,
+//! rather than at the file name.
+
+use std::io::{self, Cursor, Read};
+use std::mem;
+
+#[cfg(feature = "gzip")]
+use gzip::GzDecoder;
+#[cfg(feature = "zstd")]
+use zstd::ZstdDecoder;
+#[cfg(feature = "bzip2")]
+use bzip2::Bzip2Decoder;
+
+/// Enough bytes to tell every supported format apart.
+const MAGIC_LEN: usize = 4;
+
+#[cfg(feature = "gzip")]
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+#[cfg(feature = "zstd")]
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+
+/// Decompresses whatever `R` turns out to hold, going by its magic bytes, or
+/// passes it through untouched if it isn't in a format we can decompress.
+///
+/// Nothing gets read until the first call to `read()`, so wrapping `stdin`
+/// in this doesn't block.
+pub(crate) struct AutoDecoder {
+  /// What's being sniffed, until the decoder takes it over.
+  inner: Option<Box<dyn Read + Send>>,
+  /// The bytes read so far while sniffing.
+  prefix: Vec<u8>,
+  decoder: Option<Box<dyn Read + Send>>
+}
+
+impl AutoDecoder {
+  pub(crate) fn new(inner: Box<dyn Read + Send>) -> Self {
+    AutoDecoder {
+      inner: Some(inner),
+      prefix: Vec::with_capacity(MAGIC_LEN),
+      decoder: None
+    }
+  }
+
+  /// Read in the magic bytes, if they aren't already, and work out how to
+  /// decode the rest. Anything read before an error is kept for next time.
+  fn sniff(&mut self) -> io::Result<&mut Box<dyn Read + Send>> {
+    if self.decoder.is_none() {
+      let mut inner = self.inner.take().expect("no decoder, so nothing has taken the reader");
+      let mut buf = [0; MAGIC_LEN];
+
+      while self.prefix.len() < MAGIC_LEN {
+        match inner.read(&mut buf[..MAGIC_LEN - self.prefix.len()]) {
+          Ok(0) => break,
+          Ok(read) => self.prefix.extend_from_slice(&buf[..read]),
+          Err(err) => {
+            self.inner = Some(inner);
+            return Err(err);
+          }
+        }
+      }
+
+      self.decoder = Some(decoder(mem::take(&mut self.prefix), inner));
+    }
+
+    Ok(self.decoder.as_mut().unwrap())
+  }
+}
+
+/// Wrap up `inner`, whose first few bytes have already been read into
+/// `magic`, in whichever decoder fits.
+fn decoder(magic: Vec<u8>, inner: Box<dyn Read + Send>) -> Box<dyn Read + Send> {
+  #[cfg(feature = "gzip")]
+  let gzip = magic.starts_with(GZIP_MAGIC);
+  #[cfg(feature = "zstd")]
+  let zstd = magic.starts_with(ZSTD_MAGIC);
+  // `BZh`, then the block size, from 1 to 9.
+  #[cfg(feature = "bzip2")]
+  let bzip2 = magic.len() == 4 && magic.starts_with(b"BZh") && (b'1'..=b'9').contains(&magic[3]);
+
+  let reader: Box<dyn Read + Send> = Box::new(Cursor::new(magic).chain(inner));
+
+  #[cfg(feature = "gzip")]
+  {
+    if gzip {
+      return Box::new(GzDecoder::new(reader));
+    }
+  }
+
+  #[cfg(feature = "zstd")]
+  {
+    if zstd {
+      return Box::new(ZstdDecoder::new(reader));
+    }
+  }
+
+  #[cfg(feature = "bzip2")]
+  {
+    if bzip2 {
+      return Box::new(Bzip2Decoder::new(reader));
+    }
+  }
+
+  reader
+}
+
+impl Read for AutoDecoder {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    self.sniff()?.read(buf)
+  }
+}
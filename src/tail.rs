@@ -0,0 +1,76 @@
+//! Finding the last few lines of input.
+
+use std::collections::VecDeque;
+use std::mem;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+
+use lines::strip_newline;
+use seekable::SeekableArgf;
+
+const BLOCK_SIZE: u64 = 8 * 1024;
+
+/// Keep the last `n` lines of `reader`, reading all of it from the start.
+/// Only the lines which get kept have to be valid UTF-8.
+pub(crate) fn tail_lines<R>(mut reader: R, n: usize) -> io::Result<Vec<String>> where
+  R: BufRead
+{
+  if n == 0 {
+    return Ok(Vec::new());
+  }
+
+  let mut kept = VecDeque::new();
+
+  let mut line = Vec::new();
+
+  while reader.read_until(b'\n', &mut line)? > 0 {
+    // Reuse the oldest line's buffer once there are enough lines.
+    let spare = if kept.len() == n { kept.pop_front().unwrap_or_default() } else { Vec::new() };
+
+    kept.push_back(mem::replace(&mut line, spare));
+    line.clear();
+  }
+
+  kept.into_iter().map(|line| {
+    let mut line = String::from_utf8(line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    strip_newline(&mut line);
+    Ok(line)
+  }).collect()
+}
+
+/// Keep the last `n` lines of `reader`, looking backwards from the end for
+/// where they start, so that everything before them never gets read.
+pub(crate) fn tail_seekable(reader: &mut SeekableArgf, n: usize) -> io::Result<Vec<String>> {
+  let len = reader.len();
+  let mut block = vec![0; BLOCK_SIZE as usize];
+  let mut end = len;
+  let mut start = 0;
+  let mut newlines = 0;
+
+  'scan: while end > 0 && n > 0 {
+    let begin = end.saturating_sub(BLOCK_SIZE);
+    let block = &mut block[..(end - begin) as usize];
+
+    reader.seek(SeekFrom::Start(begin))?;
+    reader.read_exact(block)?;
+
+    for (index, &byte) in block.iter().enumerate().rev() {
+      let offset = begin + index as u64;
+
+      // A newline at the very end finishes the last line, rather than
+      // starting another one.
+      if byte == b'\n' && offset + 1 != len {
+        newlines += 1;
+
+        if newlines == n {
+          start = offset + 1;
+          break 'scan;
+        }
+      }
+    }
+
+    end = begin;
+  }
+
+  reader.seek(SeekFrom::Start(start))?;
+  tail_lines(BufReader::new(reader), n)
+}
@@ -0,0 +1,81 @@
+//! Finding all the files inside directory arguments, like `grep -r`.
+
+use std::io;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Every regular file under the given directory, depth-first, with the
+/// entries of each directory in sorted order, along with whatever couldn't
+/// be looked at on the way, and why. Something which fails doesn't stop the
+/// rest of the directory from being walked.
+///
+/// Symbolic links inside the directory are skipped, unless
+/// `follow_symlinks` is set. Directories which have already been walked on
+/// the way down are skipped too, so that following links can't go around in
+/// circles.
+pub(crate) fn walk(dir: &Path, follow_symlinks: bool) -> (Vec<PathBuf>, Vec<(PathBuf, io::Error)>) {
+  let mut walked = Walked { files: Vec::new(), failures: Vec::new() };
+  let mut ancestors = Vec::new();
+
+  walk_into(dir, follow_symlinks, &mut ancestors, &mut walked);
+
+  (walked.files, walked.failures)
+}
+
+struct Walked {
+  files: Vec<PathBuf>,
+  failures: Vec<(PathBuf, io::Error)>
+}
+
+fn walk_into(dir: &Path, follow_symlinks: bool, ancestors: &mut Vec<PathBuf>, walked: &mut Walked) {
+  let listed = fs::canonicalize(dir).and_then(|canonical| {
+    let entries = fs::read_dir(dir)?
+      .map(|entry| entry.map(|entry| entry.path()))
+      .collect::<io::Result<Vec<_>>>()?;
+
+    Ok((canonical, entries))
+  });
+
+  let (canonical, mut entries) = match listed {
+    Ok(listed) => listed,
+    Err(err) => return walked.failures.push((dir.to_path_buf(), err))
+  };
+
+  if ancestors.contains(&canonical) {
+    return;
+  }
+
+  entries.sort();
+
+  ancestors.push(canonical);
+
+  for path in entries {
+    let link_metadata = match fs::symlink_metadata(&path) {
+      Ok(metadata) => metadata,
+      Err(err) => {
+        walked.failures.push((path, err));
+        continue;
+      }
+    };
+
+    let metadata = if !link_metadata.file_type().is_symlink() {
+      link_metadata
+    } else if follow_symlinks {
+      // A dangling link has nothing to read.
+      match fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(_) => continue
+      }
+    } else {
+      continue;
+    };
+
+    if metadata.is_dir() {
+      walk_into(&path, follow_symlinks, ancestors, walked);
+    } else if metadata.is_file() {
+      walked.files.push(path);
+    }
+  }
+
+  ancestors.pop();
+}
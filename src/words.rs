@@ -0,0 +1,71 @@
+use std::io::{self, BufRead};
+
+use reader::ArgfReader;
+
+/// An iterator over all whitespace-separated words of input.
+///
+/// Input is read a buffer at a time, not all at once. Like everything else,
+/// words can span files: if one file doesn't end in whitespace, and the next
+/// doesn't start with it, the end of the first and the start of the second
+/// make one word. Only ASCII whitespace separates words.
+pub struct Words {
+  reader: ArgfReader
+}
+
+impl Words {
+  pub(crate) fn new(reader: ArgfReader) -> Self {
+    Words { reader }
+  }
+
+  fn read_word(&mut self) -> io::Result<Option<String>> {
+    let mut word = Vec::new();
+
+    loop {
+      let (used, done) = {
+        let available = self.reader.fill_buf()?;
+
+        if available.is_empty() {
+          break;
+        }
+
+        let mut used = 0;
+        let mut done = false;
+
+        for &byte in available {
+          used += 1;
+
+          if !byte.is_ascii_whitespace() {
+            word.push(byte);
+          } else if !word.is_empty() {
+            done = true;
+            break;
+          }
+        }
+
+        (used, done)
+      };
+
+      self.reader.consume(used);
+
+      if done {
+        break;
+      }
+    }
+
+    if word.is_empty() {
+      return Ok(None);
+    }
+
+    String::from_utf8(word)
+      .map(Some)
+      .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+  }
+}
+
+impl Iterator for Words {
+  type Item = io::Result<String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.read_word().transpose()
+  }
+}
@@ -0,0 +1,983 @@
+//! A small, dependency-free zstd decoder, used to transparently decompress
+//! `.zst` inputs. Only decompression is supported, and frames which need a
+//! dictionary are refused.
+//!
+//! See RFC 8878 for the format.
+
+use std::io::{self, Read, BufRead, BufReader};
+
+const MAGIC: u32 = 0xFD2F_B528;
+
+/// Skippable frames can have any magic number from this one up to
+/// `0x184D2A5F`.
+const SKIPPABLE_MAGIC: u32 = 0x184D_2A50;
+
+const MAX_BLOCK_SIZE: usize = 128 * 1024;
+
+const LITERAL_LENGTH_BASE: [u32; 36] = [
+  0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+  16, 18, 20, 22, 24, 28, 32, 40, 48, 64, 128, 256, 512, 1024, 2048, 4096,
+  8192, 16384, 32768, 65536
+];
+const LITERAL_LENGTH_EXTRA: [u8; 36] = [
+  0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+  1, 1, 1, 1, 2, 2, 3, 3, 4, 6, 7, 8, 9, 10, 11, 12,
+  13, 14, 15, 16
+];
+const MATCH_LENGTH_BASE: [u32; 53] = [
+  3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+  19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34,
+  35, 37, 39, 41, 43, 47, 51, 59, 67, 83, 99, 131, 259, 515, 1027, 2051,
+  4099, 8195, 16387, 32771, 65539
+];
+const MATCH_LENGTH_EXTRA: [u8; 53] = [
+  0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+  0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+  1, 1, 1, 1, 2, 2, 3, 3, 4, 4, 5, 7, 8, 9, 10, 11,
+  12, 13, 14, 15, 16
+];
+
+/// The tables used for sequences when a block says to use the predefined
+/// ones.
+const LITERAL_LENGTH_DEFAULT: [i16; 36] = [
+  4, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1,
+  2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 2, 1, 1, 1, 1, 1,
+  -1, -1, -1, -1
+];
+const MATCH_LENGTH_DEFAULT: [i16; 53] = [
+  1, 4, 3, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1,
+  1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+  1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, -1, -1,
+  -1, -1, -1, -1, -1
+];
+const OFFSET_DEFAULT: [i16; 29] = [
+  1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1,
+  1, 1, 1, 1, 1, 1, 1, 1, -1, -1, -1, -1, -1
+];
+
+fn invalid(msg: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, format!("invalid zstd data: {}", msg))
+}
+
+/// Reads bits from the start of a slice, lowest bit first. Running off the
+/// end reads zeros; check `overran()` once done.
+struct ForwardBits<'a> {
+  data: &'a [u8],
+  pos: usize
+}
+
+impl<'a> ForwardBits<'a> {
+  fn new(data: &'a [u8]) -> Self {
+    ForwardBits { data, pos: 0 }
+  }
+
+  fn peek(&self, count: u32) -> u32 {
+    let mut value = 0;
+
+    for bit in 0..count as usize {
+      let pos = self.pos + bit;
+      let byte = self.data.get(pos / 8).cloned().unwrap_or(0);
+      value |= u32::from((byte >> (pos % 8)) & 1) << bit;
+    }
+
+    value
+  }
+
+  fn bits(&mut self, count: u32) -> u32 {
+    let value = self.peek(count);
+    self.pos += count as usize;
+    value
+  }
+
+  fn overran(&self) -> bool {
+    self.pos > self.data.len() * 8
+  }
+
+  /// How many bytes have been read from, including a partial last one.
+  fn bytes_used(&self) -> usize {
+    self.pos.div_ceil(8)
+  }
+}
+
+/// Reads bits from the end of a slice back towards its start, the way
+/// entropy-coded streams are laid out. The highest set bit of the last byte
+/// only marks where the stream starts. Running off the start reads zeros.
+struct BackwardBits<'a> {
+  data: &'a [u8],
+  /// How many bits are left to read.
+  pos: isize
+}
+
+impl<'a> BackwardBits<'a> {
+  fn new(data: &'a [u8]) -> io::Result<Self> {
+    match data.last() {
+      Some(&last) if last != 0 => {
+        let padding = last.leading_zeros() as isize + 1;
+        Ok(BackwardBits { data, pos: data.len() as isize * 8 - padding })
+      },
+      _ => Err(invalid("bitstream is missing its end marker"))
+    }
+  }
+
+  fn peek(&self, count: u32) -> u64 {
+    let end = self.pos;
+    let start = end - count as isize;
+
+    if count == 0 || end <= 0 {
+      return 0;
+    }
+
+    let low = isize::max(start, 0) as usize;
+    let end = end as usize;
+
+    let mut word = 0u64;
+    for (index, &byte) in self.data[low / 8..=(end - 1) / 8].iter().enumerate() {
+      word |= u64::from(byte) << (8 * index);
+    }
+
+    let value = (word >> (low % 8)) & ((1 << (end - low)) - 1);
+    value << (low as isize - start)
+  }
+
+  fn bits(&mut self, count: u32) -> u64 {
+    let value = self.peek(count);
+    self.pos -= count as isize;
+    value
+  }
+
+  fn overran(&self) -> bool {
+    self.pos < 0
+  }
+
+  fn finished(&self) -> bool {
+    self.pos == 0
+  }
+}
+
+#[derive(Clone, Copy, Default)]
+struct FseEntry {
+  symbol: u8,
+  bits: u8,
+  base: u16
+}
+
+/// A finite state entropy decoding table.
+#[derive(Clone)]
+struct Fse {
+  log: u32,
+  entries: Vec<FseEntry>
+}
+
+impl Fse {
+  /// Build the table for a distribution, where each symbol's probability is
+  /// out of `1 << log`, and `-1` means "less than 1".
+  fn new(log: u32, probabilities: &[i16]) -> io::Result<Self> {
+    let size = 1 << log;
+    let mut entries = vec![FseEntry::default(); size];
+    let mut next = vec![0u32; probabilities.len()];
+
+    // "Less than 1" symbols each get one state, right at the end.
+    let mut high = size;
+    for (symbol, &probability) in probabilities.iter().enumerate() {
+      if probability == -1 {
+        if high == 0 {
+          return Err(invalid("bad FSE distribution"));
+        }
+        high -= 1;
+        entries[high].symbol = symbol as u8;
+        next[symbol] = 1;
+      } else {
+        next[symbol] = probability as u32;
+      }
+    }
+
+    let step = (size >> 1) + (size >> 3) + 3;
+    let mask = size - 1;
+    let mut pos = 0;
+
+    for (symbol, &probability) in probabilities.iter().enumerate() {
+      for _ in 0..i16::max(probability, 0) {
+        entries[pos].symbol = symbol as u8;
+
+        pos = (pos + step) & mask;
+        while pos >= high {
+          pos = (pos + step) & mask;
+        }
+      }
+    }
+    if pos != 0 {
+      return Err(invalid("bad FSE distribution"));
+    }
+
+    for entry in &mut entries {
+      let state = next[entry.symbol as usize];
+      next[entry.symbol as usize] += 1;
+
+      let bits = log - (31 - state.leading_zeros());
+      entry.bits = bits as u8;
+      entry.base = ((state << bits) - size as u32) as u16;
+    }
+
+    Ok(Fse { log, entries })
+  }
+
+  /// A table which only ever gives back `symbol`.
+  fn rle(symbol: u8) -> Self {
+    Fse { log: 0, entries: vec![FseEntry { symbol, bits: 0, base: 0 }] }
+  }
+
+  /// Read a table description from the start of `data`, giving back the
+  /// table and how many bytes it took up.
+  fn read(data: &[u8], max_log: u32, max_symbol: usize) -> io::Result<(Self, usize)> {
+    let mut bits = ForwardBits::new(data);
+    let log = bits.bits(4) + 5;
+
+    if log > max_log {
+      return Err(invalid("FSE table is too large"));
+    }
+
+    let mut remaining: i32 = (1 << log) + 1;
+    let mut threshold: i32 = 1 << log;
+    let mut count_bits = log + 1;
+    let mut probabilities = Vec::new();
+
+    while remaining > 1 {
+      if probabilities.len() > max_symbol {
+        return Err(invalid("too many symbols in FSE table"));
+      }
+
+      let max = 2 * threshold - 1 - remaining;
+      let mut count = bits.peek(count_bits - 1) as i32;
+
+      if count < max {
+        bits.bits(count_bits - 1);
+      } else {
+        count = bits.bits(count_bits) as i32;
+        if count >= threshold {
+          count -= max;
+        }
+      }
+
+      count -= 1;
+      remaining -= count.abs();
+      probabilities.push(count as i16);
+
+      // A zero is followed by how many more zeros come after it.
+      if count == 0 {
+        loop {
+          let repeat = bits.bits(2);
+          probabilities.extend((0..repeat).map(|_| 0));
+          if repeat != 3 {
+            break;
+          }
+        }
+      }
+
+      while remaining < threshold {
+        count_bits -= 1;
+        threshold >>= 1;
+      }
+    }
+
+    if remaining != 1 || probabilities.len() > max_symbol + 1 || bits.overran() {
+      return Err(invalid("bad FSE table description"));
+    }
+
+    Ok((Fse::new(log, &probabilities)?, bits.bytes_used()))
+  }
+
+  fn symbol(&self, state: usize) -> u8 {
+    self.entries[state].symbol
+  }
+
+  fn update(&self, state: usize, bits: &mut BackwardBits) -> usize {
+    let entry = self.entries[state];
+    usize::from(entry.base) + bits.bits(u32::from(entry.bits)) as usize
+  }
+}
+
+/// A Huffman code for literals, as a lookup table indexed by the next
+/// `max_bits` bits of the stream.
+#[derive(Clone)]
+struct Huffman {
+  max_bits: u32,
+  /// Each entry is a symbol and the length of its code.
+  entries: Vec<(u8, u8)>
+}
+
+impl Huffman {
+  /// Read a Huffman tree description from the start of `data`, giving back
+  /// the code and how many bytes it took up.
+  fn read(data: &[u8]) -> io::Result<(Self, usize)> {
+    let header = *data.first().ok_or_else(|| invalid("missing Huffman tree"))? as usize;
+    let mut weights = Vec::new();
+
+    let used = if header < 128 {
+      let compressed = data.get(1..1 + header).ok_or_else(|| invalid("Huffman tree runs past the block"))?;
+      let (table, table_len) = Fse::read(compressed, 6, 255)?;
+      let mut bits = BackwardBits::new(&compressed[table_len..])?;
+
+      // Two states take turns, until the stream runs out.
+      let mut first = bits.bits(table.log) as usize;
+      let mut second = bits.bits(table.log) as usize;
+
+      loop {
+        weights.push(table.symbol(first));
+        first = table.update(first, &mut bits);
+        if bits.overran() {
+          weights.push(table.symbol(second));
+          break;
+        }
+
+        weights.push(table.symbol(second));
+        second = table.update(second, &mut bits);
+        if bits.overran() {
+          weights.push(table.symbol(first));
+          break;
+        }
+
+        if weights.len() > 255 {
+          return Err(invalid("too many Huffman weights"));
+        }
+      }
+
+      1 + header
+    } else {
+      let count = header - 127;
+      let packed = data.get(1..1 + count.div_ceil(2)).ok_or_else(|| invalid("Huffman tree runs past the block"))?;
+
+      for index in 0..count {
+        let byte = packed[index / 2];
+        weights.push(if index % 2 == 0 { byte >> 4 } else { byte & 0xF });
+      }
+
+      1 + packed.len()
+    };
+
+    Ok((Huffman::new(weights)?, used))
+  }
+
+  /// Build the code from each symbol's weight. The last symbol's weight is
+  /// left out, since it's whatever makes the code complete.
+  fn new(mut weights: Vec<u8>) -> io::Result<Self> {
+    if weights.len() > 255 {
+      return Err(invalid("too many Huffman weights"));
+    }
+
+    let mut total: u32 = 0;
+    for &weight in &weights {
+      if weight > 11 {
+        return Err(invalid("Huffman weight is too large"));
+      }
+      if weight > 0 {
+        total += 1 << (weight - 1);
+      }
+    }
+    if total == 0 {
+      return Err(invalid("empty Huffman tree"));
+    }
+
+    let max_bits = 32 - total.leading_zeros();
+    let left = (1 << max_bits) - total;
+    if max_bits > 11 || !left.is_power_of_two() {
+      return Err(invalid("incomplete Huffman tree"));
+    }
+    weights.push(left.trailing_zeros() as u8 + 1);
+
+    // Lighter symbols get longer codes, which come first.
+    let mut entries = Vec::with_capacity(1 << max_bits);
+    for weight in 1..=max_bits as u8 {
+      for (symbol, _) in weights.iter().enumerate().filter(|&(_, &w)| w == weight) {
+        let len = max_bits as u8 + 1 - weight;
+        entries.extend((0..1 << (weight - 1)).map(|_| (symbol as u8, len)));
+      }
+    }
+
+    Ok(Huffman { max_bits, entries })
+  }
+
+  /// Decode exactly `count` literals from a single stream.
+  fn decode(&self, data: &[u8], count: usize, literals: &mut Vec<u8>) -> io::Result<()> {
+    let mut bits = BackwardBits::new(data)?;
+
+    for _ in 0..count {
+      let (symbol, len) = self.entries[bits.peek(self.max_bits) as usize];
+      bits.bits(u32::from(len));
+      literals.push(symbol);
+    }
+
+    if !bits.finished() {
+      return Err(invalid("corrupt Huffman stream"));
+    }
+    Ok(())
+  }
+}
+
+/// A streaming XXH64 hash, which is what frame checksums are made from.
+struct Xxh64 {
+  lanes: [u64; 4],
+  pending: Vec<u8>,
+  len: u64
+}
+
+const PRIME64_1: u64 = 0x9E37_79B1_85EB_CA87;
+const PRIME64_2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const PRIME64_3: u64 = 0x1656_67B1_9E37_79F9;
+const PRIME64_4: u64 = 0x85EB_CA77_C2B2_AE63;
+const PRIME64_5: u64 = 0x27D4_EB2F_1656_67C5;
+
+fn xxh64_round(acc: u64, input: u64) -> u64 {
+  acc.wrapping_add(input.wrapping_mul(PRIME64_2)).rotate_left(31).wrapping_mul(PRIME64_1)
+}
+
+fn xxh64_merge(acc: u64, lane: u64) -> u64 {
+  (acc ^ xxh64_round(0, lane)).wrapping_mul(PRIME64_1).wrapping_add(PRIME64_4)
+}
+
+fn u64_le(bytes: &[u8]) -> u64 {
+  bytes.iter().rev().fold(0, |acc, &byte| acc << 8 | u64::from(byte))
+}
+
+impl Xxh64 {
+  fn new() -> Self {
+    Xxh64 {
+      lanes: [
+        PRIME64_1.wrapping_add(PRIME64_2),
+        PRIME64_2,
+        0,
+        0u64.wrapping_sub(PRIME64_1)
+      ],
+      pending: Vec::with_capacity(32),
+      len: 0
+    }
+  }
+
+  fn update(&mut self, mut bytes: &[u8]) {
+    self.len += bytes.len() as u64;
+
+    if self.pending.len() + bytes.len() < 32 {
+      self.pending.extend_from_slice(bytes);
+      return;
+    }
+
+    if !self.pending.is_empty() {
+      let (start, rest) = bytes.split_at(32 - self.pending.len());
+      let mut stripe = ::std::mem::take(&mut self.pending);
+      stripe.extend_from_slice(start);
+      self.stripe(&stripe);
+      bytes = rest;
+    }
+
+    let mut stripes = bytes.chunks_exact(32);
+    for stripe in &mut stripes {
+      self.stripe(stripe);
+    }
+    self.pending.extend_from_slice(stripes.remainder());
+  }
+
+  fn stripe(&mut self, stripe: &[u8]) {
+    for (lane, input) in self.lanes.iter_mut().zip(stripe.chunks_exact(8)) {
+      *lane = xxh64_round(*lane, u64_le(input));
+    }
+  }
+
+  fn finish(&self) -> u64 {
+    let [v1, v2, v3, v4] = self.lanes;
+
+    let mut hash = if self.len >= 32 {
+      let hash = v1.rotate_left(1)
+        .wrapping_add(v2.rotate_left(7))
+        .wrapping_add(v3.rotate_left(12))
+        .wrapping_add(v4.rotate_left(18));
+      self.lanes.iter().fold(hash, |hash, &lane| xxh64_merge(hash, lane))
+    } else {
+      PRIME64_5
+    };
+    hash = hash.wrapping_add(self.len);
+
+    let mut rest = &self.pending[..];
+    while rest.len() >= 8 {
+      hash ^= xxh64_round(0, u64_le(&rest[..8]));
+      hash = hash.rotate_left(27).wrapping_mul(PRIME64_1).wrapping_add(PRIME64_4);
+      rest = &rest[8..];
+    }
+    if rest.len() >= 4 {
+      hash ^= u64_le(&rest[..4]).wrapping_mul(PRIME64_1);
+      hash = hash.rotate_left(23).wrapping_mul(PRIME64_2).wrapping_add(PRIME64_3);
+      rest = &rest[4..];
+    }
+    for &byte in rest {
+      hash ^= u64::from(byte).wrapping_mul(PRIME64_5);
+      hash = hash.rotate_left(11).wrapping_mul(PRIME64_1);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(PRIME64_2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(PRIME64_3);
+    hash ^ (hash >> 32)
+  }
+}
+
+enum State {
+  FrameHeader,
+  Block,
+  Checksum,
+  Done
+}
+
+/// Decompresses a zstd stream read from `R`. Multiple concatenated frames
+/// are decompressed one after another, like `zstdcat` does, and skippable
+/// frames are skipped.
+pub struct ZstdDecoder<R> {
+  inner: BufReader<R>,
+  state: State,
+  /// How far back matches can reach in the current frame.
+  window: usize,
+  has_checksum: bool,
+  hash: Xxh64,
+  /// How much the current frame has decompressed to so far.
+  frame_len: usize,
+  /// The three most recent match offsets.
+  offsets: [usize; 3],
+  huffman: Option<Huffman>,
+  literal_lengths: Option<Fse>,
+  offset_codes: Option<Fse>,
+  match_lengths: Option<Fse>,
+  /// Everything decompressed that hasn't been trimmed yet; the tail of this
+  /// is the window that matches refer into.
+  output: Vec<u8>,
+  /// How much of `output` has been handed back to the caller already.
+  delivered: usize
+}
+
+impl<R: Read> ZstdDecoder<R> {
+  pub fn new(inner: R) -> Self {
+    ZstdDecoder {
+      inner: BufReader::new(inner),
+      state: State::FrameHeader,
+      window: 0,
+      has_checksum: false,
+      hash: Xxh64::new(),
+      frame_len: 0,
+      offsets: [1, 4, 8],
+      huffman: None,
+      literal_lengths: None,
+      offset_codes: None,
+      match_lengths: None,
+      output: Vec::new(),
+      delivered: 0
+    }
+  }
+
+  fn byte(&mut self) -> io::Result<u8> {
+    let byte = match self.inner.fill_buf()?.first() {
+      Some(&byte) => byte,
+      None => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "zstd stream ended early"))
+    };
+    self.inner.consume(1);
+
+    Ok(byte)
+  }
+
+  fn bytes(&mut self, count: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![0; count];
+    self.inner.read_exact(&mut bytes).map_err(|err| if err.kind() == io::ErrorKind::UnexpectedEof {
+      io::Error::new(io::ErrorKind::UnexpectedEof, "zstd stream ended early")
+    } else {
+      err
+    })?;
+
+    Ok(bytes)
+  }
+
+  fn uint_le(&mut self, count: usize) -> io::Result<u64> {
+    Ok(u64_le(&self.bytes(count)?))
+  }
+
+  /// Read a frame header, skipping over any skippable frames first. Gives
+  /// back `false` if the stream ends after a skippable frame.
+  fn frame_header(&mut self) -> io::Result<bool> {
+    let mut magic = self.uint_le(4)? as u32;
+
+    while magic & 0xFFFF_FFF0 == SKIPPABLE_MAGIC {
+      let len = self.uint_le(4)?;
+      if io::copy(&mut self.inner.by_ref().take(len), &mut io::sink())? < len {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "zstd stream ended early"));
+      }
+
+      if self.inner.fill_buf()?.is_empty() {
+        return Ok(false);
+      }
+      magic = self.uint_le(4)? as u32;
+    }
+
+    if magic != MAGIC {
+      return Err(invalid("bad magic number"));
+    }
+
+    let descriptor = self.byte()?;
+    let single_segment = descriptor & 0x20 != 0;
+
+    if descriptor & 0x08 != 0 {
+      return Err(invalid("reserved bit is set"));
+    }
+
+    let mut window = 0;
+    if !single_segment {
+      let descriptor = self.byte()?;
+      let base = 1u64 << (10 + (descriptor >> 3));
+      window = base + base / 8 * u64::from(descriptor & 7);
+    }
+
+    let dictionary_len = [0, 1, 2, 4][(descriptor & 3) as usize];
+    if self.uint_le(dictionary_len)? != 0 {
+      return Err(io::Error::new(io::ErrorKind::InvalidData, "zstd frames which need a dictionary aren't supported"));
+    }
+
+    let content_len = match descriptor >> 6 {
+      0 if single_segment => self.uint_le(1)?,
+      0 => 0,
+      1 => self.uint_le(2)? + 256,
+      2 => self.uint_le(4)?,
+      _ => self.uint_le(8)?
+    };
+    if single_segment {
+      window = content_len;
+    }
+
+    self.window = if window > usize::MAX as u64 { usize::MAX } else { window as usize };
+    self.has_checksum = descriptor & 0x04 != 0;
+    self.hash = Xxh64::new();
+    self.frame_len = 0;
+    self.offsets = [1, 4, 8];
+    self.huffman = None;
+    self.literal_lengths = None;
+    self.offset_codes = None;
+    self.match_lengths = None;
+
+    Ok(true)
+  }
+
+  /// Decompress one block, giving back whether it was the frame's last.
+  fn block(&mut self) -> io::Result<bool> {
+    let header = self.uint_le(3)? as usize;
+    let last = header & 1 != 0;
+    let len = header >> 3;
+
+    if len > MAX_BLOCK_SIZE {
+      return Err(invalid("block is too large"));
+    }
+
+    match (header >> 1) & 3 {
+      0 => {
+        let bytes = self.bytes(len)?;
+        self.output.extend_from_slice(&bytes);
+      },
+      1 => {
+        let byte = self.byte()?;
+        self.output.resize(self.output.len() + len, byte);
+      },
+      2 => {
+        let block = self.bytes(len)?;
+        self.compressed_block(&block)?;
+      },
+      _ => return Err(invalid("reserved block type"))
+    }
+
+    Ok(last)
+  }
+
+  fn compressed_block(&mut self, block: &[u8]) -> io::Result<()> {
+    let start = self.output.len();
+    let (literals, used) = self.literals(block)?;
+    let sequences = &block[used..];
+
+    let (count, mut pos) = match sequences {
+      [] => return Err(invalid("missing sequences section")),
+      [0, ..] => (0, 1),
+      [byte, ..] if *byte < 128 => (usize::from(*byte), 1),
+      [byte, next, ..] if *byte < 255 => ((usize::from(*byte) - 128) << 8 | usize::from(*next), 2),
+      [_, low, high, ..] => ((usize::from(*low) | usize::from(*high) << 8) + 0x7F00, 3),
+      _ => return Err(invalid("sequences section is cut short"))
+    };
+
+    if count == 0 {
+      self.output.extend_from_slice(&literals);
+      return Ok(());
+    }
+
+    let modes = *sequences.get(pos).ok_or_else(|| invalid("sequences section is cut short"))?;
+    pos += 1;
+    if modes & 3 != 0 {
+      return Err(invalid("reserved bits are set"));
+    }
+
+    let literal_lengths = sequence_table(modes >> 6, &sequences[pos..], &mut pos, &mut self.literal_lengths, &LITERAL_LENGTH_DEFAULT, 6, 9, 35)?;
+    let offset_codes = sequence_table((modes >> 4) & 3, &sequences[pos..], &mut pos, &mut self.offset_codes, &OFFSET_DEFAULT, 5, 8, 31)?;
+    let match_lengths = sequence_table((modes >> 2) & 3, &sequences[pos..], &mut pos, &mut self.match_lengths, &MATCH_LENGTH_DEFAULT, 6, 9, 52)?;
+
+    let mut bits = BackwardBits::new(&sequences[pos..])?;
+    let mut literal_state = bits.bits(literal_lengths.log) as usize;
+    let mut offset_state = bits.bits(offset_codes.log) as usize;
+    let mut match_state = bits.bits(match_lengths.log) as usize;
+    let mut literals_used = 0;
+
+    for index in 0..count {
+      let literal_code = usize::from(literal_lengths.symbol(literal_state));
+      let offset_code = u32::from(offset_codes.symbol(offset_state));
+      let match_code = usize::from(match_lengths.symbol(match_state));
+
+      if literal_code > 35 || match_code > 52 || offset_code > 31 {
+        return Err(invalid("bad sequence code"));
+      }
+
+      let offset_value = (1 << offset_code) + bits.bits(offset_code) as usize;
+      let match_len = MATCH_LENGTH_BASE[match_code] as usize + bits.bits(u32::from(MATCH_LENGTH_EXTRA[match_code])) as usize;
+      let literal_len = LITERAL_LENGTH_BASE[literal_code] as usize + bits.bits(u32::from(LITERAL_LENGTH_EXTRA[literal_code])) as usize;
+
+      if index + 1 < count {
+        literal_state = literal_lengths.update(literal_state, &mut bits);
+        match_state = match_lengths.update(match_state, &mut bits);
+        offset_state = offset_codes.update(offset_state, &mut bits);
+      }
+
+      let offset = self.offset(offset_value, literal_len)?;
+
+      let literal_end = literals_used + literal_len;
+      if literal_end > literals.len() {
+        return Err(invalid("sequence uses more literals than there are"));
+      }
+      self.output.extend_from_slice(&literals[literals_used..literal_end]);
+      literals_used = literal_end;
+
+      let available = usize::min(self.frame_len + (self.output.len() - start), self.output.len());
+      if offset > available {
+        return Err(invalid("match reaches back too far"));
+      }
+
+      let from = self.output.len() - offset;
+      if offset >= match_len {
+        self.output.extend_from_within(from..from + match_len);
+      } else {
+        for index in from..from + match_len {
+          let byte = self.output[index];
+          self.output.push(byte);
+        }
+      }
+    }
+
+    if !bits.finished() {
+      return Err(invalid("corrupt sequences"));
+    }
+    self.output.extend_from_slice(&literals[literals_used..]);
+
+    if self.output.len() - start > MAX_BLOCK_SIZE {
+      return Err(invalid("block is too large"));
+    }
+    Ok(())
+  }
+
+  /// Work out a match's offset from its offset value, keeping track of the
+  /// most recent offsets.
+  fn offset(&mut self, value: usize, literal_len: usize) -> io::Result<usize> {
+    let offsets = &mut self.offsets;
+
+    if value > 3 {
+      *offsets = [value - 3, offsets[0], offsets[1]];
+      return Ok(offsets[0]);
+    }
+
+    // With no literals, repeating the last offset wouldn't make sense, so
+    // everything moves along by one.
+    let repeat = if literal_len == 0 { value } else { value - 1 };
+    let offset = match repeat {
+      0 => return Ok(offsets[0]),
+      1 => offsets[1],
+      2 => offsets[2],
+      _ => offsets[0].checked_sub(1).filter(|&offset| offset > 0).ok_or_else(|| invalid("offset of 0"))?
+    };
+
+    if repeat == 1 {
+      offsets.swap(0, 1);
+    } else {
+      *offsets = [offset, offsets[0], offsets[1]];
+    }
+    Ok(offset)
+  }
+
+  /// Decode the literals section at the start of a block, giving back the
+  /// literals and how many bytes the section took up.
+  fn literals(&mut self, block: &[u8]) -> io::Result<(Vec<u8>, usize)> {
+    let short = || invalid("literals section is cut short");
+    let first = *block.first().ok_or_else(short)?;
+    let header = |len: usize| block.get(..len).map(u64_le).ok_or_else(short);
+
+    match first & 3 {
+      kind @ 0..=1 => {
+        let (len, header_len) = match (first >> 2) & 3 {
+          0 | 2 => (usize::from(first >> 3), 1),
+          1 => ((header(2)? >> 4) as usize, 2),
+          _ => ((header(3)? >> 4) as usize, 3)
+        };
+
+        if kind == 0 {
+          let literals = block.get(header_len..header_len + len).ok_or_else(short)?;
+          Ok((literals.to_vec(), header_len + len))
+        } else {
+          let byte = *block.get(header_len).ok_or_else(short)?;
+          Ok((vec![byte; len], header_len + 1))
+        }
+      },
+      kind => {
+        let size_format = (first >> 2) & 3;
+        let (header_len, size_bits) = match size_format {
+          0 | 1 => (3, 10),
+          2 => (4, 14),
+          _ => (5, 18)
+        };
+        let sizes = header(header_len)? >> 4;
+        let mask = (1 << size_bits) - 1;
+        let len = (sizes & mask) as usize;
+        let compressed_len = (sizes >> size_bits & mask) as usize;
+
+        let mut data = block.get(header_len..header_len + compressed_len).ok_or_else(short)?;
+        if kind == 2 {
+          let (huffman, used) = Huffman::read(data)?;
+          self.huffman = Some(huffman);
+          data = &data[used..];
+        }
+        let huffman = self.huffman.as_ref().ok_or_else(|| invalid("no Huffman tree to reuse"))?;
+
+        let mut literals = Vec::with_capacity(len);
+        if size_format == 0 {
+          huffman.decode(data, len, &mut literals)?;
+        } else {
+          // Four streams, with a table of the first three's sizes up front.
+          if data.len() < 6 {
+            return Err(short());
+          }
+          let (sizes, mut rest) = data.split_at(6);
+          let per_stream = len.div_ceil(4);
+
+          for stream in 0..4 {
+            let (stream_len, count) = if stream < 3 {
+              (u64_le(&sizes[stream * 2..stream * 2 + 2]) as usize, per_stream)
+            } else {
+              (rest.len(), len.checked_sub(3 * per_stream).ok_or_else(|| invalid("bad literals size"))?)
+            };
+
+            if stream_len > rest.len() {
+              return Err(short());
+            }
+            let (this, next) = rest.split_at(stream_len);
+            huffman.decode(this, count, &mut literals)?;
+            rest = next;
+          }
+        }
+
+        Ok((literals, header_len + compressed_len))
+      }
+    }
+  }
+
+  fn checksum(&mut self) -> io::Result<()> {
+    let checksum = self.uint_le(4)?;
+
+    if checksum != self.hash.finish() & 0xFFFF_FFFF {
+      return Err(invalid("checksum mismatch"));
+    }
+    Ok(())
+  }
+
+  fn end_of_frame(&mut self) -> io::Result<State> {
+    if self.inner.fill_buf()?.is_empty() { Ok(State::Done) } else { Ok(State::FrameHeader) }
+  }
+
+  /// Move the decoder along, until there's either new output, or the
+  /// stream is finished.
+  fn step(&mut self) -> io::Result<()> {
+    let before = self.output.len();
+
+    while self.output.len() == before {
+      self.state = match ::std::mem::replace(&mut self.state, State::Done) {
+        State::FrameHeader => {
+          if self.frame_header()? { State::Block } else { State::Done }
+        },
+        State::Block => {
+          if !self.block()? {
+            State::Block
+          } else if self.has_checksum {
+            State::Checksum
+          } else {
+            self.end_of_frame()?
+          }
+        },
+        State::Checksum => {
+          self.checksum()?;
+          self.end_of_frame()?
+        },
+        State::Done => return Ok(())
+      };
+    }
+
+    let new = &self.output[before..];
+    self.hash.update(new);
+    self.frame_len += new.len();
+    Ok(())
+  }
+}
+
+/// Work out the decoding table for one kind of sequence code, from its
+/// compression mode. `pos` gets moved past the table's description, if it
+/// has one.
+#[allow(clippy::too_many_arguments)]
+fn sequence_table(mode: u8, data: &[u8], pos: &mut usize, previous: &mut Option<Fse>, default: &[i16], default_log: u32, max_log: u32, max_symbol: usize) -> io::Result<Fse> {
+  let table = match mode {
+    0 => Fse::new(default_log, default)?,
+    1 => {
+      let symbol = *data.first().ok_or_else(|| invalid("sequences section is cut short"))?;
+      *pos += 1;
+      Fse::rle(symbol)
+    },
+    2 => {
+      let (table, used) = Fse::read(data, max_log, max_symbol)?;
+      *pos += used;
+      table
+    },
+    _ => previous.clone().ok_or_else(|| invalid("no sequence table to reuse"))?
+  };
+
+  *previous = Some(table.clone());
+  Ok(table)
+}
+
+impl<R: Read> Read for ZstdDecoder<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.delivered == self.output.len() {
+      // Only the last window's worth of output needs to stick around.
+      let keep = usize::max(self.window, MAX_BLOCK_SIZE);
+      if self.output.len() > keep.saturating_mul(2) {
+        let trim = self.output.len() - keep;
+        self.output.drain(..trim);
+        self.delivered -= trim;
+      }
+
+      let before = self.output.len();
+      if let Err(err) = self.step() {
+        self.state = State::Done;
+        return Err(err);
+      }
+      if self.output.len() == before {
+        return Ok(0);
+      }
+    }
+
+    let available = &self.output[self.delivered..];
+    let amt = usize::min(available.len(), buf.len());
+    buf[..amt].copy_from_slice(&available[..amt]);
+    self.delivered += amt;
+
+    Ok(amt)
+  }
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "async")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+
+use inputs::{attach_input_dir, INPUTS};
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+  fn wake(self: Arc<Self>) {
+    self.0.unpark();
+  }
+}
+
+/// Just enough of an executor to run one future to the end.
+fn block_on<F: Future>(future: F) -> F::Output {
+  let waker = Arc::new(Unpark(thread::current())).into();
+  let mut cx = Context::from_waker(&waker);
+  let mut future = pin!(future);
+
+  loop {
+    match future.as_mut().poll(&mut cx) {
+      Poll::Ready(output) => return output,
+      Poll::Pending => thread::park()
+    }
+  }
+}
+
+#[test]
+fn test_input_async() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input_async(filenames).unwrap();
+  let mut result = Vec::new();
+  let mut buf = [0; 3];
+
+  loop {
+    match block_on(all_input.read(&mut buf)).unwrap() {
+      0 => break,
+      read => result.extend_from_slice(&buf[..read])
+    }
+  }
+
+  assert_eq!(String::from_utf8(result).unwrap(), "A\nB\nC\nD\nE\n");
+}
+
+#[test]
+fn test_input_async_stays_finished() {
+  let mut all_input = arg_input::input_async(vec![attach_input_dir("A")]).unwrap();
+  let mut buf = [0; 16];
+
+  assert_eq!(block_on(all_input.read(&mut buf)).unwrap(), 2);
+  assert_eq!(block_on(all_input.read(&mut buf)).unwrap(), 0);
+  assert_eq!(block_on(all_input.read(&mut buf)).unwrap(), 0);
+}
+
+#[test]
+fn test_input_lines_stream() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut lines = arg_input::input_lines_stream(filenames).unwrap();
+  let mut result = Vec::new();
+
+  while let Some(line) = block_on(lines.next_line()) {
+    result.push(line.unwrap());
+  }
+
+  assert_eq!(result, INPUTS.to_vec());
+  assert!(block_on(lines.next_line()).is_none());
+}
@@ -0,0 +1,101 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Cursor, Read};
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+fn banner(path: &std::path::Path) -> String {
+  if path == std::path::Path::new("-") {
+    "==> standard input <==".to_string()
+  } else {
+    format!("==> {} <==", path.file_name().unwrap().to_string_lossy())
+  }
+}
+
+#[test]
+fn test_banners() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("three_lines")];
+
+  let lines: Vec<String> = InputBuilder::new()
+    .with_banners(banner)
+    .build(filenames)
+    .unwrap()
+    .lines()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["==> A <==", "A", "==> three_lines <==", "one", "two", "three"]);
+}
+
+#[test]
+fn test_banners_single_source() {
+  let mut reader = InputBuilder::new()
+    .with_banners(banner)
+    .build(vec![attach_input_dir("A")])
+    .unwrap();
+  let mut result_string = String::new();
+
+  reader.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\n");
+}
+
+#[test]
+fn test_banners_stdin() {
+  let filenames = vec![PathBuf::from("-"), attach_input_dir("empty")];
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut reader = InputBuilder::new()
+    .with_banners(banner)
+    .build_with_stdin(filenames, stdin)
+    .unwrap();
+  let mut result_string = String::new();
+
+  reader.read_to_string(&mut result_string).unwrap();
+
+  // Empty files still get a banner.
+  assert_eq!(result_string, "==> standard input <==\nfrom stdin\n==> empty <==\n");
+}
+
+#[test]
+fn test_banners_strip_bom() {
+  let filenames = vec![attach_input_dir("bom"), attach_input_dir("A")];
+
+  let mut reader = InputBuilder::new()
+    .with_banners(banner)
+    .strip_bom(true)
+    .build(filenames)
+    .unwrap();
+  let mut result_string = String::new();
+
+  reader.read_to_string(&mut result_string).unwrap();
+
+  assert!(result_string.starts_with("==> bom <==\n"));
+  assert!(!result_string.contains('\u{FEFF}'));
+  assert!(result_string.ends_with("==> A <==\nA\n"));
+}
+
+#[test]
+fn test_banners_peek() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("B")];
+
+  let mut reader = InputBuilder::new()
+    .with_banners(banner)
+    .build(filenames)
+    .unwrap();
+
+  // Peeking into the next file sees its banner, just like reading does,
+  // and reading afterwards doesn't see it twice.
+  let peeked = reader.peek(64).unwrap().to_vec();
+  let mut result_string = String::new();
+
+  reader.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "==> A <==\nA\n==> B <==\nB\n");
+  assert_eq!(String::from_utf8(peeked).unwrap(), result_string);
+}
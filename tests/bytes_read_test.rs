@@ -0,0 +1,57 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Read};
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_bytes_read() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut buf = [0; 3];
+
+  assert_eq!(all_input.bytes_read(), 0);
+
+  all_input.read_exact(&mut buf).unwrap();
+  assert_eq!(all_input.bytes_read(), 3);
+
+  all_input.peek(4).unwrap();
+  assert_eq!(all_input.bytes_read(), 3);
+
+  let mut line = String::new();
+  all_input.read_line(&mut line).unwrap();
+  assert_eq!(all_input.bytes_read(), 4);
+
+  all_input.read_to_end(&mut Vec::new()).unwrap();
+  assert_eq!(all_input.bytes_read(), 10);
+}
+
+#[test]
+fn test_bytes_read_large_reads() {
+  let filenames = vec![attach_input_dir("big"), attach_input_dir("A")];
+  let expected = std::fs::metadata(attach_input_dir("big")).unwrap().len() + 2;
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut buf = vec![0; 64 * 1024];
+
+  while all_input.read(&mut buf).unwrap() > 0 {}
+
+  assert_eq!(all_input.bytes_read(), expected);
+}
+
+#[test]
+fn test_bytes_read_strip_bom() {
+  let filenames = vec![attach_input_dir("bom")];
+  let expected = std::fs::metadata(attach_input_dir("bom")).unwrap().len() - 3;
+
+  let mut all_input = arg_input::input_text(filenames).unwrap();
+
+  all_input.read_to_end(&mut Vec::new()).unwrap();
+
+  assert_eq!(all_input.bytes_read(), expected);
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "bzip2")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::{Cursor, Read};
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_bzip2() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("compressed.bz2"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\ncompressed\nB\n");
+}
+
+#[test]
+fn test_bzip2_large() {
+  let filenames = vec![attach_input_dir("big.bz2")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, fs::read(attach_input_dir("big")).unwrap());
+}
+
+#[test]
+fn test_bzip2_multiple_streams() {
+  let filenames = vec![attach_input_dir("multi.bz2")];
+
+  let mut all_input = arg_input::input_lazy(filenames);
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "first\nsecond\n");
+}
+
+#[test]
+fn test_bzip2_files_decoded_separately() {
+  let filenames = vec![attach_input_dir("compressed.bz2"), attach_input_dir("multi.bz2"), attach_input_dir("compressed.bz2")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\nfirst\nsecond\ncompressed\n");
+}
+
+#[test]
+fn test_bzip2_corrupt() {
+  let mut compressed = fs::read(attach_input_dir("big.bz2")).unwrap();
+  let len = compressed.len();
+  compressed[len - 1] ^= 0xFF;
+
+  let mut all_input = InputBuilder::new()
+    .bzip2_stdin(true)
+    .build_with_stdin(["-"], Box::new(Cursor::new(compressed)))
+    .unwrap();
+  let mut result = Vec::new();
+
+  assert!(all_input.read_to_end(&mut result).is_err());
+}
+
+#[test]
+fn test_bzip2_stdin() {
+  let compressed = fs::read(attach_input_dir("compressed.bz2")).unwrap();
+
+  let raw = arg_input::input_with_stdin(["-"], Box::new(Cursor::new(compressed.clone())));
+  let mut result = Vec::new();
+
+  raw.unwrap().read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, compressed);
+
+  let decompressed = InputBuilder::new()
+    .bzip2_stdin(true)
+    .build_with_stdin(["-"], Box::new(Cursor::new(compressed)));
+  let mut result_string = String::new();
+
+  decompressed.unwrap().read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\n");
+}
+
+#[test]
+fn test_bzip2_auto_decompress() {
+  let compressed = fs::read(attach_input_dir("compressed.bz2")).unwrap();
+
+  let mut all_input = InputBuilder::new()
+    .auto_decompress(true)
+    .build_with_stdin(vec![attach_input_dir("A"), "-".into()], Box::new(Cursor::new(compressed)))
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\ncompressed\n");
+}
+
+#[test]
+fn test_bzip2_no_progress() {
+  let all_input = arg_input::input(vec![attach_input_dir("A"), attach_input_dir("compressed.bz2")]).unwrap();
+
+  assert_eq!(all_input.progress(), None);
+}
+
+#[test]
+fn test_bzip2_mixed_with_plain_files() {
+  let filenames = vec![attach_input_dir("compressed.bz2"), attach_input_dir("A"), attach_input_dir("multi.bz2"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\nA\nfirst\nsecond\nB\n");
+}
@@ -0,0 +1,45 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::InputReadError;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_count_lines() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  assert_eq!(arg_input::count_lines(filenames).unwrap(), INPUTS.len() as u64);
+}
+
+#[test]
+fn test_count_lines_big() {
+  let filenames = vec![attach_input_dir("big"), attach_input_dir("three_lines")];
+  let expected = arg_input::input_lines(filenames.clone()).unwrap().count() as u64;
+
+  assert_eq!(arg_input::count_lines(filenames).unwrap(), expected);
+}
+
+#[test]
+fn test_count_lines_no_newline() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("no_newline")];
+
+  // `no_newline` has a line, but no newline to count.
+  assert_eq!(arg_input::count_lines(filenames).unwrap(), 1);
+  assert_eq!(arg_input::count_lines(vec![attach_input_dir("empty")]).unwrap(), 0);
+}
+
+#[test]
+fn test_count_lines_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match arg_input::count_lines(filenames) {
+    Err(InputReadError::Input(err)) => assert_eq!(err.badfiles.len(), NONEXISTENT.len()),
+    _ => panic!("expected the files to be missing")
+  }
+}
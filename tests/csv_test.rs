@@ -0,0 +1,79 @@
+#![cfg(feature = "csv")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::attach_input_dir;
+
+fn record(fields: &[&str]) -> Vec<String> {
+  fields.iter().map(|field| field.to_string()).collect()
+}
+
+#[test]
+fn test_input_csv() {
+  let filenames = vec![attach_input_dir("shard1.csv"), attach_input_dir("shard2.csv")];
+
+  let mut records = arg_input::input_csv(filenames).unwrap();
+
+  assert_eq!(records.headers().unwrap(), Some(&record(&["name", "note"])[..]));
+
+  let records: Vec<Vec<String>> = records.map(|record| record.unwrap()).collect();
+
+  assert_eq!(records, vec![
+    record(&["alice", "likes, commas"]),
+    record(&["bob", "says \"hi\""]),
+    record(&["name", "note"]),
+    record(&["carol", "two\nlines"]),
+    record(&["dave", "plain"])
+  ]);
+}
+
+#[test]
+fn test_input_csv_skip_repeated_headers() {
+  let filenames = vec![attach_input_dir("shard1.csv"), attach_input_dir("shard2.csv")];
+
+  let records: Vec<Vec<String>> = arg_input::input_csv(filenames)
+    .unwrap()
+    .skip_repeated_headers(true)
+    .map(|record| record.unwrap())
+    .collect();
+
+  assert_eq!(records.len(), 4);
+  assert_eq!(records[2], record(&["carol", "two\nlines"]));
+}
+
+#[test]
+fn test_input_csv_no_headers() {
+  let records: Vec<Vec<String>> = arg_input::input_csv(vec![attach_input_dir("shard1.csv")])
+    .unwrap()
+    .has_headers(false)
+    .map(|record| record.unwrap())
+    .collect();
+
+  assert_eq!(records[0], record(&["name", "note"]));
+  assert_eq!(records.len(), 3);
+}
+
+#[test]
+fn test_input_csv_unterminated_quote() {
+  let filenames = vec![attach_input_dir("unterminated.csv"), attach_input_dir("shard1.csv")];
+
+  let mut records = arg_input::input_csv(filenames).unwrap();
+
+  assert!(records.next().unwrap().is_err());
+  assert_eq!(records.next().unwrap().unwrap(), record(&["name", "note"]));
+}
+
+#[test]
+fn test_input_csv_stray_quote() {
+  let records: Vec<Vec<String>> = arg_input::input_csv(vec![attach_input_dir("stray_quote.csv")])
+    .unwrap()
+    .map(|record| record.unwrap())
+    .collect();
+
+  assert_eq!(records, vec![
+    record(&["monitor", "5\" screen", "x"]),
+    record(&["cable", "3 ft"])
+  ]);
+}
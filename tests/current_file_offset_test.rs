@@ -0,0 +1,69 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Read};
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_current_file_offset() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("A")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+
+  assert_eq!(all_input.current_file_offset(), 0);
+
+  let mut buf = [0; 5];
+  all_input.read_exact(&mut buf).unwrap();
+
+  assert_eq!(all_input.current_file_offset(), 5);
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir("three_lines").as_path()));
+
+  let mut rest = [0; 9];
+  all_input.read_exact(&mut rest).unwrap();
+
+  assert_eq!(all_input.current_file_offset(), 14);
+
+  let mut buf = [0; 1];
+  all_input.read_exact(&mut buf).unwrap();
+
+  assert_eq!(&buf, b"A");
+  assert_eq!(all_input.current_file_offset(), 1);
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir("A").as_path()));
+  assert_eq!(all_input.bytes_read(), 15);
+}
+
+#[test]
+fn test_current_file_offset_lines() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("three_lines")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut offsets = Vec::new();
+  let mut line = String::new();
+
+  while all_input.read_line(&mut line).unwrap() > 0 {
+    offsets.push(all_input.current_file_offset());
+    line.clear();
+  }
+
+  assert_eq!(offsets, vec![2, 4, 8, 14]);
+}
+
+#[test]
+fn test_current_file_offset_skip_and_rewind() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut buf = [0; 3];
+
+  all_input.read_exact(&mut buf).unwrap();
+  all_input.skip_current_file();
+
+  assert_eq!(all_input.current_file_offset(), 0);
+
+  all_input.rewind().unwrap();
+  all_input.read_exact(&mut buf).unwrap();
+
+  assert_eq!(all_input.current_file_offset(), 3);
+}
@@ -0,0 +1,33 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_current_filename() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir(INPUTS[0]).as_path()));
+
+  for contents in INPUTS.iter() {
+    // Each input file is exactly one letter plus a newline.
+    let mut buf = [0; 2];
+    all_input.read_exact(&mut buf).unwrap();
+
+    assert_eq!(&buf[..1], contents.as_bytes());
+    assert_eq!(all_input.current_filename(), Some(attach_input_dir(contents).as_path()));
+  }
+
+  let mut rest = Vec::new();
+  all_input.read_to_end(&mut rest).unwrap();
+
+  assert!(rest.is_empty());
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir(INPUTS[4]).as_path()));
+}
@@ -0,0 +1,65 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, scratch_dir};
+
+#[test]
+fn test_dedup() {
+  let a = attach_input_dir("A");
+  let also_a = PathBuf::from("tests/inputs/../inputs/A");
+  let filenames = vec![a.clone(), attach_input_dir("B"), also_a, a];
+
+  let mut all_input = InputBuilder::new().dedup(true).build(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+  assert_eq!(all_input.source_count(), 2);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_dedup_symlink() {
+  let dir = scratch_dir("dedup_symlink");
+  let target = dir.join("target");
+  let link = dir.join("link");
+
+  std::fs::write(&target, "target\n").unwrap();
+  std::os::unix::fs::symlink(&target, &link).unwrap();
+
+  let mut all_input = InputBuilder::new().dedup(true).build(vec![link, target]).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "target\n");
+}
+
+#[test]
+fn test_dedup_keeps_stdin() {
+  let filenames = vec![PathBuf::from("-"), attach_input_dir("A"), PathBuf::from("-")];
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let all_input = InputBuilder::new().dedup(true).build_with_stdin(filenames, stdin).unwrap();
+
+  assert_eq!(all_input.source_count(), 3);
+}
+
+#[test]
+fn test_dedup_off() {
+  let a = attach_input_dir("A");
+
+  let mut all_input = InputBuilder::new().build(vec![a.clone(), a]).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nA\n");
+}
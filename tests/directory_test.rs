@@ -0,0 +1,60 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{ErrorKind, Read};
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, scratch_dir};
+
+#[test]
+fn test_directory_fails_to_open() {
+  let dir = scratch_dir("directory_fails_to_open");
+
+  match arg_input::input(vec![attach_input_dir("A"), dir]) {
+    Ok(_) => panic!("expected the directory to be refused"),
+    Err(err) => {
+      assert_eq!(err.badfiles.len(), 1);
+      assert_eq!(err.badfiles[0].arg_index, 1);
+      assert_eq!(err.badfiles[0].inner.kind(), ErrorKind::IsADirectory);
+      assert!(err.badfiles[0].to_string().contains("is a directory"));
+    }
+  }
+}
+
+#[test]
+fn test_directory_lazy() {
+  let dir = scratch_dir("directory_lazy");
+
+  let mut all_input = arg_input::input_lazy(vec![attach_input_dir("A"), dir, attach_input_dir("B")]);
+  let mut result_string = String::new();
+
+  let err = all_input.read_to_string(&mut result_string).unwrap_err();
+
+  assert_eq!(err.kind(), ErrorKind::IsADirectory);
+  assert_eq!(result_string, "A\n");
+}
+
+#[test]
+fn test_directory_seekable() {
+  let dir = scratch_dir("directory_seekable");
+
+  match arg_input::input_seekable(vec![dir]) {
+    Ok(_) => panic!("expected the directory to be refused"),
+    Err(err) => assert_eq!(err.badfiles[0].inner.kind(), ErrorKind::IsADirectory)
+  }
+}
+
+#[test]
+fn test_directory_recursive() {
+  let dir = scratch_dir("directory_recursive");
+  std::fs::write(dir.join("inside"), "inside\n").unwrap();
+
+  let mut all_input = InputBuilder::new().recursive(true).build(vec![dir]).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "inside\n");
+}
@@ -0,0 +1,89 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+
+use arg_input::{InputBuilder, InputErrorKind};
+
+use inputs::attach_input_dir;
+
+fn stdin() -> Box<Cursor<&'static str>> {
+  Box::new(Cursor::new("from stdin\n"))
+}
+
+#[test]
+fn test_double_dash_is_left_out() {
+  let filenames = vec![attach_input_dir("A").into_os_string(), "--".into(), attach_input_dir("B").into_os_string()];
+
+  let mut all_input = InputBuilder::new().respect_double_dash(true).build_with_stdin(filenames, stdin()).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+}
+
+#[test]
+fn test_double_dash_makes_stdin_alias_a_file() {
+  let filenames = vec!["-", "--", "-"];
+
+  match InputBuilder::new().respect_double_dash(true).build_with_stdin(filenames, stdin()) {
+    Ok(_) => panic!("a `-` after `--` should be opened as a file"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles.len(), 1);
+      assert_eq!(errs.badfiles[0].arg_index, 2);
+      assert_eq!(errs.badfiles[0].filename, "-");
+    }
+  }
+}
+
+#[test]
+fn test_double_dash_stops_expansion() {
+  let filenames = vec!["--", "@./tests/inputs/list"];
+
+  match InputBuilder::new().respect_double_dash(true).response_files(true).build_with_stdin(filenames, stdin()) {
+    Ok(_) => panic!("a response file after `--` should be opened as a file"),
+    Err(errs) => assert_eq!(errs.badfiles[0].filename, "@./tests/inputs/list")
+  }
+}
+
+#[test]
+fn test_only_first_double_dash() {
+  let filenames = vec!["--", "--"];
+
+  match InputBuilder::new().respect_double_dash(true).build_with_stdin(filenames, stdin()) {
+    Ok(_) => panic!("a second `--` should be opened as a file"),
+    Err(errs) => assert_eq!(errs.badfiles[0].filename, "--")
+  }
+}
+
+#[test]
+fn test_lone_double_dash_reads_stdin() {
+  let mut all_input = InputBuilder::new().respect_double_dash(true).build_with_stdin(vec!["--"], stdin()).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "from stdin\n");
+}
+
+#[test]
+fn test_lone_double_dash_is_no_inputs() {
+  match InputBuilder::new().respect_double_dash(true).require_inputs(true).build_with_stdin(vec!["--"], stdin()) {
+    Ok(_) => panic!("a lone `--` should count as no inputs"),
+    Err(errs) => assert_eq!(errs.kind, InputErrorKind::NoInputs)
+  }
+
+  let resolved = InputBuilder::new().respect_double_dash(true).require_inputs(true).resolve(vec!["--"]);
+
+  assert_eq!(resolved.unwrap_err().kind, InputErrorKind::NoInputs);
+}
+
+#[test]
+fn test_double_dash_disabled() {
+  match InputBuilder::new().build_with_stdin(vec!["--"], stdin()) {
+    Ok(_) => panic!("`--` should be a filename by default"),
+    Err(errs) => assert_eq!(errs.badfiles[0].filename, "--")
+  }
+}
@@ -0,0 +1,108 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Read};
+use std::path::PathBuf;
+
+use arg_input::{InputBuilder, TaggedLine};
+
+use inputs::attach_input_dir;
+
+fn with_empty_files(names: &[&str]) -> Vec<PathBuf> {
+  let mut filenames = vec![attach_input_dir("empty")];
+
+  for name in names {
+    filenames.push(attach_input_dir(name));
+    filenames.push(attach_input_dir("empty"));
+  }
+
+  filenames
+}
+
+#[test]
+fn test_empty_files_contribute_nothing() {
+  let mut all_input = arg_input::input(with_empty_files(&["A", "no_newline", "B"])).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nno newlineB\n");
+}
+
+#[test]
+fn test_only_empty_files() {
+  let filenames = vec![attach_input_dir("empty"), attach_input_dir("empty")];
+
+  assert_eq!(arg_input::input_lines(filenames.clone()).unwrap().count(), 0);
+  assert_eq!(arg_input::input_paragraphs(filenames.clone()).unwrap().count(), 0);
+  assert_eq!(arg_input::input_words(filenames.clone()).unwrap().count(), 0);
+  assert_eq!(arg_input::input_lines_tagged(filenames.clone()).unwrap().count(), 0);
+  assert_eq!(arg_input::count_lines(filenames).unwrap(), 0);
+}
+
+#[test]
+fn test_empty_files_lines() {
+  let lines = arg_input::input_lines(with_empty_files(&["A", "three_lines"])).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["A", "one", "two", "three"]);
+  assert_eq!(arg_input::count_lines(with_empty_files(&["A", "three_lines"])).unwrap(), 4);
+}
+
+#[test]
+fn test_empty_files_numbered_lines() {
+  let mut lines = arg_input::input_lines_numbered(with_empty_files(&["A", "three_lines"])).unwrap();
+  let mut numbers = Vec::new();
+
+  while let Some(line) = lines.next() {
+    line.unwrap();
+    numbers.push((lines.lineno(), lines.file_lineno()));
+  }
+
+  assert_eq!(numbers, vec![(1, 1), (2, 1), (3, 2), (4, 3)]);
+}
+
+#[test]
+fn test_empty_files_tagged_lines() {
+  let lines = arg_input::input_lines_tagged(with_empty_files(&["A", "B"])).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec![
+    TaggedLine { text: "A".to_string(), is_file_end: true, path: attach_input_dir("A") },
+    TaggedLine { text: "B".to_string(), is_file_end: true, path: attach_input_dir("B") }
+  ]);
+}
+
+#[test]
+fn test_empty_files_paragraphs() {
+  let paragraphs = arg_input::input_paragraphs(with_empty_files(&["A", "B", "paragraphs1"])).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(paragraphs, vec!["A\nB", "From: a\nTo: b", "Subject: first"]);
+}
+
+#[test]
+fn test_empty_files_words() {
+  let words = arg_input::input_words(with_empty_files(&["A", "no_newline"])).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(words, vec!["A", "no", "newline"]);
+}
+
+#[test]
+fn test_empty_files_get_banners() {
+  let lines = InputBuilder::new()
+    .with_banners(|path| format!("==> {} <==", path.file_name().unwrap().to_string_lossy()))
+    .build(vec![attach_input_dir("A"), attach_input_dir("empty"), attach_input_dir("B")])
+    .unwrap()
+    .lines()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["==> A <==", "A", "==> empty <==", "==> B <==", "B"]);
+}
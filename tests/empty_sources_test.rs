@@ -0,0 +1,62 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Cursor, Read};
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_empty_sources() {
+  let filenames = vec![attach_input_dir("empty"), attach_input_dir("A"), attach_input_dir("empty"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result_string = String::new();
+
+  assert!(all_input.empty_sources().is_empty());
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(all_input.empty_sources(), vec![attach_input_dir("empty"), attach_input_dir("empty")]);
+}
+
+#[test]
+fn test_empty_sources_lines() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("empty")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut line = String::new();
+
+  all_input.read_line(&mut line).unwrap();
+  assert!(all_input.empty_sources().is_empty());
+
+  while all_input.read_line(&mut line).unwrap() > 0 {}
+  assert_eq!(all_input.empty_sources(), vec![attach_input_dir("empty")]);
+}
+
+#[test]
+fn test_empty_sources_stdin() {
+  let filenames = vec![attach_input_dir("A"), PathBuf::from("-")];
+
+  let mut all_input = InputBuilder::new().build_with_stdin(filenames, Box::new(Cursor::new(""))).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(all_input.empty_sources(), vec![PathBuf::from("-")]);
+}
+
+#[test]
+fn test_empty_sources_skipped() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("empty")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+
+  all_input.skip_current_file();
+  all_input.skip_current_file();
+
+  assert!(all_input.empty_sources().is_empty());
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "encoding")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::{Cursor, Read};
+
+use arg_input::{DecodeReader, Encoding};
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_lines_encoded_latin1() {
+  let lines: Vec<String> = arg_input::input_lines_encoded(vec![attach_input_dir("latin1")], Encoding::Latin1)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["café"]);
+}
+
+#[test]
+fn test_input_lines_encoded_across_files() {
+  let filenames = vec![attach_input_dir("utf16le_part1"), attach_input_dir("utf16le_part2")];
+
+  let lines: Vec<String> = arg_input::input_lines_encoded(filenames, Encoding::Utf16Le)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["\u{FEFF}héllo", "wörld", "😀"]);
+}
+
+#[test]
+fn test_for_bom() {
+  let utf16le = fs::read(attach_input_dir("utf16le")).unwrap();
+  let utf16be = fs::read(attach_input_dir("utf16be")).unwrap();
+
+  assert_eq!(Encoding::for_bom(&utf16le), Some((Encoding::Utf16Le, 2)));
+  assert_eq!(Encoding::for_bom(&utf16be), Some((Encoding::Utf16Be, 2)));
+  assert_eq!(Encoding::for_bom(b"\xEF\xBB\xBFtext"), Some((Encoding::Utf8, 3)));
+  assert_eq!(Encoding::for_bom(b"text"), None);
+}
+
+#[test]
+fn test_decode_reader_invalid() {
+  let mut decoded = String::new();
+
+  DecodeReader::new(Cursor::new(b"ok\xFF\xE2\x82".to_vec()), Encoding::Utf8)
+    .read_to_string(&mut decoded)
+    .unwrap();
+
+  assert_eq!(decoded, "ok\u{FFFD}\u{FFFD}");
+}
+
+#[test]
+fn test_decode_reader_odd_utf16() {
+  let mut decoded = String::new();
+
+  DecodeReader::new(Cursor::new(b"h\x00i".to_vec()), Encoding::Utf16Le)
+    .read_to_string(&mut decoded)
+    .unwrap();
+
+  assert_eq!(decoded, "h\u{FFFD}");
+}
+
+#[test]
+fn test_input_decoded_utf16() {
+  let filenames = vec![attach_input_dir("utf16le"), attach_input_dir("utf16le_part2")];
+
+  let mut decoded = arg_input::input_decoded(vec![attach_input_dir("utf16be")]).unwrap();
+  let mut result_string = String::new();
+
+  assert_eq!(decoded.encoding(), Encoding::Utf16Be);
+  decoded.read_to_string(&mut result_string).unwrap();
+  assert_eq!(result_string, "café\n");
+
+  let mut decoded = arg_input::input_decoded(filenames).unwrap();
+  let mut result_string = String::new();
+
+  decoded.read_to_string(&mut result_string).unwrap();
+  assert!(result_string.starts_with("héllo\nwörld\n😀\n"));
+}
+
+#[test]
+fn test_input_decoded_utf8() {
+  let filenames = vec![attach_input_dir("bom"), attach_input_dir("A")];
+  let expected = fs::read_to_string(attach_input_dir("bom")).unwrap();
+
+  let mut decoded = arg_input::input_decoded(filenames).unwrap();
+  let mut result_string = String::new();
+
+  assert_eq!(decoded.encoding(), Encoding::Utf8);
+  decoded.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, expected.trim_start_matches('\u{FEFF}').to_string() + "A\n");
+}
+
+#[test]
+fn test_input_decoded_only_first_file() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("utf16le")];
+
+  let decoded = arg_input::input_decoded(filenames).unwrap();
+
+  assert_eq!(decoded.encoding(), Encoding::Utf8);
+}
@@ -0,0 +1,54 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::env;
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_env_stdin_alias() {
+  env::set_var("ARG_INPUT_TEST_STDIN_ALIAS", "STDIN");
+
+  let filenames = vec![attach_input_dir("A"), "STDIN".into(), "-".into()];
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut all_input = InputBuilder::new()
+    .env_stdin_alias("ARG_INPUT_TEST_STDIN_ALIAS")
+    .build_with_stdin(filenames, stdin)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nfrom stdin\n");
+  assert_eq!(all_input.source_count(), 3);
+}
+
+#[test]
+fn test_env_stdin_alias_unset() {
+  env::remove_var("ARG_INPUT_TEST_UNSET_ALIAS");
+
+  let built = InputBuilder::new()
+    .env_stdin_alias("ARG_INPUT_TEST_UNSET_ALIAS")
+    .build(vec!["STDIN"]);
+
+  match built {
+    Ok(_) => panic!("expected STDIN to be treated as a file"),
+    Err(err) => assert_eq!(err.badfiles[0].filename, "STDIN")
+  }
+}
+
+#[test]
+fn test_env_stdin_alias_empty() {
+  env::set_var("ARG_INPUT_TEST_EMPTY_ALIAS", "");
+
+  let built = InputBuilder::new()
+    .env_stdin_alias("ARG_INPUT_TEST_EMPTY_ALIAS")
+    .build(vec![""]);
+
+  assert!(built.is_err());
+}
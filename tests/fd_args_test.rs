@@ -0,0 +1,72 @@
+#![cfg(unix)]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_fd_args() {
+  let a = File::open(attach_input_dir("A")).unwrap();
+  let b = File::open(attach_input_dir("B")).unwrap();
+
+  let filenames = vec![format!("/dev/fd/{}", a.as_raw_fd()), format!("fd:{}", b.as_raw_fd())];
+  let mut all_input = InputBuilder::new().fd_args(true).build(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+  drop(all_input);
+
+  assert_eq!(result_string, "A\nB\n");
+
+  // The original descriptors are still open.
+  let mut a = a;
+  let mut again = String::new();
+  a.seek(SeekFrom::Start(0)).unwrap();
+  a.read_to_string(&mut again).unwrap();
+
+  assert_eq!(again, "A\n");
+  assert!(b.metadata().is_ok());
+}
+
+#[test]
+fn test_fd_args_same_fd_twice() {
+  let a = File::open(attach_input_dir("three_lines")).unwrap();
+  let arg = format!("fd:{}", a.as_raw_fd());
+
+  let lines: Vec<String> = InputBuilder::new()
+    .fd_args(true)
+    .lazy(true)
+    .build(vec![arg.clone(), arg])
+    .unwrap()
+    .lines()
+    .map(|line| line.unwrap())
+    .collect();
+
+  // Both share one position, so the second gets nothing left.
+  assert_eq!(lines, vec!["one", "two", "three"]);
+  assert!(a.metadata().is_ok());
+}
+
+#[test]
+fn test_fd_args_bad_fd() {
+  match InputBuilder::new().fd_args(true).build(vec!["fd:987654"]) {
+    Ok(_) => panic!("expected the descriptor not to be open"),
+    Err(err) => assert_eq!(err.badfiles[0].filename, "fd:987654")
+  }
+}
+
+#[test]
+fn test_fd_args_off() {
+  match InputBuilder::new().build(vec!["fd:3"]) {
+    Ok(_) => panic!("expected fd:3 to be treated as a file name"),
+    Err(err) => assert_eq!(err.badfiles[0].inner.kind(), std::io::ErrorKind::NotFound)
+  }
+}
@@ -0,0 +1,118 @@
+#![cfg(unix)]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Read, Write};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use inputs::{attach_input_dir, scratch_dir};
+
+fn make_fifo(name: &str) -> PathBuf {
+  let fifo = scratch_dir(name).join("fifo");
+
+  let status = Command::new("mkfifo").arg(&fifo).status().unwrap();
+  assert!(status.success());
+
+  fifo
+}
+
+fn write_fifo(fifo: &Path, contents: &'static str) -> JoinHandle<()> {
+  let fifo = fifo.to_path_buf();
+
+  thread::spawn(move || {
+    let mut writer = OpenOptions::new().write(true).open(fifo).unwrap();
+    writer.write_all(contents.as_bytes()).unwrap();
+  })
+}
+
+#[test]
+fn test_fifo_streams() {
+  let fifo = make_fifo("fifo_streams");
+  let writer = write_fifo(&fifo, "from a pipe\n");
+
+  let mut reader = arg_input::input(vec![attach_input_dir("A"), fifo]).unwrap();
+  let mut contents = String::new();
+  reader.read_to_string(&mut contents).unwrap();
+  writer.join().unwrap();
+
+  assert_eq!(contents, "A\nfrom a pipe\n");
+}
+
+#[test]
+fn test_fifo_has_no_len() {
+  let fifo = make_fifo("fifo_has_no_len");
+  let writer = write_fifo(&fifo, "from a pipe\n");
+
+  let (mut reader, info) = arg_input::input_with_metadata(vec![&fifo]).unwrap();
+  let mut contents = String::new();
+  reader.read_to_string(&mut contents).unwrap();
+  writer.join().unwrap();
+
+  assert_eq!(info[0].len, None);
+  assert!(!info[0].is_stdin);
+  assert_eq!(contents, "from a pipe\n");
+}
+
+#[test]
+fn test_fifo_to_string() {
+  let fifo = make_fifo("fifo_to_string");
+  let writer = write_fifo(&fifo, "from a pipe\n");
+
+  let contents = arg_input::input_to_string(vec![&fifo]).unwrap();
+  writer.join().unwrap();
+
+  assert_eq!(contents, "from a pipe\n");
+}
+
+#[test]
+fn test_fifo_not_seekable() {
+  // With no writer, this would wait forever if it tried to open the pipe.
+  let fifo = make_fifo("fifo_not_seekable");
+
+  match arg_input::input_seekable(vec![&fifo]) {
+    Ok(_) => panic!("expected a named pipe to be refused"),
+    Err(err) => assert_eq!(err.badfiles[0].inner.kind(), std::io::ErrorKind::InvalidInput)
+  }
+}
+
+#[test]
+fn test_fifo_tail() {
+  let fifo = make_fifo("fifo_tail");
+  let writer = write_fifo(&fifo, "one\ntwo\nthree\n");
+
+  let tail = arg_input::input_tail(vec![attach_input_dir("A"), fifo], 2).unwrap();
+  writer.join().unwrap();
+
+  assert_eq!(tail, vec!["two", "three"]);
+}
+
+#[test]
+fn test_fifo_opened_once_read() {
+  let fifo = make_fifo("fifo_opened_once_read");
+  let (sender, receiver) = mpsc::channel();
+
+  let building = fifo.clone();
+  thread::spawn(move || {
+    sender.send(arg_input::input(vec![attach_input_dir("A"), building])).unwrap();
+  });
+
+  // With no writer, this would wait forever if it opened the pipe up front.
+  let mut reader = receiver.recv_timeout(Duration::from_secs(10)).expect("opening a pipe with no writer hung").unwrap();
+  let mut first = [0; 2];
+  reader.read_exact(&mut first).unwrap();
+
+  let writer = write_fifo(&fifo, "from a pipe\n");
+  let mut contents = String::new();
+  reader.read_to_string(&mut contents).unwrap();
+  writer.join().unwrap();
+
+  assert_eq!(&first, b"A\n");
+  assert_eq!(contents, "from a pipe\n");
+}
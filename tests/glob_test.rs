@@ -0,0 +1,125 @@
+#![cfg(feature = "glob")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, scratch_dir};
+
+#[test]
+fn test_input_globbed() {
+  let filenames = vec![attach_input_dir("[CA]"), attach_input_dir("?")];
+
+  let mut all_input = arg_input::input_globbed(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nC\nA\nB\nC\nD\nE\n");
+}
+
+#[test]
+fn test_input_globbed_star() {
+  let filenames = vec![attach_input_dir("nul_sep*")];
+
+  let all_input = arg_input::input_globbed(filenames).unwrap();
+
+  assert_eq!(all_input.source_count(), 2);
+}
+
+#[test]
+fn test_input_globbed_negated_class() {
+  let filenames = vec![attach_input_dir("[!A-C]")];
+
+  let mut all_input = arg_input::input_globbed(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "D\nE\n");
+}
+
+#[test]
+fn test_input_globbed_directories() {
+  let filenames = vec!["tests/inp*/B"];
+
+  let mut all_input = arg_input::input_globbed(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "B\n");
+}
+
+#[test]
+fn test_input_globbed_no_matches() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("*.nothing")];
+
+  match arg_input::input_globbed(filenames) {
+    Ok(_) => panic!("a pattern matching nothing should be an error"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles.len(), 1);
+      assert_eq!(errs.badfiles[0].arg_index, 1);
+      assert_eq!(errs.badfiles[0].inner.kind(), std::io::ErrorKind::NotFound);
+    }
+  }
+}
+
+#[test]
+fn test_input_globbed_lazy() {
+  let filenames = vec![attach_input_dir("*.nothing"), attach_input_dir("[AB]")];
+
+  let mut all_input = InputBuilder::new()
+    .glob(true)
+    .lazy(true)
+    .build(filenames)
+    .unwrap();
+  let mut result_string = String::new();
+
+  assert!(all_input.read_to_string(&mut result_string).is_err());
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+}
+
+#[test]
+fn test_input_globbed_many_stars() {
+  let dir = scratch_dir("glob_many_stars");
+  let name = "a".repeat(40);
+  fs::write(dir.join(&name), "a\n").unwrap();
+
+  let start = Instant::now();
+
+  assert!(arg_input::input_globbed(vec![dir.join("*a*a*a*a*a*a*a*a*a*a*a*b")]).is_err());
+  assert_eq!(arg_input::input_globbed(vec![dir.join("*a*a*a*a*a*a*a*a*a*a*a")]).unwrap().source_count(), 1);
+  assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_input_globbed_lazy_keeps_error() {
+  let dir = scratch_dir("glob_lazy_keeps_error");
+  fs::write(dir.join("[x]"), "wrong file\n").unwrap();
+
+  let mut all_input = InputBuilder::new()
+    .glob(true)
+    .lazy(true)
+    .build(vec![dir.join("[x]"), attach_input_dir("A")])
+    .unwrap();
+  let mut result_string = String::new();
+
+  let err = all_input.read_to_string(&mut result_string).unwrap_err();
+
+  assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+  assert!(err.to_string().contains("no files match this pattern"));
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\n");
+}
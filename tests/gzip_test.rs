@@ -0,0 +1,157 @@
+#![cfg(feature = "gzip")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::{Cursor, Read};
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_gzip() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("compressed.gz"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\ncompressed\nB\n");
+}
+
+#[test]
+fn test_gzip_large() {
+  let filenames = vec![attach_input_dir("big.gz")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, fs::read(attach_input_dir("big")).unwrap());
+}
+
+#[test]
+fn test_gzip_multiple_members() {
+  let filenames = vec![attach_input_dir("multi.gz")];
+
+  let mut all_input = arg_input::input_lazy(filenames);
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "first\nsecond\n");
+}
+
+#[test]
+fn test_gzip_stdin() {
+  let compressed = fs::read(attach_input_dir("compressed.gz")).unwrap();
+
+  let raw = arg_input::input_with_stdin(["-"], Box::new(Cursor::new(compressed.clone())));
+  let mut result = Vec::new();
+
+  raw.unwrap().read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, compressed);
+
+  let decompressed = InputBuilder::new()
+    .gzip_stdin(true)
+    .build_with_stdin(["-"], Box::new(Cursor::new(compressed)));
+  let mut result_string = String::new();
+
+  decompressed.unwrap().read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\n");
+}
+
+#[test]
+fn test_gzip_zero_padding() {
+  let mut padded = fs::read(attach_input_dir("compressed.gz")).unwrap();
+  padded.extend_from_slice(&[0; 9000]);
+
+  let mut all_input = InputBuilder::new()
+    .gzip_stdin(true)
+    .build_with_stdin(["-"], Box::new(Cursor::new(padded.clone())))
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\n");
+
+  padded.push(1);
+
+  let mut all_input = InputBuilder::new()
+    .gzip_stdin(true)
+    .build_with_stdin(["-"], Box::new(Cursor::new(padded)))
+    .unwrap();
+
+  assert!(all_input.read_to_string(&mut String::new()).is_err());
+}
+
+#[test]
+fn test_gzip_tail() {
+  let compressed = arg_input::input_tail(vec![attach_input_dir("big.gz")], 3).unwrap();
+  let plain = arg_input::input_tail(vec![attach_input_dir("big")], 3).unwrap();
+
+  assert_eq!(compressed, plain);
+}
+
+#[test]
+fn test_gzip_no_progress() {
+  let all_input = arg_input::input(vec![attach_input_dir("A"), attach_input_dir("compressed.gz")]).unwrap();
+
+  assert_eq!(all_input.progress(), None);
+}
+
+#[test]
+fn test_auto_decompress() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("compressed_renamed"), attach_input_dir("compressed.gz")];
+
+  let mut all_input = InputBuilder::new().auto_decompress(true).build(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\ncompressed\ncompressed\n");
+}
+
+#[test]
+fn test_auto_decompress_off() {
+  let filenames = vec![attach_input_dir("compressed_renamed")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, fs::read(attach_input_dir("compressed.gz")).unwrap());
+}
+
+#[test]
+fn test_auto_decompress_stdin() {
+  let stdin = fs::read(attach_input_dir("compressed.gz")).unwrap();
+
+  let mut all_input = InputBuilder::new().auto_decompress(true).build_with_stdin(vec!["-"], Box::new(Cursor::new(stdin))).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\n");
+}
+
+#[test]
+fn test_auto_decompress_short_files() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("empty"), attach_input_dir("B")];
+
+  let mut all_input = InputBuilder::new().auto_decompress(true).build(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "http")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use inputs::attach_input_dir;
+
+/// Serve each of the given responses to one request, in order, and give back
+/// the address to connect to.
+fn serve<S: AsRef<str> + Send + 'static>(responses: Vec<S>) -> String {
+  serve_logged(responses).0
+}
+
+/// Like `serve()`, but also give back the request line of each request, as
+/// it comes in.
+fn serve_logged<S: AsRef<str> + Send + 'static>(responses: Vec<S>) -> (String, Receiver<String>) {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let addr = listener.local_addr().unwrap();
+  let (requests, logged) = mpsc::channel();
+
+  thread::spawn(move || {
+    for response in responses {
+      let (stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream);
+      let mut request = None;
+
+      loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+
+        if line == "\r\n" || line.is_empty() {
+          break;
+        }
+
+        request.get_or_insert_with(|| line.trim_end().to_string());
+      }
+
+      let _ = requests.send(request.unwrap_or_default());
+      reader.get_mut().write_all(response.as_ref().as_bytes()).unwrap();
+    }
+  });
+
+  (format!("http://{}", addr), logged)
+}
+
+#[test]
+fn test_http() {
+  let server = serve(vec!["HTTP/1.0 200 OK\r\nContent-Length: 7\r\n\r\nremote\nignored"]);
+
+  let mut all_input = arg_input::input(vec![attach_input_dir("A").to_str().unwrap().to_string(), format!("{}/remote.log", server)]).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nremote\n");
+}
+
+#[test]
+fn test_http_short_body() {
+  let server = serve(vec!["HTTP/1.0 200 OK\r\nContent-Length: 10\r\n\r\nshort"]);
+
+  let mut all_input = arg_input::input(vec![format!("{}/short", server)]).unwrap();
+  let mut result_string = String::new();
+
+  let err = all_input.read_to_string(&mut result_string).unwrap_err();
+
+  assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_http_chunked_redirect() {
+  let server = serve(vec![
+    "HTTP/1.1 302 Found\r\nLocation: /moved\r\n\r\n",
+    "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\none\n\r\n4\r\ntwo\n\r\n0\r\n\r\n"
+  ]);
+
+  let contents = arg_input::input_to_string(vec![format!("{}/start", server)]).unwrap();
+
+  assert_eq!(contents, "one\ntwo\n");
+}
+
+#[test]
+fn test_http_relative_redirect() {
+  let (server, requests) = serve_logged(vec![
+    "HTTP/1.1 302 Found\r\nLocation: next.txt\r\n\r\n",
+    "HTTP/1.0 200 OK\r\nContent-Length: 5\r\n\r\nnext\n"
+  ]);
+
+  let contents = arg_input::input_to_string(vec![format!("{}/dir/start?from=1", server)]).unwrap();
+
+  assert_eq!(contents, "next\n");
+  assert_eq!(requests.recv().unwrap(), "GET /dir/start?from=1 HTTP/1.0");
+  assert_eq!(requests.recv().unwrap(), "GET /dir/next.txt HTTP/1.0");
+}
+
+#[test]
+fn test_http_scheme_relative_redirect() {
+  let elsewhere = serve(vec!["HTTP/1.0 200 OK\r\nContent-Length: 10\r\n\r\nelsewhere\n"]);
+  let location = format!("HTTP/1.1 301 Moved Permanently\r\nLocation: {}/file\r\n\r\n", elsewhere.strip_prefix("http:").unwrap());
+  let server = serve(vec![location]);
+
+  let contents = arg_input::input_to_string(vec![format!("{}/start", server)]).unwrap();
+
+  assert_eq!(contents, "elsewhere\n");
+}
+
+#[test]
+fn test_http_not_found() {
+  let server = serve(vec!["HTTP/1.0 404 Not Found\r\n\r\n"]);
+  let url = format!("{}/missing?version=2", server);
+
+  match arg_input::input(vec![&url]) {
+    Ok(_) => panic!("expected the request to fail"),
+    Err(err) => {
+      assert_eq!(err.badfiles[0].filename, url);
+      assert_eq!(err.badfiles[0].inner.kind(), std::io::ErrorKind::NotFound);
+    }
+  }
+}
+
+#[test]
+fn test_https_unsupported() {
+  match arg_input::input(vec!["https://example.com/file"]) {
+    Ok(_) => panic!("expected https to be refused"),
+    Err(err) => assert_eq!(err.badfiles[0].inner.kind(), std::io::ErrorKind::Unsupported)
+  }
+}
@@ -0,0 +1,54 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_any_filtered() {
+  let filenames = INPUTS.iter()
+    .filter(|&&name| name != "C")
+    .map(|name| attach_input_dir(name));
+
+  let mut all_input = arg_input::input_any(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\nD\nE\n");
+}
+
+#[test]
+fn test_input_any_missing() {
+  let filenames = INPUTS.iter().chain(NONEXISTENT.iter())
+    .filter(|&&name| name != "A")
+    .map(|name| attach_input_dir(name));
+
+  match arg_input::input_any(filenames) {
+    Ok(_) => panic!("missing files should be errors"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles.len(), 3);
+      assert_eq!(errs.badfiles[0].arg_index, 4);
+      assert_eq!(errs.summary(), "failed to read 3 of 7 inputs");
+    }
+  }
+}
+
+#[test]
+fn test_input_any_empty_requires_inputs() {
+  let filenames = INPUTS.iter().filter(|_| false).map(PathBuf::from);
+
+  assert!(InputBuilder::new().require_inputs(true).build_any(filenames).is_err());
+}
+
+#[test]
+fn test_input_any_lone_double_dash() {
+  let filenames = vec!["--"].into_iter().filter(|_| true);
+
+  assert!(InputBuilder::new().respect_double_dash(true).require_inputs(true).build_any(filenames).is_err());
+}
@@ -0,0 +1,135 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_builder_skip_missing() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("Z"), attach_input_dir("B")];
+
+  let mut all_input = InputBuilder::new()
+    .skip_missing(true)
+    .build(filenames)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+}
+
+#[test]
+fn test_input_builder_skip_all_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = InputBuilder::new()
+    .skip_missing(true)
+    .build(filenames)
+    .unwrap();
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert!(result.is_empty());
+}
+
+#[test]
+fn test_input_builder_stdin_alias() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("-")];
+
+  // With a different alias, "-" is just a (missing) file.
+  let all_input = InputBuilder::new()
+    .stdin_alias("STDIN")
+    .build(filenames);
+
+  match all_input {
+    Ok(_) => panic!("build() should have tried to open ./tests/inputs/-"),
+    Err(errs) => assert_eq!(errs.badfiles.len(), 1)
+  }
+}
+
+#[test]
+fn test_input_builder_buffer_capacity() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("A")];
+
+  let mut all_input = InputBuilder::new()
+    .buffer_capacity(1)
+    .build(filenames)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "one\ntwo\nthree\nA\n");
+}
+
+#[test]
+fn test_input_builder_stdin_aliases() {
+  let filenames = vec!["STDIN", "-"];
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut all_input = InputBuilder::new()
+    .stdin_aliases(["STDIN", "-"])
+    .build_with_stdin(filenames, stdin)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "from stdin\n");
+}
+
+#[test]
+fn test_input_builder_dev_stdin() {
+  let filenames = vec![attach_input_dir("A"), "/dev/stdin".into(), "/dev/fd/0".into()];
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut all_input = InputBuilder::new()
+    .dev_stdin(true)
+    .build_with_stdin(filenames, stdin)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nfrom stdin\n");
+  assert_eq!(all_input.current_filename(), None);
+}
+
+#[test]
+fn test_input_builder_require_inputs() {
+  use std::path::PathBuf;
+
+  use arg_input::InputErrorKind;
+
+  let builder = InputBuilder::new().require_inputs(true);
+
+  match builder.build(Vec::<PathBuf>::new()) {
+    Ok(_) => panic!("expected no inputs to be refused"),
+    Err(err) => {
+      assert_eq!(err.kind, InputErrorKind::NoInputs);
+      assert!(err.badfiles.is_empty());
+    }
+  }
+
+  match builder.clone().lazy(true).build(Vec::<PathBuf>::new()) {
+    Ok(_) => panic!("expected no inputs to be refused"),
+    Err(err) => assert_eq!(err.kind, InputErrorKind::NoInputs)
+  }
+
+  // An explicit `-` still reads from stdin.
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+  let mut all_input = builder.build_with_stdin(vec![attach_input_dir("A"), "-".into()], stdin).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nfrom stdin\n");
+}
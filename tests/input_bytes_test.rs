@@ -0,0 +1,19 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_input_bytes() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let bytes: Vec<u8> = arg_input::input_bytes(filenames)
+    .unwrap()
+    .map(|byte| byte.unwrap())
+    .collect();
+
+  assert_eq!(bytes, b"A\nB\nC\nD\nE\n".to_vec());
+}
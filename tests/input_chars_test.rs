@@ -0,0 +1,41 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::CharsError;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_chars_across_files() {
+  let filenames = vec![attach_input_dir("split_char1"), attach_input_dir("split_char2")];
+
+  let chars: String = arg_input::input_chars(filenames)
+    .unwrap()
+    .map(|c| c.unwrap())
+    .collect();
+
+  assert_eq!(chars, "aü€\n");
+}
+
+#[test]
+fn test_input_chars_invalid() {
+  let filenames = vec![attach_input_dir("bad_utf8")];
+
+  let chars: Vec<Result<char, CharsError>> = arg_input::input_chars(filenames)
+    .unwrap()
+    .collect();
+
+  assert_eq!(chars.len(), 5);
+  assert_eq!(chars[0].as_ref().unwrap(), &'x');
+  match chars[1] {
+    Err(CharsError::NotUtf8(ref bytes)) => assert_eq!(bytes, &[0xFF]),
+    _ => panic!("0xFF should not have decoded")
+  }
+  assert_eq!(chars[2].as_ref().unwrap(), &'y');
+  match chars[3] {
+    Err(CharsError::NotUtf8(ref bytes)) => assert_eq!(bytes, &[0xE2, 0x82]),
+    _ => panic!("truncated sequence should not have decoded")
+  }
+  assert_eq!(chars[4].as_ref().unwrap(), &'z');
+}
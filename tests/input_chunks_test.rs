@@ -0,0 +1,59 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_chunks() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let chunks: Vec<Vec<u8>> = arg_input::input_chunks(filenames, 3)
+    .unwrap()
+    .map(|chunk| chunk.unwrap())
+    .collect();
+
+  // Blocks cross file boundaries; only the last one is short.
+  assert_eq!(chunks, vec![b"A\nB".to_vec(), b"\nC\n".to_vec(), b"D\nE".to_vec(), b"\n".to_vec()]);
+}
+
+#[test]
+fn test_input_chunks_big() {
+  let filenames = vec![attach_input_dir("big"), attach_input_dir("big")];
+  let mut expected = fs::read(attach_input_dir("big")).unwrap();
+  expected.extend(expected.clone());
+
+  let chunks: Vec<Vec<u8>> = arg_input::input_chunks(filenames, 1000)
+    .unwrap()
+    .map(|chunk| chunk.unwrap())
+    .collect();
+
+  assert!(chunks[..chunks.len() - 1].iter().all(|chunk| chunk.len() == 1000));
+  assert_eq!(chunks.concat(), expected);
+}
+
+#[test]
+fn test_input_chunks_empty() {
+  let mut chunks = arg_input::input_chunks(vec![attach_input_dir("empty")], 16).unwrap();
+
+  assert!(chunks.next().is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_input_chunks_zero() {
+  let _ = arg_input::input_chunks(vec![attach_input_dir("A")], 0);
+}
+
+#[test]
+fn test_input_chunks_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  assert!(arg_input::input_chunks(filenames, 16).is_err());
+}
@@ -0,0 +1,61 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{self, Write};
+use std::fs;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+/// FNV-1a, as a stand-in for a real hash function.
+struct Fnv(u64);
+
+impl Write for Fnv {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    for &byte in buf {
+      self.0 = (self.0 ^ byte as u64).wrapping_mul(0x100000001b3);
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+fn fnv(bytes: &[u8]) -> u64 {
+  let mut hasher = Fnv(0xcbf29ce484222325);
+  hasher.write_all(bytes).unwrap();
+  hasher.0
+}
+
+#[test]
+fn test_input_digest() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let hasher = arg_input::input_digest(filenames, Fnv(0xcbf29ce484222325)).unwrap();
+
+  assert_eq!(hasher.0, fnv(b"A\nB\nC\nD\nE\n"));
+}
+
+#[test]
+fn test_input_digest_big() {
+  let filenames = vec![attach_input_dir("big"), attach_input_dir("three_lines")];
+  let mut expected = fs::read(attach_input_dir("big")).unwrap();
+  expected.extend(fs::read(attach_input_dir("three_lines")).unwrap());
+
+  let hashed = arg_input::input_digest(filenames, Vec::new()).unwrap();
+
+  assert_eq!(hashed, expected);
+}
+
+#[test]
+fn test_input_digest_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  assert!(arg_input::input_digest(filenames, Vec::new()).is_err());
+}
@@ -0,0 +1,49 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_error_summary() {
+  let mut filenames = vec![attach_input_dir("A")];
+  filenames.extend(NONEXISTENT.iter().map(|str| attach_input_dir(str)));
+
+  match arg_input::input(filenames) {
+    Ok(_) => panic!("expected the files to be missing"),
+    Err(err) => {
+      assert_eq!(err.summary(), "failed to read 3 of 4 inputs");
+
+      let message = err.to_string();
+      let lines: Vec<&str> = message.lines().collect();
+
+      assert_eq!(lines.len(), 4);
+      assert_eq!(lines[0], "failed to read 3 of 4 inputs:");
+      assert!(lines[1].starts_with(&format!("  #2 ({}): ", attach_input_dir("Z").display())));
+      assert!(lines[3].starts_with("  #4 "));
+    }
+  }
+}
+
+#[test]
+fn test_input_error_summary_single() {
+  match arg_input::input(vec![attach_input_dir("Z")]) {
+    Ok(_) => panic!("expected the file to be missing"),
+    Err(err) => assert_eq!(err.summary(), "failed to read 1 of 1 input")
+  }
+}
+
+#[test]
+fn test_input_error_summary_no_inputs() {
+  match InputBuilder::new().require_inputs(true).build(Vec::<PathBuf>::new()) {
+    Ok(_) => panic!("expected no inputs to be refused"),
+    Err(err) => {
+      assert_eq!(err.summary(), "no input files given");
+      assert_eq!(err.to_string(), "no input files given\n");
+    }
+  }
+}
@@ -0,0 +1,55 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use arg_input::InputFile;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_files() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let files = arg_input::input_files(filenames).unwrap();
+  let mut all = String::new();
+
+  for (file, name) in files.into_iter().zip(INPUTS.iter()) {
+    match file {
+      InputFile::File(path, mut file) => {
+        assert_eq!(path, attach_input_dir(name));
+        file.read_to_string(&mut all).unwrap();
+      },
+      InputFile::Stdin => panic!("expected a file")
+    }
+  }
+
+  assert_eq!(all, "A\nB\nC\nD\nE\n");
+}
+
+#[test]
+fn test_input_files_stdin() {
+  let files = arg_input::input_files(vec![attach_input_dir("A").to_str().unwrap(), "-"]).unwrap();
+
+  assert_eq!(files.len(), 2);
+  assert!(matches!(files[1], InputFile::Stdin));
+
+  let files = arg_input::input_files(Vec::<&str>::new()).unwrap();
+
+  assert!(matches!(files[..], [InputFile::Stdin]));
+}
+
+#[test]
+fn test_input_files_nonexistent() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let err = arg_input::input_files(filenames).unwrap_err();
+
+  assert_eq!(err.badfiles.len(), NONEXISTENT.len());
+  assert_eq!(err.badfiles[1].arg_index, 1);
+}
@@ -0,0 +1,34 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::InputReadError;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_for_each_line() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("crlf"), attach_input_dir("no_newline")];
+
+  let mut lines = Vec::new();
+  arg_input::input_for_each_line(filenames.clone(), |line| lines.push(line.to_string())).unwrap();
+
+  let expected: Vec<String> = arg_input::input_lines(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, expected);
+}
+
+#[test]
+fn test_input_for_each_line_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match arg_input::input_for_each_line(filenames, |_| panic!("there shouldn't be any lines")) {
+    Err(InputReadError::Input(err)) => assert_eq!(err.badfiles.len(), NONEXISTENT.len()),
+    other => panic!("expected an InputError, got {:?}", other)
+  }
+}
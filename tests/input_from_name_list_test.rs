@@ -0,0 +1,85 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::{Cursor, Read};
+
+use arg_input::{InputBuilder, InputReadError};
+
+use inputs::scratch_dir;
+
+#[test]
+fn test_input_from_name_list() {
+  let names = Cursor::new(b"./tests/inputs/B\0./tests/inputs/A\0".to_vec());
+
+  let mut all_input = arg_input::input_from_name_list(names).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "B\nA\n");
+}
+
+#[test]
+fn test_input_from_name_list_odd_names() {
+  let dir = scratch_dir("name_list");
+  let odd = dir.join("with space\nand newline");
+
+  fs::write(&odd, "odd\n").unwrap();
+
+  let mut names = odd.into_os_string().into_string().unwrap().into_bytes();
+  names.extend_from_slice(b"\0./tests/inputs/A");
+
+  let mut all_input = arg_input::input_from_name_list(Cursor::new(names)).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "odd\nA\n");
+}
+
+#[test]
+fn test_input_from_name_list_empty() {
+  let mut all_input = arg_input::input_from_name_list(Cursor::new(Vec::new())).unwrap();
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert!(result.is_empty());
+}
+
+#[test]
+fn test_input_from_name_list_missing() {
+  let names = Cursor::new(b"./tests/inputs/Z\0./tests/inputs/A\0".to_vec());
+
+  match arg_input::input_from_name_list(names) {
+    Err(InputReadError::Input(err)) => assert_eq!(err.badfiles.len(), 1),
+    other => panic!("expected an InputError, got {:?}", other.map(|_| ()))
+  }
+}
+
+#[test]
+fn test_input_from_name_list_literal_names() {
+  let dir = scratch_dir("name_list_literal");
+  let list = dir.join("list");
+
+  fs::write(&list, "./tests/inputs/A\n").unwrap();
+
+  // Neither `-` nor `@list` is a file here, so both should fail to open,
+  // rather than reading `stdin` or the list.
+  let mut names = b"-\0@".to_vec();
+  names.extend_from_slice(list.into_os_string().into_string().unwrap().as_bytes());
+
+  let built = InputBuilder::new()
+    .response_files(true)
+    .build_from_name_list(Cursor::new(names));
+
+  match built {
+    Err(InputReadError::Input(err)) => {
+      assert_eq!(err.badfiles.len(), 2);
+      assert_eq!(err.badfiles[0].filename, "-");
+    },
+    other => panic!("expected an InputError, got {:?}", other.map(|_| ()))
+  }
+}
@@ -0,0 +1,57 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Read};
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_head_bytes_per_file() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("words1")];
+
+  let mut all_input = arg_input::input_head_bytes_per_file(filenames, 4).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "one\n  th");
+}
+
+#[test]
+fn test_input_head_bytes_per_file_short_files() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("empty"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input_head_bytes_per_file(filenames, 100).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+}
+
+#[test]
+fn test_input_head_bytes_per_file_lines() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("three_lines")];
+
+  let all_input = arg_input::input_head_bytes_per_file(filenames, 6).unwrap();
+  let lines = all_input.lines().map(Result::unwrap).collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["one", "twone", "tw"]);
+}
+
+#[test]
+fn test_input_head_bytes_per_file_rewind() {
+  let filenames = vec![attach_input_dir("three_lines")];
+
+  let mut all_input = arg_input::input_head_bytes_per_file(filenames, 4).unwrap();
+  let mut first = String::new();
+  let mut second = String::new();
+
+  all_input.read_to_string(&mut first).unwrap();
+  all_input.rewind().unwrap();
+  all_input.read_to_string(&mut second).unwrap();
+
+  assert_eq!(first, "one\n");
+  assert_eq!(second, "one\n");
+}
@@ -0,0 +1,52 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::BufRead;
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_head() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("A")];
+
+  let lines: Vec<String> = arg_input::input_head(filenames, 4)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["one", "two", "three", "A"]);
+}
+
+#[test]
+fn test_input_head_short_input() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let lines: Vec<String> = arg_input::input_head(filenames, 100)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines.len(), INPUTS.len());
+}
+
+#[test]
+fn test_head_lazy_never_opens_rest() {
+  let mut filenames = vec![attach_input_dir("three_lines")];
+  filenames.extend(NONEXISTENT.iter().map(|str| attach_input_dir(str)));
+
+  let lines: Vec<String> = InputBuilder::new()
+    .lazy(true)
+    .build(filenames)
+    .unwrap()
+    .lines()
+    .take(3)
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["one", "two", "three"]);
+}
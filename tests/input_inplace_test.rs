@@ -0,0 +1,125 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::Write;
+
+use inputs::scratch_dir;
+
+#[test]
+fn test_input_inplace() {
+  let dir = scratch_dir("inplace");
+  let first = dir.join("first");
+  let second = dir.join("second");
+
+  fs::write(&first, "one\ntwo\n").unwrap();
+  fs::write(&second, "three\n").unwrap();
+
+  let mut editor = arg_input::input_inplace(vec![&first, &second]).unwrap();
+
+  while let Some(line) = editor.next() {
+    let line = line.unwrap();
+    writeln!(editor, "{}", line.to_uppercase()).unwrap();
+  }
+
+  assert_eq!(fs::read_to_string(&first).unwrap(), "ONE\nTWO\n");
+  assert_eq!(fs::read_to_string(&second).unwrap(), "THREE\n");
+
+  // No temporary files should be left behind.
+  assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+}
+
+#[test]
+fn test_input_inplace_abandoned() {
+  let dir = scratch_dir("inplace_abandoned");
+  let file = dir.join("file");
+
+  fs::write(&file, "one\ntwo\n").unwrap();
+
+  {
+    let mut editor = arg_input::input_inplace(vec![&file]).unwrap();
+    let line = editor.next().unwrap().unwrap();
+    writeln!(editor, "{}", line.to_uppercase()).unwrap();
+  }
+
+  assert_eq!(fs::read_to_string(&file).unwrap(), "one\ntwo\n");
+  assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
+
+#[test]
+fn test_input_inplace_finish() {
+  let dir = scratch_dir("inplace_finish");
+  let first = dir.join("first");
+  let second = dir.join("second");
+
+  fs::write(&first, "one\ntwo\n").unwrap();
+  fs::write(&second, "three\n").unwrap();
+
+  let mut editor = arg_input::input_inplace(vec![&first, &second]).unwrap();
+  let line = editor.next().unwrap().unwrap();
+  writeln!(editor, "{}", line.to_uppercase()).unwrap();
+  editor.finish().unwrap();
+
+  assert_eq!(fs::read_to_string(&first).unwrap(), "ONE\n");
+  assert_eq!(fs::read_to_string(&second).unwrap(), "three\n");
+}
+
+#[test]
+fn test_input_inplace_backup() {
+  let dir = scratch_dir("inplace_backup");
+  let file = dir.join("file.txt");
+
+  fs::create_dir(dir.join("old")).unwrap();
+
+  for suffix in &[".bak", "orig_*", "old/*"] {
+    fs::write(&file, "text\n").unwrap();
+
+    let mut editor = arg_input::input_inplace(vec![&file]).unwrap().backup_suffix(suffix);
+
+    while let Some(line) = editor.next() {
+      let line = line.unwrap();
+      writeln!(editor, "{}", line.to_uppercase()).unwrap();
+    }
+
+    assert_eq!(fs::read_to_string(&file).unwrap(), "TEXT\n");
+  }
+
+  assert_eq!(fs::read_to_string(dir.join("file.txt.bak")).unwrap(), "text\n");
+  assert_eq!(fs::read_to_string(dir.join("orig_file.txt")).unwrap(), "text\n");
+  assert_eq!(fs::read_to_string(dir.join("old").join("file.txt")).unwrap(), "text\n");
+}
+
+#[test]
+fn test_input_inplace_no_backup() {
+  let dir = scratch_dir("inplace_no_backup");
+  let file = dir.join("file.txt");
+
+  fs::write(&file, "text\n").unwrap();
+
+  let editor = arg_input::input_inplace(vec![&file]).unwrap().backup_suffix("");
+
+  for line in editor {
+    line.unwrap();
+  }
+
+  assert_eq!(fs::read_to_string(&file).unwrap(), "");
+  assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn test_input_inplace_compressed() {
+  let dir = scratch_dir("inplace_compressed");
+  let file = dir.join("compressed.gz");
+
+  fs::copy(inputs::attach_input_dir("compressed.gz"), &file).unwrap();
+
+  match arg_input::input_inplace(vec![&file]) {
+    Ok(_) => panic!("input_inplace() should have refused to edit a compressed file"),
+    Err(err) => assert_eq!(err.badfiles.len(), 1)
+  }
+
+  assert_eq!(fs::read(&file).unwrap(), fs::read(inputs::attach_input_dir("compressed.gz")).unwrap());
+  assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
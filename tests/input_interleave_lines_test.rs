@@ -0,0 +1,51 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_interleave_lines() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("A"), attach_input_dir("three_lines")];
+
+  let lines = arg_input::input_interleave_lines(filenames).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["one", "A", "one", "two", "two", "three", "three"]);
+}
+
+#[test]
+fn test_input_interleave_lines_empty_files() {
+  let filenames = vec![attach_input_dir("empty"), attach_input_dir("A"), attach_input_dir("empty"), attach_input_dir("B")];
+
+  let lines = arg_input::input_interleave_lines(filenames).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["A", "B"]);
+}
+
+#[test]
+fn test_input_interleave_lines_no_newline() {
+  let filenames = vec![attach_input_dir("no_newline"), attach_input_dir("B")];
+
+  let lines = arg_input::input_interleave_lines(filenames).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["no newline", "B"]);
+}
+
+#[test]
+fn test_input_interleave_lines_missing_file() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir(NONEXISTENT[0])];
+
+  match arg_input::input_interleave_lines(filenames) {
+    Ok(_) => panic!("a missing file should be an error"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles.len(), 1);
+      assert_eq!(errs.badfiles[0].arg_index, 1);
+    }
+  }
+}
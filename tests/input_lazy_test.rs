@@ -0,0 +1,48 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{ErrorKind, Read};
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_input_lazy() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input_lazy(filenames);
+  let mut result_string = String::new();
+
+  let mut comparison_string = String::new();
+
+  for contents in INPUTS.iter() {
+    comparison_string += contents;
+    comparison_string += "\n";
+  }
+
+  let result = all_input.read_to_string(&mut result_string);
+
+  assert!(result.is_ok());
+  assert_eq!(result_string, comparison_string);
+}
+
+#[test]
+fn test_input_lazy_nonexistent() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("Z"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input_lazy(filenames);
+  let mut result_string = String::new();
+
+  let err = all_input.read_to_string(&mut result_string).unwrap_err();
+
+  assert_eq!(err.kind(), ErrorKind::NotFound);
+  assert_eq!(result_string, "A\n");
+
+  // Reading again carries on past the bad file.
+  let result = all_input.read_to_string(&mut result_string);
+
+  assert!(result.is_ok());
+  assert_eq!(result_string, "A\nB\n");
+}
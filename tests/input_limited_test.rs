@@ -0,0 +1,36 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_input_limited_across_files() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input_limited(filenames, 5).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\nC");
+  assert_eq!(all_input.get_ref().current_filename(), Some(attach_input_dir("C").as_path()));
+}
+
+#[test]
+fn test_input_limited_past_end() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input_limited(filenames, 1000).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\nC\nD\nE\n");
+}
@@ -0,0 +1,34 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_input_lines_filtered() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("crlf")];
+
+  let lines: Vec<String> = arg_input::input_lines_filtered(filenames.clone(), |line| line.contains('o'))
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+  let expected: Vec<String> = arg_input::input_lines(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .filter(|line| line.contains('o'))
+    .collect();
+
+  assert!(lines.contains(&"one".to_string()));
+  assert_eq!(lines, expected);
+}
+
+#[test]
+fn test_input_lines_filtered_none() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut lines = arg_input::input_lines_filtered(filenames, |_| false).unwrap();
+
+  assert!(lines.next().is_none());
+}
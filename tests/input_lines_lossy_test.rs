@@ -0,0 +1,36 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+
+use inputs::{attach_input_dir, scratch_dir, NONEXISTENT};
+
+#[test]
+fn test_input_lines_lossy() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("bad_utf8"), attach_input_dir("B")];
+
+  let lines = arg_input::input_lines_lossy(filenames).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["A", "x\u{FFFD}y\u{FFFD}zB"]);
+}
+
+#[test]
+fn test_input_lines_lossy_keeps_going() {
+  let dir = scratch_dir("input_lines_lossy_keeps_going");
+  fs::write(dir.join("log"), b"ok\n\xC0\xC1 garbage\r\nstill ok\n").unwrap();
+
+  let lines = arg_input::input_lines_lossy(vec![dir.join("log")]).unwrap()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["ok", "\u{FFFD}\u{FFFD} garbage", "still ok"]);
+  assert!(arg_input::input_lines(vec![dir.join("log")]).unwrap().any(|line| line.is_err()));
+}
+
+#[test]
+fn test_input_lines_lossy_missing() {
+  assert!(arg_input::input_lines_lossy(vec![attach_input_dir(NONEXISTENT[0])]).is_err());
+}
@@ -0,0 +1,26 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_lines_numbered() {
+  let filenames = vec![
+    attach_input_dir("three_lines"),
+    attach_input_dir("A"),
+    attach_input_dir("three_lines")
+  ];
+
+  let mut all_input = arg_input::input_lines_numbered(filenames).unwrap();
+  let mut numbers = Vec::new();
+
+  assert_eq!(all_input.lineno(), 0);
+
+  while let Some(line) = all_input.next() {
+    assert!(line.is_ok());
+    numbers.push((all_input.lineno(), all_input.file_lineno()));
+  }
+
+  assert_eq!(numbers, vec![(1, 1), (2, 2), (3, 3), (4, 1), (5, 1), (6, 2), (7, 3)]);
+}
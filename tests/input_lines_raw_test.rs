@@ -0,0 +1,17 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_lines_raw() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("no_newline")];
+
+  let lines: Vec<String> = arg_input::input_lines_raw(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["one\n", "two\n", "three\n", "no newline"]);
+}
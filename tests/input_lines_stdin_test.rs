@@ -0,0 +1,76 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Cursor};
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+fn stdin_lines(filenames: Vec<PathBuf>, stdin: &'static str) -> Vec<String> {
+  arg_input::input_with_stdin(filenames, Box::new(Cursor::new(stdin)))
+    .unwrap()
+    .lines()
+    .map(Result::unwrap)
+    .collect()
+}
+
+#[test]
+fn test_stdin_lines_no_args() {
+  assert_eq!(stdin_lines(Vec::new(), "one\ntwo\n"), vec!["one", "two"]);
+}
+
+#[test]
+fn test_stdin_lines_empty() {
+  assert!(stdin_lines(Vec::new(), "").is_empty());
+}
+
+#[test]
+fn test_stdin_lines_no_trailing_newline() {
+  assert_eq!(stdin_lines(Vec::new(), "one\r\ntwo"), vec!["one", "two"]);
+}
+
+#[test]
+fn test_stdin_lines_alias() {
+  let filenames = vec![attach_input_dir("A"), PathBuf::from("-"), attach_input_dir("B")];
+
+  assert_eq!(stdin_lines(filenames, "from\nstdin\n"), vec!["A", "from", "stdin", "B"]);
+}
+
+#[test]
+fn test_stdin_lines_alias_spanning() {
+  let filenames = vec![PathBuf::from("-"), attach_input_dir("B")];
+
+  assert_eq!(stdin_lines(filenames, "unfinished "), vec!["unfinished B"]);
+}
+
+#[test]
+fn test_stdin_lines_repeated_alias() {
+  let filenames = vec![PathBuf::from("-"), attach_input_dir("A"), PathBuf::from("-")];
+
+  assert_eq!(stdin_lines(filenames, "once\n"), vec!["once", "A"]);
+}
+
+#[test]
+fn test_stdin_lines_ignored_with_args() {
+  let filenames = vec![attach_input_dir("A")];
+
+  assert_eq!(stdin_lines(filenames, "never read\n"), vec!["A"]);
+}
+
+#[test]
+fn test_stdin_lines_custom_alias() {
+  let builder = InputBuilder::new().stdin_alias("STDIN");
+
+  let lines: Vec<String> = builder
+    .build_with_stdin(vec![PathBuf::from("STDIN"), attach_input_dir("A")], Box::new(Cursor::new("from stdin\n")))
+    .unwrap()
+    .lines()
+    .map(Result::unwrap)
+    .collect();
+
+  assert_eq!(lines, vec!["from stdin", "A"]);
+  assert!(builder.build_with_stdin(vec!["-"], Box::new(Cursor::new(""))).is_err());
+}
@@ -0,0 +1,51 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io;
+
+use arg_input::LineError;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_lines_streaming() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir(NONEXISTENT[0]), attach_input_dir("three_lines")];
+
+  let lines = arg_input::input_lines_streaming(filenames).collect::<Vec<_>>();
+
+  assert_eq!(lines.len(), 5);
+  assert_eq!(lines[0].as_ref().unwrap(), "A");
+
+  match lines[1] {
+    Err(LineError::Open(ref err)) => {
+      assert_eq!(err.arg_index, 1);
+      assert_eq!(err.inner.kind(), io::ErrorKind::NotFound);
+    },
+    ref other => panic!("expected an open error, got {:?}", other)
+  }
+
+  let rest: Vec<&str> = lines[2..].iter().map(|line| line.as_ref().unwrap().as_str()).collect();
+  assert_eq!(rest, vec!["one", "two", "three"]);
+}
+
+#[test]
+fn test_input_lines_streaming_all_fine() {
+  let filenames = vec![attach_input_dir("no_newline"), attach_input_dir("B")];
+
+  let lines = arg_input::input_lines_streaming(filenames).map(Result::unwrap).collect::<Vec<_>>();
+
+  assert_eq!(lines, vec!["no newline", "B"]);
+}
+
+#[test]
+fn test_input_lines_streaming_directory() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir(""), attach_input_dir("B")];
+
+  let lines = arg_input::input_lines_streaming(filenames).collect::<Vec<_>>();
+
+  assert_eq!(lines.len(), 3);
+  assert_eq!(lines[0].as_ref().unwrap(), "A");
+  assert!(matches!(lines[1], Err(LineError::Open(ref err)) if err.inner.kind() == io::ErrorKind::IsADirectory));
+  assert_eq!(lines[2].as_ref().unwrap(), "B");
+}
@@ -0,0 +1,38 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::TaggedLine;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_lines_tagged() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("empty"), attach_input_dir("A"), attach_input_dir("no_newline")];
+
+  let lines: Vec<TaggedLine> = arg_input::input_lines_tagged(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+  let ends: Vec<(&str, bool)> = lines.iter().map(|line| (line.text.as_str(), line.is_file_end)).collect();
+
+  assert_eq!(ends, vec![
+    ("one", false), ("two", false), ("three", true),
+    ("A", true),
+    ("no newline", true)
+  ]);
+  assert_eq!(lines[2].path, attach_input_dir("three_lines"));
+  assert_eq!(lines[3].path, attach_input_dir("A"));
+}
+
+#[test]
+fn test_input_lines_tagged_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match arg_input::input_lines_tagged(filenames) {
+    Ok(_) => panic!("expected the files to be missing"),
+    Err(err) => assert_eq!(err.badfiles.len(), NONEXISTENT.len())
+  }
+}
@@ -2,6 +2,8 @@ extern crate arg_input;
 
 mod inputs;
 
+use std::io::{BufRead, BufReader, Cursor};
+
 use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
 
 #[test]
@@ -38,3 +40,59 @@ fn test_input_lines_nonexistent() {
     Err(errs) => assert_eq!(errs.badfiles.len(), NONEXISTENT.len())
   }
 }
+
+#[test]
+fn test_input_lines_crlf() {
+  let filenames = vec![attach_input_dir("crlf"), attach_input_dir("A")];
+
+  let lines: Vec<String> = arg_input::input_lines(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  // Only a `\r` right before the `\n` is a line ending.
+  assert_eq!(lines, vec!["one", "two", "mid\rline", "last\rA"]);
+}
+
+#[test]
+fn test_input_lines_with_capacity() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("A")];
+
+  let lines: Vec<String> = arg_input::input_lines_with_capacity(filenames, 2)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["one", "two", "three", "A"]);
+}
+
+#[test]
+fn test_input_lines_over_channel() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let lines = arg_input::input_lines(filenames).unwrap();
+  let (sender, receiver) = std::sync::mpsc::channel();
+
+  std::thread::spawn(move || {
+    for line in lines {
+      sender.send(line.unwrap()).unwrap();
+    }
+  });
+
+  let received: Vec<String> = receiver.iter().collect();
+
+  assert_eq!(received, INPUTS.to_vec());
+}
+
+#[test]
+fn test_lines_type_alias() {
+  let from_input: arg_input::Lines = arg_input::input_lines(vec![attach_input_dir("A")]).unwrap();
+  let from_cursor: arg_input::Lines<BufReader<Cursor<&str>>> = BufReader::new(Cursor::new("A\n")).lines();
+
+  let from_input: Vec<String> = from_input.map(Result::unwrap).collect();
+  let from_cursor: Vec<String> = from_cursor.map(Result::unwrap).collect();
+
+  assert_eq!(from_input, from_cursor);
+}
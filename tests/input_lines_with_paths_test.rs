@@ -0,0 +1,66 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::path::PathBuf;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_lines_with_paths() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let all_input = arg_input::input_lines_with_paths(filenames).unwrap();
+  let mut count = 0;
+
+  for (i, line) in all_input.enumerate() {
+    let (path, line_text) = line.unwrap();
+
+    assert_eq!(path, attach_input_dir(INPUTS[i]));
+    assert_eq!(&line_text, INPUTS[i]);
+    count += 1;
+  }
+
+  assert_eq!(count, INPUTS.len());
+}
+
+#[test]
+fn test_input_lines_with_paths_no_trailing_newline() {
+  let filenames = vec![attach_input_dir("no_newline"), attach_input_dir("A")];
+
+  let lines: Vec<(PathBuf, String)> = arg_input::input_lines_with_paths(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec![
+    (attach_input_dir("no_newline"), "no newline".to_string()),
+    (attach_input_dir("A"), "A".to_string())
+  ]);
+}
+
+#[test]
+fn test_input_lines_with_paths_nonexistent() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match arg_input::input_lines_with_paths(filenames) {
+    Ok(_) => panic!("input_lines_with_paths() should not have found these files"),
+    Err(errs) => assert_eq!(errs.badfiles.len(), NONEXISTENT.len())
+  }
+}
+
+#[test]
+fn test_input_lines_with_paths_crlf() {
+  let filenames = vec![attach_input_dir("crlf")];
+
+  let lines: Vec<String> = arg_input::input_lines_with_paths(filenames)
+    .unwrap()
+    .map(|line| line.unwrap().1)
+    .collect();
+
+  assert_eq!(lines, vec!["one", "two", "mid\rline", "last\r"]);
+}
@@ -0,0 +1,76 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::env;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_locked() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("B")];
+
+  let contents = arg_input::input_locked(filenames, |mut reader| {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents).unwrap();
+    contents
+  }).unwrap();
+
+  assert_eq!(contents, "A\nB\n");
+}
+
+/// Set in the child process `test_input_locked_with_stdin` runs, which gets
+/// something to read piped into its `stdin`.
+const CHILD_VAR: &str = "ARG_INPUT_LOCKED_TEST_CHILD";
+
+#[test]
+fn test_input_locked_with_stdin() {
+  if env::var_os(CHILD_VAR).is_some() {
+    let filenames = vec![attach_input_dir("A"), PathBuf::from("-")];
+
+    let contents = arg_input::input_locked(filenames, |mut reader| {
+      let mut contents = String::new();
+      reader.read_to_string(&mut contents).unwrap();
+      (reader.used_stdin(), contents)
+    }).unwrap();
+
+    assert_eq!(contents, (true, "A\nfrom stdin\n".to_string()));
+    return;
+  }
+
+  let mut child = Command::new(env::current_exe().unwrap())
+    .args(["--exact", "test_input_locked_with_stdin", "--test-threads=1"])
+    .env(CHILD_VAR, "1")
+    .stdin(Stdio::piped())
+    .stdout(Stdio::null())
+    .spawn()
+    .unwrap();
+  child.stdin.take().unwrap().write_all(b"from stdin\n").unwrap();
+
+  // Reading `stdin` while it's locked used to deadlock.
+  let started = Instant::now();
+  let status = loop {
+    if let Some(status) = child.try_wait().unwrap() {
+      break status;
+    }
+    if started.elapsed() > Duration::from_secs(10) {
+      child.kill().unwrap();
+      panic!("reading stdin while it was locked hung");
+    }
+    thread::sleep(Duration::from_millis(20));
+  };
+
+  assert!(status.success());
+}
+
+#[test]
+fn test_input_locked_missing_file() {
+  let filenames = vec![attach_input_dir(NONEXISTENT[0])];
+
+  assert!(arg_input::input_locked(filenames, |_| panic!("no reader should be handed over")).is_err());
+}
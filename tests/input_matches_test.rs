@@ -0,0 +1,56 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::Match;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_input_matches() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("crlf")];
+
+  let matches: Vec<Match> = arg_input::input_matches(filenames.clone(), |line| line.contains('o'))
+    .unwrap()
+    .map(|found| found.unwrap())
+    .collect();
+
+  assert_eq!(matches[0], Match { path: attach_input_dir("three_lines"), lineno: 1, line: "one".to_string() });
+  assert_eq!(matches[1], Match { path: attach_input_dir("three_lines"), lineno: 2, line: "two".to_string() });
+
+  // Line numbers start over in each file.
+  let expected: Vec<(usize, String)> = arg_input::input_lines(vec![attach_input_dir("crlf")])
+    .unwrap()
+    .map(|line| line.unwrap())
+    .enumerate()
+    .filter(|(_, line)| line.contains('o'))
+    .map(|(index, line)| (index + 1, line))
+    .collect();
+  let found: Vec<(usize, String)> = matches.into_iter()
+    .filter(|found| found.path == attach_input_dir("crlf"))
+    .map(|found| (found.lineno, found.line))
+    .collect();
+
+  assert_eq!(found, expected);
+}
+
+#[test]
+fn test_input_matches_none() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut matches = arg_input::input_matches(filenames, |line| line == "nothing like this").unwrap();
+
+  assert!(matches.next().is_none());
+}
+
+#[test]
+fn test_input_matches_missing() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("Z")];
+
+  match arg_input::input_matches(filenames, |_| true) {
+    Ok(_) => panic!("expected the file to be missing"),
+    Err(err) => assert_eq!(err.badfiles[0].arg_index, 1)
+  }
+}
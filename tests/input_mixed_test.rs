@@ -0,0 +1,93 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+use arg_input::{InputBuilder, InputSource};
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_mixed() {
+  let sources = vec![
+    InputSource::Reader(Box::new(Cursor::new("header\n"))),
+    InputSource::Path(attach_input_dir("A")),
+    InputSource::Reader(Box::new(Cursor::new("middle\n"))),
+    InputSource::Path(attach_input_dir("B"))
+  ];
+
+  let mut all_input = arg_input::input_mixed(sources).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "header\nA\nmiddle\nB\n");
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir("B").as_path()));
+  assert!(!all_input.used_stdin());
+}
+
+#[test]
+fn test_input_mixed_current_filename() {
+  let sources = vec![InputSource::Reader(Box::new(Cursor::new("header\n"))), InputSource::Path(attach_input_dir("A"))];
+
+  let mut all_input = arg_input::input_mixed(sources).unwrap();
+  let mut buf = [0; 7];
+
+  all_input.read_exact(&mut buf).unwrap();
+
+  assert_eq!(all_input.current_filename(), None);
+}
+
+#[test]
+fn test_input_mixed_missing_files() {
+  let sources = vec![
+    InputSource::Path(attach_input_dir("A")),
+    InputSource::Path(attach_input_dir(NONEXISTENT[0])),
+    InputSource::Path(PathBuf::from("-"))
+  ];
+
+  match arg_input::input_mixed(sources) {
+    Ok(_) => panic!("missing files should be errors"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles.len(), 2);
+      assert_eq!(errs.badfiles[0].arg_index, 1);
+      assert_eq!(errs.badfiles[1].arg_index, 2);
+      assert_eq!(errs.badfiles[1].filename, "-");
+    }
+  }
+}
+
+#[test]
+fn test_input_mixed_skip_missing() {
+  let sources = vec![
+    InputSource::Path(attach_input_dir(NONEXISTENT[0])),
+    InputSource::Reader(Box::new(Cursor::new("from memory\n")))
+  ];
+
+  let mut all_input = InputBuilder::new().skip_missing(true).build_mixed(sources).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "from memory\n");
+}
+
+#[test]
+fn test_input_mixed_readers_cant_rewind() {
+  let sources = vec![InputSource::Reader(Box::new(Cursor::new("from memory\n")))];
+
+  let mut all_input = arg_input::input_mixed(sources).unwrap();
+
+  assert!(all_input.rewind().is_err());
+}
+
+#[test]
+fn test_input_mixed_stdin() {
+  let sources = vec![InputSource::Path(attach_input_dir("A")), InputSource::Stdin];
+
+  let all_input = arg_input::input_mixed(sources).unwrap();
+
+  assert!(all_input.used_stdin());
+}
@@ -0,0 +1,49 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::NdjsonError;
+
+use inputs::attach_input_dir;
+
+/// Stands in for a real JSON parser, for values like `{"n": 1}`.
+fn parse_n(line: &str) -> Result<u32, String> {
+  line.trim()
+    .strip_prefix("{\"n\": ")
+    .and_then(|rest| rest.strip_suffix('}'))
+    .and_then(|n| n.parse().ok())
+    .ok_or_else(|| format!("bad value: {}", line))
+}
+
+#[test]
+fn test_input_ndjson() {
+  let filenames = vec![attach_input_dir("values.ndjson"), attach_input_dir("values.ndjson")];
+
+  let values: Vec<u32> = arg_input::input_ndjson(filenames, parse_n)
+    .unwrap()
+    .map(|value| value.unwrap())
+    .collect();
+
+  assert_eq!(values, vec![1, 2, 1, 2]);
+}
+
+#[test]
+fn test_input_ndjson_parse_error() {
+  let filenames = vec![attach_input_dir("values.ndjson"), attach_input_dir("bad.ndjson")];
+
+  let values: Vec<Result<u32, NdjsonError<String>>> = arg_input::input_ndjson(filenames, parse_n)
+    .unwrap()
+    .collect();
+
+  assert_eq!(values.len(), 4);
+
+  match values[2] {
+    Err(NdjsonError::Parse { lineno, ref error }) => {
+      assert_eq!(lineno, 5);
+      assert_eq!(error, "bad value: not json");
+    },
+    ref other => panic!("expected a parse error, got {:?}", other)
+  }
+
+  assert_eq!(values[3].as_ref().unwrap(), &3);
+}
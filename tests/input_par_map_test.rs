@@ -0,0 +1,46 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+fn read_all(reader: &mut dyn Read) -> String {
+  let mut contents = String::new();
+  reader.read_to_string(&mut contents).unwrap();
+  contents
+}
+
+#[test]
+fn test_input_par_map() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let results = arg_input::input_par_map(filenames, read_all).unwrap();
+
+  assert_eq!(results, vec!["A\n", "B\n", "C\n", "D\n", "E\n"]);
+}
+
+#[test]
+fn test_input_par_map_many() {
+  let filenames: Vec<_> = (0..100).map(|i| attach_input_dir(INPUTS[i % INPUTS.len()])).collect();
+
+  let results = arg_input::input_par_map(&filenames, |reader| read_all(reader).len()).unwrap();
+
+  assert_eq!(results.len(), 100);
+  assert!(results.iter().all(|&len| len == 2));
+}
+
+#[test]
+fn test_input_par_map_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match arg_input::input_par_map(filenames, read_all) {
+    Ok(_) => panic!("input_par_map() should have failed on nonexistent files"),
+    Err(errs) => assert_eq!(errs.badfiles.len(), NONEXISTENT.len())
+  }
+}
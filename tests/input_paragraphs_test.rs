@@ -0,0 +1,29 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_paragraphs() {
+  let filenames = vec![attach_input_dir("paragraphs1"), attach_input_dir("paragraphs2")];
+
+  let paragraphs: Vec<String> = arg_input::input_paragraphs(filenames)
+    .unwrap()
+    .map(|paragraph| paragraph.unwrap())
+    .collect();
+
+  assert_eq!(paragraphs, vec!["From: a\nTo: b", "Subject: first\ncontinued", "last\nparagraph"]);
+}
+
+#[test]
+fn test_input_paragraphs_no_blank_lines() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("B")];
+
+  let paragraphs: Vec<String> = arg_input::input_paragraphs(filenames)
+    .unwrap()
+    .map(|paragraph| paragraph.unwrap())
+    .collect();
+
+  assert_eq!(paragraphs, vec!["A\nB"]);
+}
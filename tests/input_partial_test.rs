@@ -0,0 +1,44 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_partial() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("Z"), attach_input_dir("B"), attach_input_dir("Y")];
+
+  let (all_input, errors) = arg_input::input_partial(filenames);
+  let mut result_string = String::new();
+
+  all_input.unwrap().read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+  assert_eq!(errors.len(), 2);
+  assert_eq!(errors[0].filename, attach_input_dir("Z").to_string_lossy());
+}
+
+#[test]
+fn test_input_partial_nonexistent() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let (all_input, errors) = arg_input::input_partial(filenames);
+
+  assert!(all_input.is_none());
+  assert_eq!(errors.len(), NONEXISTENT.len());
+}
+
+#[test]
+fn test_input_partial_arg_index() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("Z"), attach_input_dir("B"), attach_input_dir("Y")];
+
+  let (_, errors) = arg_input::input_partial(filenames);
+
+  assert_eq!(errors[0].arg_index, 1);
+  assert_eq!(errors[1].arg_index, 3);
+  assert!(errors[1].to_string().starts_with("could not read argument #4 (./tests/inputs/Y)"));
+}
@@ -0,0 +1,27 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use arg_input::ArgfReader;
+
+use inputs::{attach_input_dir, INPUTS};
+
+struct Tool {
+  input: ArgfReader
+}
+
+#[test]
+fn test_input_reader() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut tool = Tool { input: arg_input::input_reader(filenames).unwrap() };
+  let mut result_string = String::new();
+
+  tool.input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\nC\nD\nE\n");
+}
@@ -0,0 +1,43 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_report() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("Z"), attach_input_dir("B"), attach_input_dir("Y")];
+
+  let report = arg_input::input_report(filenames);
+
+  let contents: Vec<_> = report.readers.into_iter().map(|(path, mut reader)| {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents).unwrap();
+    (path, contents)
+  }).collect();
+
+  assert_eq!(contents, vec![(attach_input_dir("A"), "A\n".to_string()), (attach_input_dir("B"), "B\n".to_string())]);
+  assert_eq!(report.errors.iter().map(|err| err.arg_index).collect::<Vec<_>>(), vec![1, 3]);
+}
+
+#[test]
+fn test_input_report_all_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let report = arg_input::input_report(filenames);
+
+  assert!(report.readers.is_empty());
+  assert_eq!(report.errors.len(), NONEXISTENT.len());
+}
+
+#[test]
+fn test_input_report_require_inputs() {
+  let report = arg_input::InputBuilder::new().require_inputs(true).build_report(Vec::<&str>::new());
+
+  assert!(report.readers.is_empty());
+  assert!(report.errors.is_empty());
+}
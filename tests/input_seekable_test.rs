@@ -0,0 +1,56 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_input_seekable() {
+  let filenames = vec![
+    attach_input_dir("three_lines"),
+    attach_input_dir("empty"),
+    attach_input_dir("A"),
+    attach_input_dir("B")
+  ];
+
+  let mut all_input = arg_input::input_seekable(filenames).unwrap();
+  let mut result_string = String::new();
+
+  assert_eq!(all_input.len(), 18);
+
+  all_input.seek(SeekFrom::Start(4)).unwrap();
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "two\nthree\nA\nB\n");
+
+  // Right at the start of a file, skipping over the empty one.
+  all_input.seek(SeekFrom::Start(14)).unwrap();
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir("A").as_path()));
+
+  all_input.seek(SeekFrom::End(-3)).unwrap();
+  result_string.clear();
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "\nB\n");
+
+  all_input.seek(SeekFrom::Start(0)).unwrap();
+  result_string.clear();
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "one\ntwo\nthree\nA\nB\n");
+}
+
+#[test]
+fn test_input_seekable_stdin() {
+  let filenames = vec![attach_input_dir(INPUTS[0]), PathBuf::from("-")];
+
+  match arg_input::input_seekable(filenames) {
+    Ok(_) => panic!("input_seekable() should not accept stdin"),
+    Err(errs) => assert_eq!(errs.badfiles[0].filename, "-")
+  }
+
+  assert!(arg_input::input_seekable(Vec::<PathBuf>::new()).is_err());
+}
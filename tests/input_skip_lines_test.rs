@@ -0,0 +1,47 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_skip_lines() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("no_newline"), attach_input_dir("A")];
+
+  let lines: Vec<String> = arg_input::input_skip_lines(filenames.clone(), 2)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+  let all: Vec<String> = arg_input::input_lines(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, all[2..].to_vec());
+}
+
+#[test]
+fn test_input_skip_lines_big() {
+  let filenames = vec![attach_input_dir("big"), attach_input_dir("A")];
+
+  let lines: Vec<String> = arg_input::input_skip_lines(filenames.clone(), 1000)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+  let all: Vec<String> = arg_input::input_lines(filenames)
+    .unwrap()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, all[usize::min(1000, all.len())..].to_vec());
+}
+
+#[test]
+fn test_input_skip_lines_past_end() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("B")];
+
+  let mut lines = arg_input::input_skip_lines(filenames, 5).unwrap();
+
+  assert!(lines.next().is_none());
+  assert!(lines.next().is_none());
+}
@@ -0,0 +1,35 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_input_skipping() {
+  let filenames = vec![attach_input_dir("Z"), attach_input_dir("A"), attach_input_dir("B")];
+
+  let (mut all_input, errors) = arg_input::input_skipping(filenames);
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+  assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn test_input_skipping_nonexistent() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let (mut all_input, errors) = arg_input::input_skipping(filenames);
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert!(result.is_empty());
+  assert_eq!(errors.len(), NONEXISTENT.len());
+}
@@ -0,0 +1,34 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_split() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("A")];
+
+  let records: Vec<Vec<u8>> = arg_input::input_split(filenames, b'e')
+    .unwrap()
+    .map(|record| record.unwrap())
+    .collect();
+
+  assert_eq!(records, vec![
+    b"on".to_vec(),
+    b"\ntwo\nthr".to_vec(),
+    b"".to_vec(),
+    b"\nA\n".to_vec()
+  ]);
+}
+
+#[test]
+fn test_input_zero_terminated() {
+  let filenames = vec![attach_input_dir("nul_separated"), attach_input_dir("nul_separated2")];
+
+  let records: Vec<Vec<u8>> = arg_input::input_zero_terminated(filenames)
+    .unwrap()
+    .map(|record| record.unwrap())
+    .collect();
+
+  assert_eq!(records, vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()]);
+}
@@ -0,0 +1,74 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::{attach_input_dir, scratch_dir, INPUTS, NONEXISTENT};
+
+fn last_lines(filenames: Vec<std::path::PathBuf>, n: usize) -> Vec<String> {
+  let lines: Vec<String> = arg_input::input_lines(filenames).unwrap().map(Result::unwrap).collect();
+
+  lines[lines.len().saturating_sub(n)..].to_vec()
+}
+
+#[test]
+fn test_input_tail() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  assert_eq!(arg_input::input_tail(filenames, 2).unwrap(), vec!["D", "E"]);
+}
+
+#[test]
+fn test_input_tail_more_than_there_are() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("three_lines")];
+
+  assert_eq!(arg_input::input_tail(filenames, 10).unwrap(), vec!["A", "one", "two", "three"]);
+}
+
+#[test]
+fn test_input_tail_zero() {
+  let filenames = vec![attach_input_dir("three_lines")];
+
+  assert!(arg_input::input_tail(filenames, 0).unwrap().is_empty());
+}
+
+#[test]
+fn test_input_tail_spanning_files() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("no_newline"), attach_input_dir("B")];
+
+  assert_eq!(arg_input::input_tail(filenames, 2).unwrap(), vec!["three", "no newlineB"]);
+}
+
+#[test]
+fn test_input_tail_crlf() {
+  let filenames = vec![attach_input_dir("crlf")];
+
+  assert_eq!(arg_input::input_tail(filenames, 2).unwrap(), vec!["mid\rline", "last\r"]);
+}
+
+#[test]
+fn test_input_tail_big() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("big")];
+
+  assert_eq!(arg_input::input_tail(filenames.clone(), 5000).unwrap(), last_lines(filenames, 5000));
+}
+
+#[test]
+fn test_input_tail_blank_lines() {
+  let dir = scratch_dir("input_tail_blank_lines");
+  std::fs::write(dir.join("blank"), "a\n\n\n").unwrap();
+
+  assert_eq!(arg_input::input_tail(vec![dir.join("blank")], 2).unwrap(), vec!["", ""]);
+  assert_eq!(arg_input::input_tail(vec![dir.join("blank")], 5).unwrap(), vec!["a", "", ""]);
+}
+
+#[test]
+fn test_input_tail_missing_file() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir(NONEXISTENT[0])];
+
+  match arg_input::input_tail(filenames, 1) {
+    Err(arg_input::InputReadError::Input(errs)) => assert_eq!(errs.badfiles.len(), 1),
+    other => panic!("expected the missing file to fail, got {:?}", other)
+  }
+}
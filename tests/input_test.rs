@@ -45,3 +45,75 @@ fn test_input_nonexistent() {
     Err(errs) => assert_eq!(errs.badfiles.len(), NONEXISTENT.len())
   };
 }
+
+#[cfg(unix)]
+#[test]
+fn test_input_non_utf8_filename() {
+  use std::ffi::OsStr;
+  use std::os::unix::ffi::OsStrExt;
+
+  let filename = OsStr::from_bytes(b"./tests/inputs/not\xffthere");
+
+  match arg_input::input(vec![filename]) {
+    Ok(_) => panic!("input() should not have found this file"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles[0].os_filename, filename);
+      assert_eq!(errs.badfiles[0].filename, "./tests/inputs/not\u{fffd}there");
+    }
+  }
+}
+
+#[test]
+fn test_input_error_source() {
+  use std::error::Error;
+
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let errs = arg_input::input(filenames).err().unwrap();
+  let first = errs.source().unwrap();
+
+  assert_eq!(first.to_string(), errs.badfiles[0].to_string());
+  assert_eq!(first.source().unwrap().to_string(), errs.badfiles[0].inner.to_string());
+}
+
+#[test]
+fn test_input_or_err_if_tty() {
+  use std::io::{self, IsTerminal};
+  use std::path::PathBuf;
+
+  use arg_input::InputErrorKind;
+
+  let all_input = arg_input::input_or_err_if_tty(Vec::<PathBuf>::new());
+
+  if io::stdin().is_terminal() {
+    assert_eq!(all_input.err().unwrap().kind, InputErrorKind::StdinIsTerminal);
+  } else {
+    assert!(all_input.is_ok());
+  }
+
+  // Files are read like normal, whatever stdin is.
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  assert!(arg_input::input_or_err_if_tty(filenames).is_ok());
+}
+
+#[test]
+fn test_input_send() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+
+  let reading = std::thread::spawn(move || {
+    let mut result_string = String::new();
+    all_input.read_to_string(&mut result_string).unwrap();
+    result_string
+  });
+
+  assert_eq!(reading.join().unwrap(), "A\nB\nC\nD\nE\n");
+}
@@ -0,0 +1,38 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_text() {
+  let filenames = vec![attach_input_dir("bom"), attach_input_dir("bom")];
+
+  let mut all_input = arg_input::input_text(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  // Only the BOM at the very start gets stripped.
+  assert_eq!(result_string, "bom\n\u{feff}bom\n");
+}
+
+#[test]
+fn test_input_text_short_file() {
+  let filenames = vec![attach_input_dir("short_bom"), attach_input_dir("A")];
+
+  let mut all_input = InputBuilder::new()
+    .strip_bom(true)
+    .buffer_capacity(1)
+    .build(filenames)
+    .unwrap();
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, b"\xEF\xBBA\n");
+}
@@ -0,0 +1,45 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+
+use arg_input::InputReadError;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_to_bytes() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let contents = arg_input::input_to_bytes(filenames).unwrap();
+
+  assert_eq!(contents, b"A\nB\nC\nD\nE\n".to_vec());
+}
+
+#[test]
+fn test_input_to_bytes_binary() {
+  let filenames = vec![attach_input_dir("bad_utf8"), attach_input_dir("big")];
+
+  let mut expected = fs::read(attach_input_dir("bad_utf8")).unwrap();
+  expected.extend(fs::read(attach_input_dir("big")).unwrap());
+
+  let contents = arg_input::input_to_bytes(filenames).unwrap();
+
+  assert_eq!(contents, expected);
+  assert!(contents.capacity() >= expected.len());
+}
+
+#[test]
+fn test_input_to_bytes_missing_file() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match arg_input::input_to_bytes(filenames) {
+    Err(InputReadError::Input(err)) => assert_eq!(err.badfiles.len(), NONEXISTENT.len()),
+    other => panic!("expected an InputError, got {:?}", other)
+  }
+}
@@ -0,0 +1,40 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::InputReadError;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_to_string() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let contents = arg_input::input_to_string(filenames).unwrap();
+
+  assert_eq!(contents, "A\nB\nC\nD\nE\n");
+}
+
+#[test]
+fn test_input_to_string_missing_file() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match arg_input::input_to_string(filenames) {
+    Err(InputReadError::Input(err)) => assert_eq!(err.badfiles.len(), NONEXISTENT.len()),
+    other => panic!("expected an InputError, got {:?}", other)
+  }
+}
+
+#[test]
+fn test_input_to_string_not_utf8() {
+  let filenames = vec![attach_input_dir("bad_utf8")];
+
+  match arg_input::input_to_string(filenames) {
+    Err(InputReadError::Io(err)) => assert_eq!(err.kind(), std::io::ErrorKind::InvalidData),
+    other => panic!("expected an io::Error, got {:?}", other)
+  }
+}
@@ -0,0 +1,60 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{self, ErrorKind};
+
+use arg_input::InputReadError;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_input_until_broken_pipe() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut lines = Vec::new();
+  let result = arg_input::input_until_broken_pipe(filenames, |line| {
+    if lines.len() == 2 {
+      return Err(io::Error::new(ErrorKind::BrokenPipe, "downstream went away"));
+    }
+
+    lines.push(line.to_string());
+    Ok(())
+  });
+
+  assert!(result.is_ok());
+  assert_eq!(lines, vec!["A", "B"]);
+}
+
+#[test]
+fn test_input_until_broken_pipe_other_error() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let result = arg_input::input_until_broken_pipe(filenames, |_| {
+    Err(io::Error::other("disk full"))
+  });
+
+  match result {
+    Err(InputReadError::Io(err)) => assert_eq!(err.kind(), ErrorKind::Other),
+    _ => panic!("expected the error to be passed along")
+  }
+}
+
+#[test]
+fn test_input_until_broken_pipe_all_input() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut count = 0;
+  arg_input::input_until_broken_pipe(filenames, |_| {
+    count += 1;
+    Ok(())
+  }).unwrap();
+
+  assert_eq!(count, INPUTS.len());
+}
@@ -0,0 +1,41 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_with_metadata() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("big")];
+
+  let (mut all_input, info) = arg_input::input_with_metadata(filenames).unwrap();
+  let big = fs::metadata(attach_input_dir("big")).unwrap();
+
+  assert_eq!(info.len(), 2);
+  assert_eq!(info[0].path, attach_input_dir("A"));
+  assert_eq!(info[0].len, Some(2));
+  assert_eq!(info[1].len, Some(big.len()));
+  assert_eq!(info[1].modified, big.modified().ok());
+  assert!(!info[1].is_stdin);
+
+  let mut result = Vec::new();
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert_eq!(result.len() as u64, 2 + big.len());
+}
+
+#[test]
+fn test_input_with_metadata_stdin() {
+  let filenames = vec!["-"];
+
+  let (_, info) = arg_input::input_with_metadata(filenames).unwrap();
+
+  assert_eq!(info[0].path, PathBuf::from("-"));
+  assert_eq!(info[0].len, None);
+  assert_eq!(info[0].modified, None);
+  assert!(info[0].is_stdin);
+}
@@ -0,0 +1,65 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_with_stdin_no_args() {
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut all_input = arg_input::input_with_stdin(Vec::<PathBuf>::new(), stdin).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "from stdin\n");
+  assert_eq!(all_input.current_filename(), None);
+}
+
+#[test]
+fn test_input_with_stdin_alias() {
+  let filenames = vec![attach_input_dir("A"), PathBuf::from("-"), attach_input_dir("B")];
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut all_input = arg_input::input_with_stdin(filenames, stdin).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nfrom stdin\nB\n");
+}
+
+/// Like a terminal, gives back more input each time after reporting EOF.
+struct Terminal {
+  eof_next: bool
+}
+
+impl Read for Terminal {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    self.eof_next = !self.eof_next;
+
+    if self.eof_next {
+      buf[..2].copy_from_slice(b"x\n");
+      Ok(2)
+    } else {
+      Ok(0)
+    }
+  }
+}
+
+#[test]
+fn test_input_with_stdin_repeated_alias() {
+  let filenames = vec![PathBuf::from("-"), attach_input_dir("A"), PathBuf::from("-")];
+  let stdin = Box::new(Terminal { eof_next: false });
+
+  let mut all_input = arg_input::input_with_stdin(filenames, stdin).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "x\nA\n");
+}
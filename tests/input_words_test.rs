@@ -0,0 +1,30 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_input_words() {
+  let filenames = vec![attach_input_dir("words1"), attach_input_dir("words2"), attach_input_dir("A")];
+
+  let words: Vec<String> = arg_input::input_words(filenames)
+    .unwrap()
+    .map(|word| word.unwrap())
+    .collect();
+
+  assert_eq!(words, vec!["the", "quick", "brown", "fox", "jumps", "over", "A"]);
+}
+
+#[test]
+fn test_input_words_separate_files() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("B")];
+
+  let words: Vec<String> = arg_input::input_words(filenames)
+    .unwrap()
+    .map(|word| word.unwrap())
+    .collect();
+
+  assert_eq!(words, vec!["A", "B"]);
+}
+
@@ -1,9 +1,13 @@
+// Shared between the integration tests, not all of which use every item.
+#![allow(dead_code)]
+
+use std::fs;
 use std::path::PathBuf;
 
-pub static INPUTS: [&'static str; 5] = ["A", "B", "C", "D", "E"];
-pub static NONEXISTENT: [&'static str; 3] = ["Z", "Y", "X"];
+pub static INPUTS: [&str; 5] = ["A", "B", "C", "D", "E"];
+pub static NONEXISTENT: [&str; 3] = ["Z", "Y", "X"];
 
-pub fn attach_input_dir<'a>(input_name: &'a str) -> PathBuf {
+pub fn attach_input_dir(input_name: &str) -> PathBuf {
   let mut fullpath = PathBuf::new();
 
   fullpath.push(".");
@@ -13,3 +17,14 @@ pub fn attach_input_dir<'a>(input_name: &'a str) -> PathBuf {
 
   fullpath
 }
+
+/// A fresh, empty directory for tests that need to write files.
+pub fn scratch_dir(name: &str) -> PathBuf {
+  let mut dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+  dir.push(name);
+
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(&dir).unwrap();
+
+  dir
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "mmap")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_input_mmap() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mapped = unsafe { arg_input::input_mmap(filenames) }.unwrap();
+  let all: Vec<u8> = mapped.chunks().flat_map(|chunk| chunk.iter().cloned()).collect();
+
+  assert_eq!(all, b"A\nB\nC\nD\nE\n".to_vec());
+  assert_eq!(mapped.len(), 10);
+  assert_eq!(mapped.as_bytes(), None);
+}
+
+#[test]
+fn test_input_mmap_as_bytes() {
+  let filenames = vec![attach_input_dir("empty"), attach_input_dir("big"), attach_input_dir("empty")];
+
+  let mapped = unsafe { arg_input::input_mmap(filenames) }.unwrap();
+
+  assert_eq!(mapped.as_bytes().unwrap(), &fs::read(attach_input_dir("big")).unwrap()[..]);
+  assert_eq!(mapped.files().nth(1).unwrap().0, Some(attach_input_dir("big").as_path()));
+}
+
+#[test]
+fn test_input_mmap_empty() {
+  let mapped = unsafe { arg_input::input_mmap(vec![attach_input_dir("empty")]) }.unwrap();
+
+  assert!(mapped.is_empty());
+  assert_eq!(mapped.as_bytes(), Some(&b""[..]));
+}
+
+#[test]
+fn test_input_mmap_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  match unsafe { arg_input::input_mmap(filenames) } {
+    Ok(_) => panic!("input_mmap() should have failed on nonexistent files"),
+    Err(errs) => assert_eq!(errs.badfiles.len(), NONEXISTENT.len())
+  }
+}
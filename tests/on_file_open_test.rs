@@ -0,0 +1,52 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_on_file_open() {
+  let opened = Arc::new(Mutex::new(Vec::new()));
+  let seen = opened.clone();
+
+  let filenames = vec![attach_input_dir("A"), PathBuf::from("-"), attach_input_dir("B")];
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut all_input = InputBuilder::new()
+    .on_file_open(move |path| seen.lock().unwrap().push(path.to_path_buf()))
+    .build_with_stdin(filenames, stdin)
+    .unwrap();
+
+  // Nothing gets announced until reading actually starts.
+  assert!(opened.lock().unwrap().is_empty());
+
+  let mut buf = [0; 1];
+  all_input.read_exact(&mut buf).unwrap();
+  assert_eq!(*opened.lock().unwrap(), vec![attach_input_dir("A")]);
+
+  all_input.read_to_end(&mut Vec::new()).unwrap();
+  assert_eq!(*opened.lock().unwrap(), vec![attach_input_dir("A"), PathBuf::from("-"), attach_input_dir("B")]);
+}
+
+#[test]
+fn test_on_file_open_rewind() {
+  let count = Arc::new(Mutex::new(0));
+  let counter = count.clone();
+
+  let mut all_input = InputBuilder::new()
+    .on_file_open(move |_| *counter.lock().unwrap() += 1)
+    .build(vec![attach_input_dir("A"), attach_input_dir("B")])
+    .unwrap();
+
+  all_input.read_to_end(&mut Vec::new()).unwrap();
+  all_input.rewind().unwrap();
+  all_input.read_to_end(&mut Vec::new()).unwrap();
+
+  assert_eq!(*count.lock().unwrap(), 4);
+}
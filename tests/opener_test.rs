@@ -0,0 +1,73 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{self, BufRead, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+fn fixtures(path: &Path) -> io::Result<Box<dyn Read + Send>> {
+  match path.to_str() {
+    Some("first") => Ok(Box::new(Cursor::new("one\ntwo\n"))),
+    Some("second") => Ok(Box::new(Cursor::new("three\n"))),
+    _ => Err(io::Error::new(io::ErrorKind::NotFound, "no such fixture"))
+  }
+}
+
+#[test]
+fn test_opener() {
+  let lines: Vec<String> = InputBuilder::new()
+    .opener(fixtures)
+    .build(vec!["first", "second"])
+    .unwrap()
+    .lines()
+    .map(|line| line.unwrap())
+    .collect();
+
+  assert_eq!(lines, vec!["one", "two", "three"]);
+}
+
+#[test]
+fn test_opener_stdin() {
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+
+  let mut all_input = InputBuilder::new()
+    .opener(fixtures)
+    .build_with_stdin(vec![PathBuf::from("second"), PathBuf::from("-")], stdin)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "three\nfrom stdin\n");
+}
+
+#[test]
+fn test_opener_errors() {
+  match InputBuilder::new().opener(fixtures).build(vec![attach_input_dir("A")]) {
+    Ok(_) => panic!("expected the opener to be used instead of the filesystem"),
+    Err(err) => {
+      assert_eq!(err.badfiles[0].inner.kind(), io::ErrorKind::NotFound);
+      assert_eq!(err.badfiles[0].inner.to_string(), "no such fixture");
+    }
+  }
+}
+
+#[test]
+fn test_opener_lazy_rewind() {
+  let mut all_input = InputBuilder::new()
+    .opener(fixtures)
+    .lazy(true)
+    .build(vec!["first"])
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+  all_input.rewind().unwrap();
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "one\ntwo\none\ntwo\n");
+}
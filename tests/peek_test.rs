@@ -0,0 +1,40 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_peek() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+
+  assert_eq!(all_input.peek(1).unwrap(), b"A");
+  // Peeking further than the first file reaches into the ones after it.
+  assert_eq!(all_input.peek(5).unwrap(), b"A\nB\nC");
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir(INPUTS[0]).as_path()));
+
+  let mut result_string = String::new();
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\nC\nD\nE\n");
+}
+
+#[test]
+fn test_peek_past_end() {
+  let filenames = vec![attach_input_dir("empty"), attach_input_dir("A")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+
+  assert_eq!(all_input.peek(10).unwrap(), b"A\n");
+
+  let mut buf = [0; 1];
+  all_input.read_exact(&mut buf).unwrap();
+
+  assert_eq!(all_input.peek(10).unwrap(), b"\n");
+}
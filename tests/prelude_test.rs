@@ -0,0 +1,38 @@
+extern crate arg_input;
+
+mod inputs;
+
+use arg_input::prelude::*;
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+fn concatenate(filenames: Vec<std::path::PathBuf>) -> Result<String, InputReadError> {
+  let lines = input_lines(filenames)?;
+  let mut joined = String::new();
+
+  for line in lines {
+    joined.push_str(&line?);
+  }
+
+  Ok(joined)
+}
+
+#[test]
+fn test_prelude() {
+  let reader: ArgfReader = InputBuilder::new().build(vec![attach_input_dir("A")]).unwrap();
+
+  assert_eq!(reader.source_count(), 1);
+  assert_eq!(concatenate(vec![attach_input_dir("A"), attach_input_dir("B")]).unwrap(), "AB");
+  assert_eq!(input_to_string(vec![attach_input_dir("A")]).unwrap(), "A\n");
+}
+
+#[test]
+fn test_prelude_errors() {
+  match concatenate(vec![attach_input_dir(NONEXISTENT[0])]) {
+    Err(InputReadError::Input(err)) => {
+      assert_eq!(err.kind, InputErrorKind::BadFiles);
+      let _: &FailReadFileError = &err.badfiles[0];
+    },
+    other => panic!("expected an input error, got {:?}", other)
+  }
+}
@@ -0,0 +1,77 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_progress() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut buf = [0; 5];
+
+  assert_eq!(all_input.progress(), Some(0.0));
+
+  all_input.read_exact(&mut buf).unwrap();
+  assert_eq!(all_input.progress(), Some(0.5));
+
+  let mut rest = Vec::new();
+  all_input.read_to_end(&mut rest).unwrap();
+  assert_eq!(all_input.progress(), Some(1.0));
+}
+
+#[test]
+fn test_progress_rewind() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut contents = Vec::new();
+  let mut buf = [0; 5];
+
+  all_input.read_to_end(&mut contents).unwrap();
+  all_input.rewind().unwrap();
+
+  assert_eq!(all_input.bytes_read(), 0);
+  assert_eq!(all_input.progress(), Some(0.0));
+
+  all_input.read_exact(&mut buf).unwrap();
+  assert_eq!(all_input.progress(), Some(0.5));
+}
+
+#[test]
+fn test_progress_stdin() {
+  let filenames = vec![attach_input_dir("A"), PathBuf::from("-")];
+
+  let all_input = InputBuilder::new().build_with_stdin(filenames, Box::new(Cursor::new("from stdin\n"))).unwrap();
+
+  assert_eq!(all_input.progress(), None);
+}
+
+#[test]
+fn test_progress_empty_files() {
+  let all_input = arg_input::input(vec![attach_input_dir("empty")]).unwrap();
+
+  assert_eq!(all_input.progress(), Some(1.0));
+}
+
+#[test]
+fn test_progress_with_banners() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("B")];
+
+  let mut all_input = InputBuilder::new().with_banners(|_| "==> banner <==".to_string()).build(filenames).unwrap();
+  let mut contents = String::new();
+
+  all_input.read_to_string(&mut contents).unwrap();
+
+  assert_eq!(all_input.progress(), Some(1.0));
+}
@@ -0,0 +1,107 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{self, BufRead, Cursor, Read};
+use std::path::Path;
+
+use arg_input::{FailReadAtError, FailReadFileError, InputBuilder};
+
+/// Gives back its contents, and then fails.
+struct Broken(Cursor<&'static str>);
+
+impl Read for Broken {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    match self.0.read(buf)? {
+      0 => Err(io::Error::other("disk on fire")),
+      read => Ok(read)
+    }
+  }
+}
+
+fn fixtures(path: &Path) -> io::Result<Box<dyn Read + Send>> {
+  match path.to_str() {
+    Some("first") => Ok(Box::new(Cursor::new("one\ntwo\n"))),
+    Some("broken") => Ok(Box::new(Broken(Cursor::new("three\nfo")))),
+    _ => Err(io::Error::new(io::ErrorKind::NotFound, "no such fixture"))
+  }
+}
+
+fn read_at_error(err: &io::Error) -> &FailReadAtError {
+  err.get_ref().and_then(|err| err.downcast_ref::<FailReadAtError>()).unwrap()
+}
+
+#[test]
+fn test_read_error_lines() {
+  let lines: Vec<io::Result<String>> = InputBuilder::new()
+    .opener(fixtures)
+    .build(vec!["first", "broken"])
+    .unwrap()
+    .lines()
+    .take(4)
+    .collect();
+
+  assert_eq!(lines[2].as_ref().unwrap(), "three");
+
+  let err = lines[3].as_ref().unwrap_err();
+  let at = read_at_error(err);
+
+  assert_eq!(err.kind(), io::ErrorKind::Other);
+  assert_eq!(at.arg_index, 1);
+  assert_eq!(at.filename, "broken");
+  assert_eq!(at.line, 4);
+  assert_eq!(at.file_line, 2);
+  assert_eq!(at.offset, 16);
+  assert_eq!(at.file_offset, 8);
+  assert_eq!(at.inner.to_string(), "disk on fire");
+  assert_eq!(at.to_string(), "could not read argument #2 (broken) at line 2, byte 8\ncaused by: disk on fire");
+}
+
+#[test]
+fn test_read_error_read() {
+  let mut reader = InputBuilder::new()
+    .opener(fixtures)
+    .build(vec!["broken"])
+    .unwrap();
+  let mut contents = String::new();
+
+  let err = reader.read_to_string(&mut contents).unwrap_err();
+  let at = read_at_error(&err);
+
+  assert_eq!(at.line, 2);
+  assert_eq!(at.offset, 8);
+  assert_eq!(at.to_string(), "could not read argument #1 (broken) at line 2, byte 8\ncaused by: disk on fire");
+}
+
+#[test]
+fn test_read_error_after_rewind() {
+  let mut reader = InputBuilder::new()
+    .opener(fixtures)
+    .build(vec!["broken"])
+    .unwrap();
+  let mut contents = String::new();
+
+  assert!(reader.read_to_string(&mut contents).is_err());
+
+  reader.rewind().unwrap();
+  contents.clear();
+
+  let err = reader.read_to_string(&mut contents).unwrap_err();
+
+  assert_eq!(contents, "three\nfo");
+  assert_eq!(read_at_error(&err).line, 2);
+}
+
+#[test]
+fn test_open_errors_stay_open_errors() {
+  let mut reader = InputBuilder::new()
+    .opener(fixtures)
+    .lazy(true)
+    .build(vec!["missing"])
+    .unwrap();
+  let mut contents = String::new();
+
+  let err = reader.read_to_string(&mut contents).unwrap_err();
+
+  assert!(err.get_ref().unwrap().is::<FailReadFileError>());
+}
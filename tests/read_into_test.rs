@@ -0,0 +1,58 @@
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_read_next_line_into() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("no_newline"), attach_input_dir("crlf")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut line = String::from("left over");
+  let mut lines = Vec::new();
+
+  while all_input.read_next_line_into(&mut line).unwrap() {
+    lines.push(line.clone());
+  }
+
+  assert_eq!(lines, vec!["one", "two", "three", "no newlineone", "two", "mid\rline", "last\r"]);
+  assert!(line.is_empty());
+}
+
+#[test]
+fn test_read_next_line_into_reuses_buffer() {
+  let mut all_input = arg_input::input(vec![attach_input_dir("big")]).unwrap();
+  let mut line = String::with_capacity(4096);
+  let capacity = line.capacity();
+
+  while all_input.read_next_line_into(&mut line).unwrap() {}
+
+  assert_eq!(line.capacity(), capacity);
+}
+
+#[test]
+fn test_read_next_chunk_into() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut chunk = vec![b'x'; 10];
+  let mut chunks = Vec::new();
+
+  while all_input.read_next_chunk_into(&mut chunk, 4).unwrap() {
+    chunks.push(chunk.clone());
+  }
+
+  assert_eq!(chunks, vec![b"A\nB\n".to_vec(), b"C\nD\n".to_vec(), b"E\n".to_vec()]);
+  assert!(chunk.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_read_next_chunk_into_zero() {
+  let mut all_input = arg_input::input(vec![attach_input_dir("A")]).unwrap();
+
+  let _ = all_input.read_next_chunk_into(&mut Vec::new(), 0);
+}
@@ -0,0 +1,108 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::Read;
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, scratch_dir};
+
+#[test]
+fn test_recursive() {
+  let dir = scratch_dir("recursive");
+
+  fs::create_dir_all(dir.join("b/inner")).unwrap();
+  fs::create_dir_all(dir.join("empty")).unwrap();
+  fs::write(dir.join("c"), "c\n").unwrap();
+  fs::write(dir.join("a"), "a\n").unwrap();
+  fs::write(dir.join("b/inner/deep"), "deep\n").unwrap();
+  fs::write(dir.join("b/z"), "z\n").unwrap();
+
+  let mut all_input = InputBuilder::new()
+    .recursive(true)
+    .build(vec![dir.clone(), attach_input_dir("A")])
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "a\ndeep\nz\nc\nA\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_recursive_symlinks() {
+  use std::os::unix::fs::symlink;
+
+  let dir = scratch_dir("recursive_symlinks");
+
+  fs::create_dir_all(dir.join("sub")).unwrap();
+  fs::write(dir.join("sub/file"), "file\n").unwrap();
+  symlink(dir.join("sub/file"), dir.join("link")).unwrap();
+  symlink(&dir, dir.join("sub/loop")).unwrap();
+
+  let mut not_followed = String::new();
+  InputBuilder::new()
+    .recursive(true)
+    .build(vec![&dir])
+    .unwrap()
+    .read_to_string(&mut not_followed)
+    .unwrap();
+
+  assert_eq!(not_followed, "file\n");
+
+  let mut followed = String::new();
+  InputBuilder::new()
+    .recursive(true)
+    .follow_symlinks(true)
+    .build(vec![&dir])
+    .unwrap()
+    .read_to_string(&mut followed)
+    .unwrap();
+
+  assert_eq!(followed, "file\nfile\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_recursive_unreadable_entry() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let dir = scratch_dir("recursive_unreadable");
+  let locked = dir.join("b");
+
+  fs::create_dir_all(&locked).unwrap();
+  fs::write(dir.join("a"), "a\n").unwrap();
+  fs::write(locked.join("secret"), "secret\n").unwrap();
+  fs::write(dir.join("c"), "c\n").unwrap();
+  fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+
+  // Permissions don't stop root, so there's nothing to test then.
+  if fs::read_dir(&locked).is_ok() {
+    return;
+  }
+
+  let failed = InputBuilder::new().recursive(true).build(vec![&dir]);
+  let mut skipped = String::new();
+  InputBuilder::new()
+    .recursive(true)
+    .skip_missing(true)
+    .build(vec![&dir])
+    .unwrap()
+    .read_to_string(&mut skipped)
+    .unwrap();
+
+  fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+
+  match failed {
+    Ok(_) => panic!("expected the unreadable directory to fail"),
+    Err(err) => {
+      assert_eq!(err.badfiles.len(), 1);
+      assert_eq!(err.badfiles[0].os_filename, locked.into_os_string());
+      assert_eq!(err.badfiles[0].arg_index, 0);
+    }
+  }
+  assert_eq!(skipped, "a\nc\n");
+}
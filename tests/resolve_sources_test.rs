@@ -0,0 +1,57 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::path::PathBuf;
+
+use arg_input::{InputBuilder, ResolvedSource, SortKey};
+
+use inputs::{attach_input_dir, NONEXISTENT};
+
+#[test]
+fn test_resolve_sources() {
+  let filenames = vec![attach_input_dir("B"), PathBuf::from("-"), attach_input_dir(NONEXISTENT[0])];
+
+  let sources = arg_input::resolve_sources(filenames).unwrap();
+
+  assert_eq!(sources, vec![
+    ResolvedSource::File(attach_input_dir("B")),
+    ResolvedSource::Stdin,
+    ResolvedSource::File(attach_input_dir(NONEXISTENT[0]))
+  ]);
+}
+
+#[test]
+fn test_resolve_sources_no_inputs() {
+  let filenames: Vec<PathBuf> = Vec::new();
+
+  assert_eq!(arg_input::resolve_sources(filenames).unwrap(), vec![ResolvedSource::Stdin]);
+}
+
+#[test]
+fn test_resolve_sources_expanded() {
+  let filenames = vec![attach_input_dir("C").into_os_string(), "@./tests/inputs/list".into(), attach_input_dir("A").into_os_string()];
+
+  let sources = InputBuilder::new()
+    .response_files(true)
+    .dedup(true)
+    .sort(SortKey::Name)
+    .resolve(filenames)
+    .unwrap();
+
+  assert_eq!(sources, vec![
+    ResolvedSource::File(PathBuf::from("./tests/inputs/A")),
+    ResolvedSource::File(PathBuf::from("./tests/inputs/B")),
+    ResolvedSource::File(attach_input_dir("C"))
+  ]);
+}
+
+#[test]
+fn test_resolve_sources_expansion_error() {
+  let filenames = vec!["@./tests/inputs/Z", "./tests/inputs/A"];
+
+  let errs = InputBuilder::new().response_files(true).resolve(filenames).unwrap_err();
+
+  assert_eq!(errs.badfiles.len(), 1);
+  assert_eq!(errs.badfiles[0].filename, "@./tests/inputs/Z");
+}
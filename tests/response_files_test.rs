@@ -0,0 +1,60 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Read;
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_response_files() {
+  let filenames = vec![attach_input_dir("C").into_os_string(), "@./tests/inputs/list".into()];
+
+  let mut all_input = InputBuilder::new()
+    .response_files(true)
+    .build(filenames)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "C\nB\nA\n");
+}
+
+#[test]
+fn test_response_files_missing() {
+  let filenames = vec!["@./tests/inputs/Z", "./tests/inputs/A"];
+
+  match InputBuilder::new().response_files(true).build(filenames) {
+    Ok(_) => panic!("a missing response file should be an error"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles.len(), 1);
+      assert_eq!(errs.badfiles[0].arg_index, 0);
+      assert_eq!(errs.badfiles[0].filename, "@./tests/inputs/Z");
+    }
+  }
+}
+
+#[test]
+fn test_response_files_disabled() {
+  let filenames = vec!["@./tests/inputs/list"];
+
+  assert!(arg_input::input(filenames).is_err());
+}
+
+#[test]
+fn test_response_files_crlf() {
+  let filenames = vec!["@./tests/inputs/list_crlf", "./tests/inputs/C"];
+
+  let mut all_input = InputBuilder::new()
+    .response_files(true)
+    .build(filenames)
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "B\nA\nC\n");
+}
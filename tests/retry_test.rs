@@ -0,0 +1,63 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{self, Read};
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, scratch_dir};
+
+#[test]
+fn test_retry_until_file_appears() {
+  let dir = scratch_dir("retry_appears");
+  let path = dir.join("late");
+
+  let writer = {
+    let path = path.clone();
+    thread::spawn(move || {
+      thread::sleep(Duration::from_millis(50));
+      fs::write(path, "late\n").unwrap();
+    })
+  };
+
+  let mut reader = InputBuilder::new()
+    .retry(10, Duration::from_millis(20))
+    .retry_on(vec![io::ErrorKind::NotFound])
+    .build(vec![&path])
+    .unwrap();
+  writer.join().unwrap();
+
+  let mut contents = String::new();
+  reader.read_to_string(&mut contents).unwrap();
+
+  assert_eq!(contents, "late\n");
+}
+
+#[test]
+fn test_retry_not_transient() {
+  let start = Instant::now();
+
+  let built = InputBuilder::new()
+    .retry(5, Duration::from_secs(1))
+    .build(vec![attach_input_dir("Z")]);
+
+  match built {
+    Ok(_) => panic!("expected the file to be missing"),
+    Err(err) => assert_eq!(err.badfiles[0].inner.kind(), io::ErrorKind::NotFound)
+  }
+  assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn test_retry_gives_up() {
+  let built = InputBuilder::new()
+    .retry(2, Duration::from_millis(1))
+    .retry_on(vec![io::ErrorKind::NotFound])
+    .build(vec![attach_input_dir("Z")]);
+
+  assert!(built.is_err());
+}
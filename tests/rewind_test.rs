@@ -0,0 +1,52 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_rewind() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut first_pass = String::new();
+  let mut second_pass = String::new();
+
+  all_input.read_to_string(&mut first_pass).unwrap();
+  all_input.rewind().unwrap();
+  all_input.read_to_string(&mut second_pass).unwrap();
+
+  assert_eq!(first_pass, "A\nB\nC\nD\nE\n");
+  assert_eq!(first_pass, second_pass);
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir(INPUTS[4]).as_path()));
+}
+
+#[test]
+fn test_rewind_lazy() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input_lazy(filenames);
+  let mut buf = [0; 2];
+
+  all_input.read_exact(&mut buf).unwrap();
+  all_input.rewind().unwrap();
+
+  let mut result_string = String::new();
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\n");
+}
+
+#[test]
+fn test_rewind_stdin() {
+  let filenames = vec![attach_input_dir("A"), PathBuf::from("-")];
+
+  let mut all_input = arg_input::input_with_stdin(filenames, Box::new(Cursor::new("stdin"))).unwrap();
+
+  assert!(all_input.rewind().is_err());
+}
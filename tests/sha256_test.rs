@@ -0,0 +1,47 @@
+#![cfg(feature = "sha256")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+fn hex(hash: [u8; 32]) -> String {
+  hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[test]
+fn test_input_sha256() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let hash = arg_input::input_sha256(filenames).unwrap();
+
+  assert_eq!(hex(hash), "8b1583da45bf9454a0078483f6f76dcf62929b57cc95031d5b74a0734a9a0ba6");
+}
+
+#[test]
+fn test_input_sha256_empty() {
+  let hash = arg_input::input_sha256(vec![attach_input_dir("empty")]).unwrap();
+
+  assert_eq!(hex(hash), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+}
+
+#[test]
+fn test_input_sha256_big() {
+  let filenames = vec![attach_input_dir("big"), attach_input_dir("three_lines")];
+
+  let hash = arg_input::input_sha256(filenames).unwrap();
+
+  assert_eq!(hex(hash), "13e50ade21c26c2da41a4d4c8d0539cfe44148fbc344c03b0ccedfc143e8d6c4");
+}
+
+#[test]
+fn test_input_sha256_missing() {
+  let filenames = NONEXISTENT.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  assert!(arg_input::input_sha256(filenames).is_err());
+}
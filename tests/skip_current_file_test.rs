@@ -0,0 +1,44 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{BufRead, Read};
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_skip_current_file() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("A"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut line = String::new();
+
+  all_input.read_line(&mut line).unwrap();
+  assert_eq!(line, "one\n");
+
+  all_input.skip_current_file();
+  assert_eq!(all_input.current_filename(), Some(attach_input_dir("A").as_path()));
+
+  let mut rest = String::new();
+  all_input.read_to_string(&mut rest).unwrap();
+
+  assert_eq!(rest, "A\nB\n");
+}
+
+#[test]
+fn test_skip_last_file() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("three_lines")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut buf = [0; 3];
+
+  all_input.read_exact(&mut buf).unwrap();
+  assert_eq!(&buf, b"A\no");
+
+  all_input.skip_current_file();
+
+  let mut rest = Vec::new();
+  all_input.read_to_end(&mut rest).unwrap();
+
+  assert!(rest.is_empty());
+}
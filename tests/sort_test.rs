@@ -0,0 +1,59 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{Cursor, Read};
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use arg_input::{InputBuilder, SortKey};
+
+use inputs::{attach_input_dir, scratch_dir};
+
+fn read_all(builder: InputBuilder, filenames: Vec<PathBuf>) -> String {
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+  let mut all_input = builder.build_with_stdin(filenames, stdin).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  result_string
+}
+
+#[test]
+fn test_sort_name() {
+  let filenames = vec![attach_input_dir("C"), attach_input_dir("A"), attach_input_dir("B")];
+
+  assert_eq!(read_all(InputBuilder::new().sort(SortKey::Name), filenames), "A\nB\nC\n");
+}
+
+#[test]
+fn test_sort_size() {
+  let filenames = vec![attach_input_dir("three_lines"), attach_input_dir("empty"), attach_input_dir("A")];
+
+  assert_eq!(read_all(InputBuilder::new().sort(SortKey::Size), filenames), "A\none\ntwo\nthree\n");
+}
+
+#[test]
+fn test_sort_modified_time() {
+  let dir = scratch_dir("sort_modified_time");
+  let now = SystemTime::now();
+
+  for (name, age) in &[("newest", 0), ("oldest", 200), ("middle", 100)] {
+    let path = dir.join(name);
+    fs::write(&path, format!("{}\n", name)).unwrap();
+    File::options().write(true).open(&path).unwrap().set_modified(now - Duration::from_secs(*age)).unwrap();
+  }
+
+  let filenames = vec![dir.join("newest"), dir.join("oldest"), dir.join("middle")];
+
+  assert_eq!(read_all(InputBuilder::new().sort(SortKey::ModifiedTime), filenames), "oldest\nmiddle\nnewest\n");
+}
+
+#[test]
+fn test_sort_keeps_stdin_in_place() {
+  let filenames = vec![attach_input_dir("B"), PathBuf::from("-"), attach_input_dir("C"), attach_input_dir("A")];
+
+  assert_eq!(read_all(InputBuilder::new().sort(SortKey::Name), filenames), "A\nfrom stdin\nB\nC\n");
+}
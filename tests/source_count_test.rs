@@ -0,0 +1,38 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use inputs::{attach_input_dir, INPUTS, NONEXISTENT};
+
+#[test]
+fn test_source_count() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let all_input = arg_input::input(filenames).unwrap();
+
+  assert_eq!(all_input.source_count(), INPUTS.len());
+}
+
+#[test]
+fn test_source_count_stdin() {
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+  let all_input = arg_input::input_with_stdin(Vec::<PathBuf>::new(), stdin).unwrap();
+
+  assert_eq!(all_input.source_count(), 1);
+}
+
+#[test]
+fn test_source_count_skipping() {
+  let mut filenames = vec![attach_input_dir("A")];
+  filenames.extend(NONEXISTENT.iter().map(|str| attach_input_dir(str)));
+
+  let (all_input, errors) = arg_input::input_skipping(filenames);
+
+  assert_eq!(all_input.source_count(), 1);
+  assert_eq!(errors.len(), NONEXISTENT.len());
+}
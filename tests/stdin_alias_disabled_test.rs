@@ -0,0 +1,73 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{self, Cursor, Read};
+use std::path::PathBuf;
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, scratch_dir};
+
+fn stdin() -> Box<Cursor<&'static str>> {
+  Box::new(Cursor::new("from stdin\n"))
+}
+
+#[test]
+fn test_dot_slash_dash_is_a_file() {
+  match InputBuilder::new().build_with_stdin(vec!["./-"], stdin()) {
+    Ok(_) => panic!("`./-` should be opened as a file"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles[0].filename, "./-");
+      assert_eq!(errs.badfiles[0].inner.kind(), io::ErrorKind::NotFound);
+    }
+  }
+}
+
+#[test]
+fn test_dot_slash_dash_reads_file() {
+  let dir = scratch_dir("dot_slash_dash");
+  std::fs::write(dir.join("-"), "literally dash\n").unwrap();
+
+  let mut all_input = InputBuilder::new().build_with_stdin(vec![dir.join(".").join("-")], stdin()).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "literally dash\n");
+}
+
+#[test]
+fn test_stdin_alias_disabled() {
+  let filenames = vec![attach_input_dir("A"), PathBuf::from("-")];
+
+  match InputBuilder::new().stdin_alias_disabled(true).build_with_stdin(filenames, stdin()) {
+    Ok(_) => panic!("`-` should be opened as a file"),
+    Err(errs) => {
+      assert_eq!(errs.badfiles.len(), 1);
+      assert_eq!(errs.badfiles[0].arg_index, 1);
+      assert_eq!(errs.badfiles[0].filename, "-");
+    }
+  }
+}
+
+#[test]
+fn test_stdin_alias_disabled_other_aliases() {
+  let filenames = vec!["/dev/stdin"];
+
+  let builder = InputBuilder::new().dev_stdin(true).stdin_alias_disabled(true);
+
+  assert_eq!(builder.resolve(filenames).unwrap(), vec![arg_input::ResolvedSource::File(PathBuf::from("/dev/stdin"))]);
+}
+
+#[test]
+fn test_stdin_alias_disabled_no_arguments() {
+  let filenames: Vec<PathBuf> = Vec::new();
+
+  let mut all_input = InputBuilder::new().stdin_alias_disabled(true).build_with_stdin(filenames, stdin()).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "from stdin\n");
+}
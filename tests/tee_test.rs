@@ -0,0 +1,151 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::{self, BufRead, Read, Write};
+use std::sync::{Arc, Mutex};
+
+use arg_input::InputBuilder;
+
+use inputs::{attach_input_dir, INPUTS};
+
+/// A writer that can still be looked at after it's been handed over.
+#[derive(Clone, Default)]
+struct Shared(Arc<Mutex<Vec<u8>>>);
+
+impl Shared {
+  fn contents(&self) -> String {
+    String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+  }
+}
+
+impl Write for Shared {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.0.lock().unwrap().write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+struct Broken;
+
+impl Write for Broken {
+  fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+    Err(io::Error::other("disk full"))
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+fn filenames() -> Vec<::std::path::PathBuf> {
+  INPUTS.iter().map(|str| attach_input_dir(str)).collect()
+}
+
+#[test]
+fn test_tee() {
+  let copy = Shared::default();
+
+  let mut all_input = InputBuilder::new().tee(Box::new(copy.clone())).build(filenames()).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\nC\nD\nE\n");
+  assert_eq!(copy.contents(), result_string);
+}
+
+#[test]
+fn test_tee_only_what_was_read() {
+  let copy = Shared::default();
+
+  let mut all_input = InputBuilder::new().tee(Box::new(copy.clone())).build(filenames()).unwrap();
+  let mut line = String::new();
+
+  assert_eq!(all_input.peek(4).unwrap(), b"A\nB\n");
+  assert_eq!(copy.contents(), "");
+
+  all_input.read_line(&mut line).unwrap();
+  line.clear();
+  all_input.read_line(&mut line).unwrap();
+
+  assert_eq!(copy.contents(), "A\nB\n");
+}
+
+#[test]
+fn test_tee_large_reads() {
+  let copy = Shared::default();
+
+  let mut all_input = InputBuilder::new()
+    .buffer_capacity(4)
+    .tee(Box::new(copy.clone()))
+    .build(vec![attach_input_dir("big")])
+    .unwrap();
+  let mut result = Vec::new();
+  let mut buf = [0; 1024];
+
+  loop {
+    let read = all_input.read(&mut buf).unwrap();
+    if read == 0 {
+      break;
+    }
+    result.extend_from_slice(&buf[..read]);
+  }
+
+  assert_eq!(copy.contents().into_bytes(), result);
+}
+
+#[test]
+fn test_tee_with_banners() {
+  let copy = Shared::default();
+
+  let mut all_input = InputBuilder::new()
+    .with_banners(|_| "==> banner <==".to_string())
+    .tee(Box::new(copy.clone()))
+    .build(vec![attach_input_dir("A"), attach_input_dir("B")])
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(copy.contents(), result_string);
+}
+
+#[test]
+fn test_tee_errors() {
+  let mut all_input = InputBuilder::new().tee(Box::new(Broken)).build(filenames()).unwrap();
+  let mut result_string = String::new();
+
+  let err = all_input.read_to_string(&mut result_string).unwrap_err();
+
+  assert_eq!(err.to_string(), "disk full");
+}
+
+#[test]
+fn test_tee_errors_from_the_next_read() {
+  for &size in &[2, 64 * 1024] {
+    let mut all_input = InputBuilder::new().tee(Box::new(Broken)).build(filenames()).unwrap();
+    let mut buf = vec![0; size];
+
+    assert_eq!(all_input.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"A\n");
+    assert_eq!(all_input.read(&mut buf).unwrap_err().to_string(), "disk full");
+  }
+}
+
+#[test]
+fn test_tee_errors_ignored() {
+  let mut all_input = InputBuilder::new()
+    .tee(Box::new(Broken))
+    .ignore_tee_errors(true)
+    .build(filenames())
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\nB\nC\nD\nE\n");
+}
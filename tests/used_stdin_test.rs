@@ -0,0 +1,35 @@
+extern crate arg_input;
+
+mod inputs;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use inputs::{attach_input_dir, INPUTS};
+
+#[test]
+fn test_used_stdin_files_only() {
+  let filenames = INPUTS.iter().map(|str| {
+    attach_input_dir(str)
+  });
+
+  let all_input = arg_input::input(filenames).unwrap();
+
+  assert!(!all_input.used_stdin());
+}
+
+#[test]
+fn test_used_stdin_fallback() {
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+  let all_input = arg_input::input_with_stdin(Vec::<PathBuf>::new(), stdin).unwrap();
+
+  assert!(all_input.used_stdin());
+}
+
+#[test]
+fn test_used_stdin_alias() {
+  let stdin = Box::new(Cursor::new("from stdin\n"));
+  let all_input = arg_input::input_with_stdin(vec![attach_input_dir("A"), PathBuf::from("-")], stdin).unwrap();
+
+  assert!(all_input.used_stdin());
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "zstd")]
+
+extern crate arg_input;
+
+mod inputs;
+
+use std::fs;
+use std::io::{Cursor, Read};
+
+use arg_input::InputBuilder;
+
+use inputs::attach_input_dir;
+
+#[test]
+fn test_zstd() {
+  let filenames = vec![attach_input_dir("A"), attach_input_dir("compressed.zst"), attach_input_dir("B")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\ncompressed\nB\n");
+}
+
+#[test]
+fn test_zstd_large() {
+  let filenames = vec![attach_input_dir("big.zst")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result = Vec::new();
+
+  all_input.read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, fs::read(attach_input_dir("big")).unwrap());
+}
+
+#[test]
+fn test_zstd_multiple_frames() {
+  let filenames = vec![attach_input_dir("multi.zst")];
+
+  let mut all_input = arg_input::input_lazy(filenames);
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "first\nsecond\n");
+}
+
+#[test]
+fn test_zstd_files_decoded_separately() {
+  let filenames = vec![attach_input_dir("compressed.zst"), attach_input_dir("multi.zst"), attach_input_dir("compressed.zst")];
+
+  let mut all_input = arg_input::input(filenames).unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\nfirst\nsecond\ncompressed\n");
+}
+
+#[test]
+fn test_zstd_corrupt() {
+  let mut compressed = fs::read(attach_input_dir("big.zst")).unwrap();
+  let len = compressed.len();
+  compressed[len - 1] ^= 0xFF;
+
+  let mut all_input = InputBuilder::new()
+    .zstd_stdin(true)
+    .build_with_stdin(["-"], Box::new(Cursor::new(compressed)))
+    .unwrap();
+  let mut result = Vec::new();
+
+  assert!(all_input.read_to_end(&mut result).is_err());
+}
+
+#[test]
+fn test_zstd_stdin() {
+  let compressed = fs::read(attach_input_dir("compressed.zst")).unwrap();
+
+  let raw = arg_input::input_with_stdin(["-"], Box::new(Cursor::new(compressed.clone())));
+  let mut result = Vec::new();
+
+  raw.unwrap().read_to_end(&mut result).unwrap();
+
+  assert_eq!(result, compressed);
+
+  let decompressed = InputBuilder::new()
+    .zstd_stdin(true)
+    .build_with_stdin(["-"], Box::new(Cursor::new(compressed)));
+  let mut result_string = String::new();
+
+  decompressed.unwrap().read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "compressed\n");
+}
+
+#[test]
+fn test_zstd_auto_decompress() {
+  let compressed = fs::read(attach_input_dir("compressed.zst")).unwrap();
+
+  let mut all_input = InputBuilder::new()
+    .auto_decompress(true)
+    .build_with_stdin(vec![attach_input_dir("A"), "-".into()], Box::new(Cursor::new(compressed)))
+    .unwrap();
+  let mut result_string = String::new();
+
+  all_input.read_to_string(&mut result_string).unwrap();
+
+  assert_eq!(result_string, "A\ncompressed\n");
+}
+
+#[test]
+fn test_zstd_no_progress() {
+  let all_input = arg_input::input(vec![attach_input_dir("A"), attach_input_dir("compressed.zst")]).unwrap();
+
+  assert_eq!(all_input.progress(), None);
+}